@@ -0,0 +1,39 @@
+use crate::components::Component;
+use egui::{Grid, Ui};
+use lmvc8_core::console::components::dma::DMA;
+
+pub struct DMASnapshotDisplay<'a> {
+    snapshot: &'a DMA,
+}
+
+impl<'a> DMASnapshotDisplay<'a> {
+    pub fn new(snapshot: &'a DMA) -> Self {
+        Self { snapshot }
+    }
+}
+
+impl Component for DMASnapshotDisplay<'_> {
+    fn ui(self, ui: &mut Ui) {
+        ui.style_mut().override_font_id = Some(egui::FontId::monospace(14.0));
+
+        Grid::new("dma_snapshot_display_grid")
+            .striped(true)
+            .num_columns(4)
+            .show(ui, |ui| {
+                ui.label("DMA");
+                ui.label(if self.snapshot.is_active() {
+                    "BUSY"
+                } else {
+                    "IDLE"
+                });
+                ui.label("LEN");
+                ui.label(format!("{}", self.snapshot.length));
+                ui.end_row();
+
+                ui.label("SRC");
+                ui.label(format!("{}", self.snapshot.source));
+                ui.label("DST");
+                ui.label(format!("{}", self.snapshot.destination));
+            });
+    }
+}
@@ -45,6 +45,7 @@ impl Component for ROMDisplay<'_> {
             .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
             .column(Column::auto().at_least(15.0))
             .column(Column::auto().at_least(50.0)) // Address column
+            .column(Column::auto().at_least(60.0)) // Label column
             .column(Column::auto().at_least(80.0))
             .column(Column::remainder())
             .body(|body| {
@@ -61,6 +62,12 @@ impl Component for ROMDisplay<'_> {
                         ui.label(format!("0x{:04X}", row_index));
                     });
 
+                    row.col(|ui| {
+                        if let Some(label) = self.disassembled_binary.label_at(row_index as u16) {
+                            ui.strong(format!("{label}:"));
+                        }
+                    });
+
                     row.col(|ui| {
                         if let Some(node) = self.disassembled_binary.nodes().get(row_index) {
                             ui.label(node.to_string());
@@ -3,10 +3,12 @@ use lmvc8_core::console::cartridge::Cartridge;
 use lmvc8_core::console::components::cpu::registers::R8;
 
 pub fn build_cartridge() -> Cartridge {
-    let binary = Compiler::new()
+    Compiler::new()
+        .label("main")
         .load_r8i(R8::A, 12)
         .load_r8i(R8::B, 13)
         .add_r8(R8::B)
-        .compile();
-    Cartridge::new(binary)
+        .compile_cartridge()
+        .expect("The demo compiles")
+        .with_title("Simple Add")
 }
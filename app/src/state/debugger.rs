@@ -1,12 +1,22 @@
 use crate::demos::Demo;
 use crate::state::debugger::action::{DebuggerAction, DebuggerActionContext};
+use lmvc8_core::compiler::assembler::Assembler;
+use lmvc8_core::compiler::lang::Frontend;
+use lmvc8_core::console::cartridge::rom_image::RomImageFormat;
 use lmvc8_core::console::cartridge::Cartridge;
+use lmvc8_core::console::components::bus::memory_map::MemoryRegion;
 use lmvc8_core::console::components::cpu::CPU;
+use lmvc8_core::console::components::dma::DMA;
+use lmvc8_core::console::components::rtc::ClockSource;
+use lmvc8_core::console::components::watchdog::WatchdogAction;
+use lmvc8_core::console::config::ConsoleConfig;
 use lmvc8_core::console::types::address::Address;
 use lmvc8_core::disassembler::{DisassembledBinary, Disassembler};
+use lmvc8_core::emulator::command::RngSeed;
 use lmvc8_core::emulator::event::EmulatorEvent;
 use lmvc8_core::emulator::Emulator;
 use std::collections::HashSet;
+use std::path::PathBuf;
 
 pub mod action;
 
@@ -15,30 +25,43 @@ pub struct DebuggerState {
     emulator: Emulator,
     debugger_action_context: DebuggerActionContext,
     pub cpu_snapshot: CPU,
+    pub dma_snapshot: DMA,
+    pub memory_layout: Vec<MemoryRegion>,
+    pub console_config: ConsoleConfig,
     pub is_running: bool,
     pub is_halting: bool,
     pub cycles_per_second: u64,
+    pub rng_seed: RngSeed,
+    pub rtc_source: ClockSource,
     pub last_frame_mics: u64,
     pub last_frame_cycles: u64,
     pub disassembled_binary: DisassembledBinary,
     pub breakpoints: HashSet<Address>,
+    pub debug_log: String,
 }
 
 impl DebuggerState {
     pub fn update(&mut self) {
         self.emulator.with_state(|state| {
             self.cpu_snapshot = state.cpu_snapshot;
+            self.dma_snapshot = state.dma_snapshot;
+            self.console_config = state.console_config;
+            if self.memory_layout != state.memory_layout {
+                self.memory_layout = state.memory_layout.clone();
+            }
             self.is_running = state.is_running;
             self.is_halting = state.is_halting;
             self.cycles_per_second = state.cycles_per_second;
+            self.rng_seed = state.rng_seed;
+            self.rtc_source = state.rtc_source;
             self.last_frame_mics = state.last_frame_mics;
             self.last_frame_cycles = state.last_frame_cycles;
             self.breakpoints = state.breakpoints.clone();
         });
 
-        if let Some(event) = self.emulator.poll_event() {
+        while let Some(event) = self.emulator.poll_event() {
             self.handle_event(event);
-        };
+        }
 
         let debugger_actions = self
             .debugger_action_context
@@ -73,6 +96,14 @@ impl DebuggerState {
         self.emulator.set_clock_speed(cycles_per_second);
     }
 
+    pub fn set_rng_seed(&self, seed: RngSeed) {
+        self.emulator.set_rng_seed(seed);
+    }
+
+    pub fn set_rtc_source(&self, source: ClockSource) {
+        self.emulator.set_rtc_source(source);
+    }
+
     pub fn format_clock_speed(&self) -> String {
         if self.cycles_per_second < 1_000 {
             format!("{} Hz", self.cycles_per_second)
@@ -89,15 +120,95 @@ impl DebuggerState {
         self.emulator.set_breakpoint(address.into());
     }
 
+    pub fn set_breakpoint_by_name(&self, name: String) {
+        self.emulator.set_breakpoint_by_name(name);
+    }
+
     pub fn remove_breakpoint(&self, address: u16) {
         self.emulator.remove_breakpoint(address.into());
     }
 
     pub fn load_cartridge(&mut self, cartridge: Cartridge) {
-        self.disassembled_binary = Disassembler::new(&cartridge.binary).disassemble();
+        self.disassembled_binary = Disassembler::new(&cartridge.binary)
+            .with_symbols(cartridge.symbols.as_ref())
+            .disassemble();
         self.emulator.load_cartridge(cartridge);
     }
 
+    pub fn clear_debug_log(&mut self) {
+        self.debug_log.clear();
+    }
+
+    pub fn load_cartridge_file(&mut self, path: PathBuf) {
+        if let Ok(cartridge) = Cartridge::load_from_file(&path) {
+            self.load_cartridge(cartridge);
+        }
+    }
+
+    pub fn load_patched_cartridge_file(&mut self, path: PathBuf, patch_path: PathBuf) {
+        let Ok(mut cartridge) = Cartridge::load_from_file(&path) else {
+            return;
+        };
+        match cartridge.apply_patch_file(&patch_path) {
+            Ok(_) => self.load_cartridge(cartridge),
+            Err(err) => self
+                .debug_log
+                .push_str(&format!("\n[Patch not applied: {err}]\n")),
+        }
+    }
+
+    pub fn load_rom_image_file(&mut self, path: PathBuf, format: RomImageFormat) {
+        if let Ok(cartridge) = Cartridge::load_rom_image_file(&path, format) {
+            self.load_cartridge(cartridge);
+        }
+    }
+
+    pub fn load_assembly_file(&mut self, path: PathBuf) {
+        let Ok(assembler) = Assembler::from_file(&path) else {
+            return;
+        };
+        let cartridge = assembler
+            .assemble()
+            .map_err(|err| err.to_string())
+            .and_then(|compiler| compiler.compile_cartridge().map_err(|err| err.to_string()));
+        match cartridge {
+            Ok(cartridge) => self.load_cartridge(cartridge),
+            Err(err) => self
+                .debug_log
+                .push_str(&format!("\n[Assembly failed: {err}]\n")),
+        }
+    }
+
+    pub fn load_lang_file(&mut self, path: PathBuf) {
+        let Ok(frontend) = Frontend::from_file(&path) else {
+            return;
+        };
+        let cartridge = frontend
+            .compile()
+            .map_err(|err| err.to_string())
+            .and_then(|compiler| compiler.compile_cartridge().map_err(|err| err.to_string()));
+        match cartridge {
+            Ok(cartridge) => self.load_cartridge(cartridge),
+            Err(err) => self
+                .debug_log
+                .push_str(&format!("\n[Compilation failed: {err}]\n")),
+        }
+    }
+
+    pub fn load_raw_binary_file(&mut self, path: PathBuf) {
+        if let Ok(cartridge) = Cartridge::load_raw_from_file(&path) {
+            self.load_cartridge(cartridge);
+        }
+    }
+
+    pub fn export_save_ram(&self, path: PathBuf) {
+        self.emulator.export_save_ram(path);
+    }
+
+    pub fn import_save_ram(&self, path: PathBuf) {
+        self.emulator.import_save_ram(path);
+    }
+
     pub fn load_demo(&mut self, demo: Demo) {
         let cartridge = demo.build_cartridge();
         self.load_cartridge(cartridge);
@@ -107,6 +218,25 @@ impl DebuggerState {
         match event {
             EmulatorEvent::CartridgeLoadSuccess => {}
             EmulatorEvent::CartridgeLoadFailed => {}
+            EmulatorEvent::DebugOutput(output) => self.debug_log.push_str(&output),
+            EmulatorEvent::SaveRamFlushFailed => {}
+            EmulatorEvent::SaveRamExportFailed => {}
+            EmulatorEvent::SaveRamExportSuccess => {}
+            EmulatorEvent::SaveRamImportFailed => {}
+            EmulatorEvent::SaveRamImportSuccess => {}
+            EmulatorEvent::UnknownSymbol(name) => {
+                self.debug_log
+                    .push_str(&format!("\n[No label or function named '{name}']\n"));
+            }
+            EmulatorEvent::WatchdogExpired { action, pc } => {
+                let action = match action {
+                    WatchdogAction::Reset => "reset",
+                    WatchdogAction::Trap => "trap",
+                };
+                let pc = u16::from(pc);
+                self.debug_log
+                    .push_str(&format!("\n[Watchdog {action} at {pc:04X}]\n"));
+            }
             EmulatorEvent::Shutdown(_) => {}
         }
     }
@@ -1,5 +1,6 @@
 use crate::components::clock_speed_edit::ClockSpeedEdit;
 use crate::components::cpu_snapshot_display::CPUSnapshotDisplay;
+use crate::components::dma_snapshot_display::DMASnapshotDisplay;
 use crate::components::rom_display::ROMDisplay;
 use crate::components::window_button::WindowButton;
 use crate::components::window_renderer::WindowRenderer;
@@ -7,26 +8,90 @@ use crate::components::Component;
 use crate::demos::Demo;
 use crate::state::AppState;
 use crate::views::{View, ViewID};
+use crate::windows::debug_log::DebugLogWindow;
+use crate::windows::memory_map::MemoryMapWindow;
 use crate::windows::settings::SettingsWindow;
-use egui::{Context, MenuBar, SidePanel, TopBottomPanel, Ui, Widget};
+use egui::{Context, MenuBar, SidePanel, TopBottomPanel, Ui};
+use lmvc8_core::compiler::assembler::Assembler;
+use lmvc8_core::compiler::lang::Frontend;
+use lmvc8_core::console::cartridge::patch::PatchFormat;
+use lmvc8_core::console::cartridge::rom_image::RomImageFormat;
+use lmvc8_core::console::cartridge::Cartridge;
+use lmvc8_core::console::components::rtc::ClockSource;
+use lmvc8_core::emulator::command::RngSeed;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct DebuggerView {
     settings_window: SettingsWindow,
+    debug_log_window: DebugLogWindow,
+    memory_map_window: MemoryMapWindow,
+    #[serde(skip)]
+    breakpoint_name: String,
 }
 
 impl DebuggerView {
     fn render_top_menu(&mut self, ui: &mut Ui, state: &mut AppState) {
         ui.menu_button("ROM", |ui| {
             ui.menu_button("Load", |ui| {
-                if ui.button("From File").clicked() {};
+                if ui.button("From File").clicked()
+                    && let Some(path) = cartridge_file_dialog().pick_file()
+                {
+                    state.debugger.load_cartridge_file(path);
+                };
+                if ui.button("From File with Patch").clicked()
+                    && let Some(path) = cartridge_file_dialog().pick_file()
+                    && let Some(patch_path) = patch_file_dialog().pick_file()
+                {
+                    state.debugger.load_patched_cartridge_file(path, patch_path);
+                };
+                if ui.button("From Intel HEX / S-record").clicked()
+                    && let Some(path) = rom_image_file_dialog().pick_file()
+                    && let Some(format) = path
+                        .extension()
+                        .and_then(|extension| extension.to_str())
+                        .and_then(RomImageFormat::from_extension)
+                {
+                    state.debugger.load_rom_image_file(path, format);
+                };
+                if ui.button("From Assembly Source").clicked()
+                    && let Some(path) = rfd::FileDialog::new()
+                        .add_filter("LMVC8 Assembly", &[Assembler::EXTENSION])
+                        .pick_file()
+                {
+                    state.debugger.load_assembly_file(path);
+                };
+                if ui.button("From LM8 Source").clicked()
+                    && let Some(path) = rfd::FileDialog::new()
+                        .add_filter("LM8 Source", &[Frontend::EXTENSION])
+                        .pick_file()
+                {
+                    state.debugger.load_lang_file(path);
+                };
+                if ui.button("From Raw Binary").clicked()
+                    && let Some(path) = rfd::FileDialog::new().pick_file()
+                {
+                    state.debugger.load_raw_binary_file(path);
+                };
                 ui.menu_button("From DEMO", |ui| {
                     if ui.button("Simple Add").clicked() {
                         state.debugger.load_demo(Demo::SimpleAdd);
                     };
                 })
             });
+
+            ui.menu_button("Save RAM", |ui| {
+                if ui.button("Export").clicked()
+                    && let Some(path) = save_file_dialog().save_file()
+                {
+                    state.debugger.export_save_ram(path);
+                };
+                if ui.button("Import").clicked()
+                    && let Some(path) = save_file_dialog().pick_file()
+                {
+                    state.debugger.import_save_ram(path);
+                };
+            });
         });
 
         ui.separator();
@@ -39,6 +104,34 @@ impl DebuggerView {
                 )
                 .ui(ui);
             });
+            ui.menu_button("RNG Seed", |ui| {
+                let fixed = RngSeed::default();
+                if ui
+                    .radio(state.debugger.rng_seed == fixed, "Fixed")
+                    .clicked()
+                {
+                    state.debugger.set_rng_seed(fixed);
+                }
+                if ui
+                    .radio(state.debugger.rng_seed == RngSeed::Host, "From Host")
+                    .clicked()
+                {
+                    state.debugger.set_rng_seed(RngSeed::Host);
+                }
+            });
+            ui.menu_button("RTC", |ui| {
+                for (source, label) in [
+                    (ClockSource::Host, "Host Clock"),
+                    (ClockSource::Virtual, "Virtual Clock"),
+                ] {
+                    if ui
+                        .radio(state.debugger.rtc_source == source, label)
+                        .clicked()
+                    {
+                        state.debugger.set_rtc_source(source);
+                    }
+                }
+            });
         });
     }
 
@@ -72,6 +165,21 @@ impl DebuggerView {
 
         ui.separator();
 
+        DMASnapshotDisplay::new(&state.debugger.dma_snapshot).ui(ui);
+
+        ui.separator();
+
+        if state.debugger.disassembled_binary.symbols().is_some() {
+            ui.horizontal(|ui| {
+                ui.text_edit_singleline(&mut self.breakpoint_name);
+                if ui.button("⏺ Label").clicked() && !self.breakpoint_name.is_empty() {
+                    state
+                        .debugger
+                        .set_breakpoint_by_name(std::mem::take(&mut self.breakpoint_name));
+                }
+            });
+        }
+
         ROMDisplay::new(
             &state.debugger.disassembled_binary,
             &state.debugger.breakpoints,
@@ -82,10 +190,37 @@ impl DebuggerView {
     }
 }
 
+fn cartridge_file_dialog() -> rfd::FileDialog {
+    rfd::FileDialog::new().add_filter("LMVC8 Cartridge", &[Cartridge::EXTENSION])
+}
+
+fn patch_file_dialog() -> rfd::FileDialog {
+    let extensions = PatchFormat::ALL.map(|format| format.extension());
+    rfd::FileDialog::new().add_filter("IPS/BPS Patch", &extensions)
+}
+
+fn rom_image_file_dialog() -> rfd::FileDialog {
+    RomImageFormat::ALL
+        .iter()
+        .fold(rfd::FileDialog::new(), |dialog, format| {
+            let name = match format {
+                RomImageFormat::IntelHex => "Intel HEX",
+                RomImageFormat::SRec => "S-record",
+            };
+            dialog.add_filter(name, format.extensions())
+        })
+}
+
+fn save_file_dialog() -> rfd::FileDialog {
+    rfd::FileDialog::new().add_filter("Save RAM", &[Cartridge::SAVE_EXTENSION])
+}
+
 impl View for DebuggerView {
     fn render(&mut self, ctx: &Context, state: &mut AppState) {
         WindowRenderer::new(ctx, state)
             .window(&mut self.settings_window)
+            .window(&mut self.debug_log_window)
+            .window(&mut self.memory_map_window)
             .render();
 
         TopBottomPanel::top("debugger_top_panel").show(ctx, |ui| {
@@ -96,6 +231,8 @@ impl View for DebuggerView {
                 ui.separator();
 
                 WindowButton::new(&mut self.settings_window, " 🛠 ").ui(ui);
+                WindowButton::new(&mut self.debug_log_window, " 🗒 ").ui(ui);
+                WindowButton::new(&mut self.memory_map_window, " 🗺 ").ui(ui);
                 ui.separator();
 
                 ui.label("Debugger");
@@ -2,6 +2,8 @@ use crate::state::AppState;
 use crate::views::View;
 use egui::{Context, Id, Ui, WidgetText};
 
+pub mod debug_log;
+pub mod memory_map;
 pub mod settings;
 
 pub trait ViewWindow: Default {
@@ -0,0 +1,43 @@
+use crate::state::AppState;
+use crate::windows::ViewWindow;
+use egui::{Id, ScrollArea, Ui, WidgetText};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DebugLogWindow {
+    open: bool,
+}
+
+impl ViewWindow for DebugLogWindow {
+    fn id(&self) -> Id {
+        Id::new("debug_log_window")
+    }
+
+    fn title(&self) -> impl Into<WidgetText> {
+        "Debug Output"
+    }
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn set_open(&mut self, open: bool) {
+        self.open = open;
+    }
+
+    fn render_content(&mut self, ui: &mut Ui, state: &mut AppState) {
+        if ui.button("Clear").clicked() {
+            state.debugger.clear_debug_log();
+        }
+
+        ui.separator();
+
+        ui.style_mut().override_font_id = Some(egui::FontId::monospace(14.0));
+        ScrollArea::vertical()
+            .auto_shrink(false)
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                ui.label(&state.debugger.debug_log);
+            });
+    }
+}
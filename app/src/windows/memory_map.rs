@@ -0,0 +1,57 @@
+use crate::state::AppState;
+use crate::windows::ViewWindow;
+use egui::{Grid, Id, Ui, WidgetText};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct MemoryMapWindow {
+    open: bool,
+}
+
+impl ViewWindow for MemoryMapWindow {
+    fn id(&self) -> Id {
+        Id::new("memory_map_window")
+    }
+
+    fn title(&self) -> impl Into<WidgetText> {
+        "Memory Map"
+    }
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn set_open(&mut self, open: bool) {
+        self.open = open;
+    }
+
+    fn render_content(&mut self, ui: &mut Ui, state: &mut AppState) {
+        let config = &state.debugger.console_config;
+        ui.label(format!(
+            "{} hardware, {} KiB RAM",
+            config.profile,
+            config.ram_size / 1024
+        ));
+        ui.separator();
+
+        ui.style_mut().override_font_id = Some(egui::FontId::monospace(14.0));
+        Grid::new("memory_map_grid")
+            .striped(true)
+            .num_columns(4)
+            .show(ui, |ui| {
+                ui.strong("Range");
+                ui.strong("Size");
+                ui.strong("Name");
+                ui.strong("Device");
+                ui.end_row();
+
+                for region in &state.debugger.memory_layout {
+                    ui.label(format!("{:04X}-{:04X}", region.start, region.end));
+                    ui.label(format!("{:#X}", region.size()));
+                    ui.label(&region.name);
+                    ui.label(region.device.to_string());
+                    ui.end_row();
+                }
+            });
+    }
+}
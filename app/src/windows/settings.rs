@@ -27,7 +27,7 @@ impl ViewWindow for SettingsWindow {
     }
 
     fn render_content(&mut self, ui: &mut Ui, state: &mut AppState) {
-        let mut ui_scale = state.settings_mut().get_ui_scale();
+        let mut ui_scale = state.settings().get_ui_scale();
         egui::ComboBox::from_id_salt("ui_scale")
             .selected_text(ui_scale.to_string())
             .show_ui(ui, |ui| {
@@ -1,27 +1,207 @@
-use crate::compiler::node::Node;
+use crate::compiler::char_map::CharMap;
+use crate::compiler::error::{CompileError, CompileErrorKind, CompileResult, NodeError};
+use crate::compiler::node::{Node, NodeType, SymbolNode};
+use crate::console::cartridge::symbols::{DebugSymbols, Label, SourceLocation, SymbolRange};
+use crate::console::cartridge::Cartridge;
 use crate::console::components::cpu::instructions::CPUInstruction;
+use crate::console::components::rom::ROM_SIZE;
+use crate::console::config::HardwareProfile;
+use std::collections::HashMap;
 
+pub mod assembler;
+pub mod char_map;
+pub mod error;
+pub mod lang;
 mod layers;
-mod node;
+pub mod node;
+pub mod optimizer;
+pub mod variables;
+
+/// The data region `align` pads with, the optimizer keeps the data after it in place
+const ALIGN_DATA: &str = "align";
 
 #[derive(Debug, Default)]
 pub struct Compiler {
     nodes: Vec<Node>,
     push_position: u16,
+    /// Fills the gaps between nodes, defaults to 0x00 (NOP)
+    fill_byte: u8,
+    /// Encodes the strings of the data helpers
+    char_map: CharMap,
+    /// Numbers the labels generated for control flow
+    label_count: usize,
+    /// The loops being built, the innermost is last
+    loops: Vec<LoopLabels>,
+    /// The return labels of the functions being built, the innermost is last
+    functions: Vec<String>,
+    /// The node holding the interrupts the startup code enables
+    interrupt_enable: Option<usize>,
+    /// The RAM allocated for variables, in address order
+    variables: Vec<SymbolRange>,
+    /// The hardware the program is built for, bounds the RAM of variables
+    profile: HardwareProfile,
+}
+
+/// The labels `continue_loop` and `break_loop` jump to
+#[derive(Debug)]
+struct LoopLabels {
+    continue_label: String,
+    break_label: String,
+}
+
+pub struct CompilationContext {
+    symbols: SymbolCollector,
+    labels: HashMap<String, u16>,
+}
+
+/// The binary being laid out, remembers which bytes nodes already wrote
+struct Layout {
+    data: Vec<u8>,
+    written: Vec<bool>,
+    fill_byte: u8,
 }
 
-pub struct CompilationContext<'a> {
-    data: &'a mut Vec<u8>,
+impl Layout {
+    fn new(fill_byte: u8) -> Self {
+        Self {
+            data: Vec::new(),
+            written: Vec::new(),
+            fill_byte,
+        }
+    }
+
+    fn place(&mut self, address: u16, bytes: &[u8]) -> Result<(), CompileErrorKind> {
+        if bytes.is_empty() {
+            return Ok(());
+        }
+        let start = address as usize;
+        let end = start + bytes.len();
+        if end > u16::MAX as usize + 1 {
+            return Err(CompileErrorKind::PositionOverflow(address));
+        }
+        if end > ROM_SIZE {
+            return Err(CompileErrorKind::ROMSizeExceeded(end));
+        }
+        if let Some(offset) = self
+            .written
+            .get(start..end.min(self.written.len()))
+            .and_then(|written| written.iter().position(|&written| written))
+        {
+            return Err(CompileErrorKind::Overlap(address + offset as u16));
+        }
+        if self.data.len() < end {
+            self.data.resize(end, self.fill_byte);
+            self.written.resize(end, false);
+        }
+        self.data[start..end].copy_from_slice(bytes);
+        self.written[start..end].fill(true);
+        Ok(())
+    }
+}
+
+/// Builds the debug symbols from the symbol nodes, pairing range starts with their ends
+#[derive(Debug, Default)]
+struct SymbolCollector {
+    symbols: DebugSymbols,
+    open_functions: Vec<(String, u16)>,
+    open_data: Vec<(String, u16)>,
+}
+
+impl SymbolCollector {
+    fn push(&mut self, address: u16, symbol: &SymbolNode) {
+        match symbol {
+            SymbolNode::Label(name) => self.symbols.labels.push(Label {
+                name: name.clone(),
+                address,
+            }),
+            SymbolNode::FunctionStart(name) => self.open_functions.push((name.clone(), address)),
+            SymbolNode::FunctionEnd => {
+                if let Some(range) = Self::close(&mut self.open_functions, address) {
+                    self.symbols.functions.push(range);
+                }
+            }
+            SymbolNode::DataStart(name) => self.open_data.push((name.clone(), address)),
+            SymbolNode::DataEnd => {
+                if let Some(range) = Self::close(&mut self.open_data, address) {
+                    self.symbols.data.push(range);
+                }
+            }
+            SymbolNode::Source(source) => self.symbols.sources.push(SourceLocation {
+                address,
+                ..source.clone()
+            }),
+        }
+    }
+
+    /// Ranges without any bytes are dropped
+    fn close(open: &mut Vec<(String, u16)>, end: u16) -> Option<SymbolRange> {
+        let (name, start) = open.pop()?;
+        (end > start).then(|| SymbolRange {
+            name,
+            start,
+            end: end - 1,
+        })
+    }
 }
 
 impl Compiler {
-    pub fn compile(self) -> Vec<u8> {
-        let mut data = Vec::new();
-        let mut context = CompilationContext { data: &mut data };
-        self.push_instruction(CPUInstruction::Halt)
-            .nodes
-            .iter()
-            .for_each(|node| node.compile(&mut context));
-        data
+    /// Compiles only the binary, the debug symbols are dropped.\
+    /// Use [`Compiler::compile_cartridge`] or [`Compiler::compile_with_symbols`] to keep them.
+    #[track_caller]
+    pub fn compile(self) -> CompileResult<Vec<u8>> {
+        Ok(self.compile_with_symbols()?.0)
+    }
+
+    /// Compiles the binary along with the debug symbols recorded by the builder
+    #[track_caller]
+    pub fn compile_with_symbols(self) -> CompileResult<(Vec<u8>, DebugSymbols)> {
+        let compiler = self.push_instruction(CPUInstruction::Halt);
+        let mut errors = Vec::new();
+        let mut layout = Layout::new(compiler.fill_byte);
+        let mut context = CompilationContext {
+            symbols: SymbolCollector::default(),
+            labels: compiler.resolve_labels(&mut errors),
+        };
+        for (index, node) in compiler.nodes.iter().enumerate() {
+            if let Err(kind) = node
+                .compile(&mut context)
+                .and_then(|bytes| layout.place(node.address, &bytes))
+            {
+                errors.push(node.error(index, kind));
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(CompileError { errors });
+        }
+        let mut symbols = context.symbols.symbols;
+        symbols.variables = compiler.variables;
+        Ok((layout.data, symbols))
+    }
+
+    /// Compiles a cartridge holding the binary and, if any were recorded, the debug symbols
+    #[track_caller]
+    pub fn compile_cartridge(self) -> CompileResult<Cartridge> {
+        let profile = self.profile;
+        let (binary, symbols) = self.compile_with_symbols()?;
+        let cartridge = Cartridge::new(binary).with_profile(profile);
+        Ok(if symbols.is_empty() {
+            cartridge
+        } else {
+            cartridge.with_symbols(symbols)
+        })
+    }
+
+    /// Collects the address of every label, so label words can refer to labels defined later
+    fn resolve_labels(&self, errors: &mut Vec<NodeError>) -> HashMap<String, u16> {
+        let mut labels = HashMap::new();
+        for (index, node) in self.nodes.iter().enumerate() {
+            if let NodeType::Symbol(SymbolNode::Label(name)) = &node.node_type
+                && labels.insert(name.clone(), node.address).is_some()
+            {
+                errors.push(node.error(index, CompileErrorKind::DuplicateLabel(name.clone())));
+            }
+        }
+        labels
     }
 }
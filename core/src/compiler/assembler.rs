@@ -0,0 +1,664 @@
+use crate::compiler::error::{AssemblyError, AssemblyResult};
+use crate::compiler::Compiler;
+use crate::console::components::cpu::instructions::{CPUInstruction, Condition};
+use crate::console::components::cpu::registers::{R16, R16S, R8};
+use crate::error::LMVC8Result;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// The file name used in errors for source that wasn't read from a file
+const DEFAULT_FILE_NAME: &str = "<source>";
+
+/// Lowers LMVC8 assembly source to compiler nodes.
+///
+/// Instructions use the mnemonics of the disassembly (`LD A, B`, `PUSH AF`, `ADD HL`, `JP NZ, loop`).
+/// A bare `HL` is the register pair, except when loading to or from an 8-bit register,
+/// `[HL]` always addresses the memory at HL.\
+/// Lines can start with a `label:`, comments start with `;`.
+/// The directives are `.org address`, `.db` bytes or strings, `.dw` words or labels and `.include "file"`.
+pub struct Assembler {
+    source: String,
+    path: Option<PathBuf>,
+}
+
+impl Assembler {
+    pub const EXTENSION: &'static str = "asm";
+
+    pub fn new(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            path: None,
+        }
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> LMVC8Result<Self> {
+        let source = std::fs::read_to_string(path.as_ref())?;
+        Ok(Self::new(source).with_path(path))
+    }
+
+    /// The path shows up in errors, includes are relative to its directory
+    pub fn with_path(mut self, path: impl AsRef<Path>) -> Self {
+        self.path = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    pub fn assemble(self) -> AssemblyResult<Compiler> {
+        let mut state = AssemblyState::default();
+        state.assemble_source(&self.source, self.path.as_deref())?;
+        state.check_references()?;
+        Ok(state.compiler)
+    }
+}
+
+/// Where a token starts, lines and columns start at 1
+#[derive(Debug, Clone)]
+struct Position {
+    file: String,
+    line: usize,
+    column: usize,
+}
+
+impl Position {
+    fn at(&self, token: &Token) -> Self {
+        Self {
+            column: token.column,
+            ..self.clone()
+        }
+    }
+
+    fn error(&self, message: impl Into<String>) -> AssemblyError {
+        AssemblyError {
+            file: self.file.clone(),
+            line: self.line,
+            column: self.column,
+            message: message.into(),
+        }
+    }
+}
+
+/// A piece of a line along with the column it starts at
+#[derive(Debug, Clone, Copy)]
+struct Token<'a> {
+    text: &'a str,
+    column: usize,
+}
+
+impl<'a> Token<'a> {
+    fn trim(self) -> Self {
+        let trimmed = self.text.trim_start();
+        Self {
+            text: trimmed.trim_end(),
+            column: self.column + self.text.len() - trimmed.len(),
+        }
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let (left, right) = self.text.split_at(index);
+        (
+            Self {
+                text: left,
+                column: self.column,
+            },
+            Self {
+                text: right,
+                column: self.column + index,
+            },
+        )
+    }
+
+    /// Splits off a leading `label:`
+    fn split_label(self) -> Option<(Self, Self)> {
+        let index = self.text.find(':')?;
+        let (label, rest) = self.split_at(index);
+        is_identifier(label.text).then(|| (label, rest.split_at(1).1.trim()))
+    }
+
+    /// Splits off the mnemonic or directive
+    fn split_word(self) -> (Self, Self) {
+        let index = self
+            .text
+            .find(char::is_whitespace)
+            .unwrap_or(self.text.len());
+        let (word, rest) = self.split_at(index);
+        (word, rest.trim())
+    }
+
+    /// Splits the operands at commas outside of strings
+    fn split_operands(self, position: &Position) -> AssemblyResult<Vec<Self>> {
+        if self.text.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut operands = Vec::new();
+        let mut rest = self;
+        while let Some(index) = find_outside_quotes(rest.text, ',') {
+            let (operand, next) = rest.split_at(index);
+            operands.push(operand.trim());
+            rest = next.split_at(1).1;
+        }
+        operands.push(rest.trim());
+
+        match operands.iter().find(|operand| operand.text.is_empty()) {
+            Some(empty) => Err(position.at(empty).error("missing operand")),
+            None => Ok(operands),
+        }
+    }
+}
+
+fn is_identifier(text: &str) -> bool {
+    let mut chars = text.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_' || first == '.')
+        && chars.all(|char| char.is_ascii_alphanumeric() || char == '_' || char == '.')
+}
+
+/// The byte index of the first unquoted occurrence of the character
+fn find_outside_quotes(text: &str, target: char) -> Option<usize> {
+    let mut quote = None;
+    let mut escaped = false;
+    for (index, char) in text.char_indices() {
+        match quote {
+            Some(_) if escaped => escaped = false,
+            Some(_) if char == '\\' => escaped = true,
+            Some(open) if char == open => quote = None,
+            Some(_) => {}
+            None if char == target => return Some(index),
+            None if char == '"' || char == '\'' => quote = Some(char),
+            None => {}
+        }
+    }
+    None
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Register {
+    R8(R8),
+    /// Both the register pair and, next to an 8-bit register, the memory at HL
+    HL,
+    /// `[HL]`
+    IndirectHL,
+    R16(R16),
+    AF,
+}
+
+impl Register {
+    fn parse(text: &str) -> Option<Self> {
+        let register = match text.to_ascii_uppercase().as_str() {
+            "A" => Self::R8(R8::A),
+            "B" => Self::R8(R8::B),
+            "C" => Self::R8(R8::C),
+            "D" => Self::R8(R8::D),
+            "E" => Self::R8(R8::E),
+            "H" => Self::R8(R8::H),
+            "L" => Self::R8(R8::L),
+            "HL" => Self::HL,
+            "[HL]" => Self::IndirectHL,
+            "BC" => Self::R16(R16::BC),
+            "DE" => Self::R16(R16::DE),
+            "SP" => Self::R16(R16::SP),
+            "AF" => Self::AF,
+            _ => return None,
+        };
+        Some(register)
+    }
+
+    fn r8(self) -> Option<R8> {
+        match self {
+            Self::R8(r8) => Some(r8),
+            Self::IndirectHL => Some(R8::HL),
+            _ => None,
+        }
+    }
+
+    fn r16(self) -> Option<R16> {
+        match self {
+            Self::R16(r16) => Some(r16),
+            Self::HL => Some(R16::HL),
+            _ => None,
+        }
+    }
+
+    fn r16s(self) -> Option<R16S> {
+        match self {
+            Self::AF => Some(R16S::AF),
+            Self::R16(R16::BC) => Some(R16S::BC),
+            Self::R16(R16::DE) => Some(R16S::DE),
+            Self::HL => Some(R16S::HL),
+            _ => None,
+        }
+    }
+}
+
+/// A number or a label in place of an address
+enum Value {
+    Number(i64),
+    Label(String),
+}
+
+/// The operand following an instruction
+enum Immediate {
+    None,
+    Byte(u8),
+    Word(Value),
+}
+
+#[derive(Default)]
+struct AssemblyState {
+    compiler: Compiler,
+    labels: HashMap<String, Position>,
+    references: Vec<(String, Position)>,
+    /// The files currently being assembled, to catch circular includes
+    includes: Vec<PathBuf>,
+}
+
+impl AssemblyState {
+    fn emit(&mut self, f: impl FnOnce(Compiler) -> Compiler) {
+        self.compiler = f(std::mem::take(&mut self.compiler));
+    }
+
+    fn assemble_source(&mut self, source: &str, path: Option<&Path>) -> AssemblyResult<()> {
+        let file = path.map_or(DEFAULT_FILE_NAME.to_string(), |path| {
+            path.display().to_string()
+        });
+        for (index, text) in source.lines().enumerate() {
+            let position = Position {
+                file: file.clone(),
+                line: index + 1,
+                column: 1,
+            };
+            self.assemble_line(text, &position, path)?;
+        }
+        Ok(())
+    }
+
+    fn assemble_line(
+        &mut self,
+        text: &str,
+        position: &Position,
+        path: Option<&Path>,
+    ) -> AssemblyResult<()> {
+        let code = &text[..find_outside_quotes(text, ';').unwrap_or(text.len())];
+        let mut line = Token {
+            text: code,
+            column: 1,
+        }
+        .trim();
+
+        if let Some((label, rest)) = line.split_label() {
+            self.define_label(label.text, position.at(&label))?;
+            line = rest;
+        }
+        if line.text.is_empty() {
+            return Ok(());
+        }
+
+        let (word, operands) = line.split_word();
+        let operands = operands.split_operands(position)?;
+        let position = position.at(&word);
+        match word.text.strip_prefix('.') {
+            Some(directive) => self.directive(directive, &operands, &position, path),
+            None => self.instruction(word.text, &operands, &position),
+        }
+    }
+
+    fn define_label(&mut self, name: &str, position: Position) -> AssemblyResult<()> {
+        if Register::parse(name).is_some() {
+            return Err(position.error(format!("'{name}' is a register and can't be a label")));
+        }
+        if let Some(previous) = self.labels.get(name) {
+            return Err(position.error(format!(
+                "label '{name}' is already defined at {}:{}",
+                previous.file, previous.line
+            )));
+        }
+        self.labels.insert(name.to_string(), position);
+        let name = name.to_string();
+        self.emit(|c| c.label(name));
+        Ok(())
+    }
+
+    fn check_references(&self) -> AssemblyResult<()> {
+        match self
+            .references
+            .iter()
+            .find(|(name, _)| !self.labels.contains_key(name))
+        {
+            Some((name, position)) => Err(position.error(format!("undefined label '{name}'"))),
+            None => Ok(()),
+        }
+    }
+
+    fn directive(
+        &mut self,
+        directive: &str,
+        operands: &[Token],
+        position: &Position,
+        path: Option<&Path>,
+    ) -> AssemblyResult<()> {
+        match directive.to_ascii_lowercase().as_str() {
+            "org" => {
+                let [address] = expect_operands(operands, position)?;
+                let address = word(number(&address, position)?, &position.at(&address))?;
+                self.emit(|c| c.set_push_position(address));
+            }
+            "db" => {
+                if operands.is_empty() {
+                    return Err(position.error("expected at least one byte"));
+                }
+                let mut data = Vec::new();
+                for operand in operands {
+                    if operand.text.starts_with('"') {
+                        data.extend(string(operand, position)?);
+                    } else {
+                        data.push(byte(number(operand, position)?, &position.at(operand))?);
+                    }
+                }
+                self.emit(|c| c.push_data(data));
+            }
+            "dw" => {
+                if operands.is_empty() {
+                    return Err(position.error("expected at least one word"));
+                }
+                for operand in operands {
+                    match self.value(operand, position)? {
+                        Value::Number(value) => {
+                            let value = word(value, &position.at(operand))?;
+                            self.emit(|c| c.push_word(value));
+                        }
+                        Value::Label(label) => self.emit(|c| c.push_label_word(label)),
+                    }
+                }
+            }
+            "include" => {
+                let [file] = expect_operands(operands, position)?;
+                let file_position = position.at(&file);
+                let file = String::from_utf8(string(&file, position)?)
+                    .map_err(|_| file_position.error("the path isn't valid UTF-8"))?;
+                let include_path = match path.and_then(Path::parent) {
+                    Some(directory) => directory.join(file),
+                    None => PathBuf::from(file),
+                };
+                self.include(&include_path, &file_position)?;
+            }
+            _ => return Err(position.error(format!("unknown directive '.{directive}'"))),
+        }
+        Ok(())
+    }
+
+    fn include(&mut self, path: &Path, position: &Position) -> AssemblyResult<()> {
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        if self.includes.contains(&canonical) {
+            return Err(position.error(format!("'{}' includes itself", path.display())));
+        }
+        let source = std::fs::read_to_string(path)
+            .map_err(|err| position.error(format!("can't include '{}': {err}", path.display())))?;
+
+        self.includes.push(canonical);
+        let result = self.assemble_source(&source, Some(path));
+        self.includes.pop();
+        result
+    }
+
+    fn instruction(
+        &mut self,
+        mnemonic: &str,
+        operands: &[Token],
+        position: &Position,
+    ) -> AssemblyResult<()> {
+        let mnemonic = mnemonic.to_ascii_uppercase();
+        let (instruction, immediate) = match mnemonic.as_str() {
+            "NOP" | "HLT" | "HALT" | "EI" | "DI" | "RET" | "RETI" => {
+                let [] = expect_operands(operands, position)?;
+                let instruction = match mnemonic.as_str() {
+                    "NOP" => CPUInstruction::NoOp,
+                    "HLT" | "HALT" => CPUInstruction::Halt,
+                    "EI" => CPUInstruction::EnableInterrupts,
+                    "DI" => CPUInstruction::DisableInterrupts,
+                    "RETI" => CPUInstruction::ReturnInterrupt,
+                    _ => CPUInstruction::Return,
+                };
+                (instruction, Immediate::None)
+            }
+            "ADD" | "SUB" | "INC" | "DEC" => {
+                let [operand] = expect_operands(operands, position)?;
+                let register = register(&operand, position)?;
+                let instruction = match (mnemonic.as_str(), register.r8(), register.r16()) {
+                    ("ADD", Some(r8), _) => CPUInstruction::AddR8(r8),
+                    ("ADD", _, Some(r16)) => CPUInstruction::AddR16(r16),
+                    ("SUB", Some(r8), _) => CPUInstruction::SubR8(r8),
+                    ("SUB", _, Some(r16)) => CPUInstruction::SubR16(r16),
+                    ("INC", Some(r8), _) => CPUInstruction::IncR8(r8),
+                    ("INC", _, Some(r16)) => CPUInstruction::IncR16(r16),
+                    ("DEC", Some(r8), _) => CPUInstruction::DecR8(r8),
+                    ("DEC", _, Some(r16)) => CPUInstruction::DecR16(r16),
+                    _ => {
+                        return Err(position
+                            .at(&operand)
+                            .error("AF can only be pushed and popped"))
+                    }
+                };
+                (instruction, Immediate::None)
+            }
+            "PUSH" | "POP" => {
+                let [operand] = expect_operands(operands, position)?;
+                let r16s = register(&operand, position)?
+                    .r16s()
+                    .ok_or_else(|| position.at(&operand).error("expected AF, BC, DE or HL"))?;
+                let instruction = match mnemonic.as_str() {
+                    "PUSH" => CPUInstruction::Push(r16s),
+                    _ => CPUInstruction::Pop(r16s),
+                };
+                (instruction, Immediate::None)
+            }
+            "JP" if operands.len() == 2 => {
+                let [condition, target] = expect_operands(operands, position)?;
+                let condition = Condition::ALL
+                    .into_iter()
+                    .find(|candidate| candidate.to_string().eq_ignore_ascii_case(condition.text))
+                    .ok_or_else(|| position.at(&condition).error("expected Z, NZ, C or NC"))?;
+                (
+                    CPUInstruction::JumpIf(condition),
+                    Immediate::Word(self.value(&target, position)?),
+                )
+            }
+            "CALL" | "JP" => {
+                let [operand] = expect_operands(operands, position)?;
+                let instruction = match mnemonic.as_str() {
+                    "CALL" => CPUInstruction::Call,
+                    _ => CPUInstruction::Jump,
+                };
+                (
+                    instruction,
+                    Immediate::Word(self.value(&operand, position)?),
+                )
+            }
+            "LD" => {
+                let [target, source] = expect_operands(operands, position)?;
+                self.load(&target, &source, position)?
+            }
+            _ => return Err(position.error(format!("unknown instruction '{mnemonic}'"))),
+        };
+
+        u8::try_from(instruction).map_err(|err| position.error(err))?;
+        let location = (
+            position.file.clone(),
+            position.line as u32,
+            position.column as u32,
+        );
+        self.emit(|c| {
+            let c = c
+                .source_location(location.0, location.1, location.2)
+                .push_instruction(instruction);
+            match immediate {
+                Immediate::None => c,
+                Immediate::Byte(value) => c.push_byte(value),
+                Immediate::Word(Value::Number(value)) => c.push_word(value as u16),
+                Immediate::Word(Value::Label(label)) => c.push_label_word(label),
+            }
+        });
+        Ok(())
+    }
+
+    fn load(
+        &mut self,
+        target: &Token,
+        source: &Token,
+        position: &Position,
+    ) -> AssemblyResult<(CPUInstruction, Immediate)> {
+        let target_register = register(target, position)?;
+        let source_register = Register::parse(source.text);
+        let as_r8 = |register: Register| match register {
+            Register::HL => Some(R8::HL),
+            register => register.r8(),
+        };
+
+        Ok(match (target_register, source_register) {
+            (target, Some(source)) if target.r8().is_some() || source.r8().is_some() => {
+                match (as_r8(target), as_r8(source)) {
+                    (Some(target), Some(source)) => {
+                        (CPUInstruction::LoadR8((target, source)), Immediate::None)
+                    }
+                    _ => return Err(position.error("can't load between 8 and 16-bit registers")),
+                }
+            }
+            (target, Some(source)) => match (target.r16(), source.r16()) {
+                (Some(target), Some(source)) => {
+                    (CPUInstruction::LoadR16((target, source)), Immediate::None)
+                }
+                _ => return Err(position.error("AF can only be pushed and popped")),
+            },
+            (register, None) => {
+                if let Some(r8) = register.r8() {
+                    let value = byte(number(source, position)?, &position.at(source))?;
+                    (CPUInstruction::LoadR8i(r8), Immediate::Byte(value))
+                } else if let Some(r16) = register.r16() {
+                    (
+                        CPUInstruction::LoadR16i(r16),
+                        Immediate::Word(self.value(source, position)?),
+                    )
+                } else {
+                    return Err(position
+                        .at(target)
+                        .error("AF can only be pushed and popped"));
+                }
+            }
+        })
+    }
+
+    /// A number that fits a word or a label reference
+    fn value(&mut self, token: &Token, position: &Position) -> AssemblyResult<Value> {
+        if is_identifier(token.text) && Register::parse(token.text).is_none() {
+            self.references
+                .push((token.text.to_string(), position.at(token)));
+            return Ok(Value::Label(token.text.to_string()));
+        }
+        let value = number(token, position)?;
+        word(value, &position.at(token))?;
+        Ok(Value::Number(value))
+    }
+}
+
+fn expect_operands<'a, const N: usize>(
+    operands: &[Token<'a>],
+    position: &Position,
+) -> AssemblyResult<[Token<'a>; N]> {
+    operands
+        .try_into()
+        .map_err(|_| position.error(format!("expected {N} operand(s), found {}", operands.len())))
+}
+
+fn register(token: &Token, position: &Position) -> AssemblyResult<Register> {
+    Register::parse(token.text).ok_or_else(|| {
+        position
+            .at(token)
+            .error(format!("expected a register, found '{}'", token.text))
+    })
+}
+
+/// Parses decimal, `0x`/`$` hexadecimal, `0b`/`%` binary and `'c'` character numbers
+fn number(token: &Token, position: &Position) -> AssemblyResult<i64> {
+    let error = || {
+        position
+            .at(token)
+            .error(format!("invalid number '{}'", token.text))
+    };
+    let (negative, text) = match token.text.strip_prefix('-') {
+        Some(text) => (true, text),
+        None => (false, token.text),
+    };
+
+    // from_str_radix would also take a sign, but only one in front of the prefix is allowed
+    let digits = |digits: &str, radix: u32| {
+        if digits.is_empty() || !digits.chars().all(|char| char.is_digit(radix)) {
+            return Err(error());
+        }
+        i64::from_str_radix(digits, radix).map_err(|_| error())
+    };
+    let value = if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix('$')) {
+        digits(hex, 16)?
+    } else if let Some(binary) = text.strip_prefix("0b").or_else(|| text.strip_prefix('%')) {
+        digits(binary, 2)?
+    } else if text.starts_with('\'') {
+        let bytes = unescape(text, '\'').ok_or_else(error)?;
+        match bytes[..] {
+            [byte] => byte as i64,
+            _ => return Err(error()),
+        }
+    } else {
+        digits(text, 10)?
+    };
+    if negative {
+        value.checked_neg().ok_or_else(error)
+    } else {
+        Ok(value)
+    }
+}
+
+fn byte(value: i64, position: &Position) -> AssemblyResult<u8> {
+    match value {
+        0..=0xFF => Ok(value as u8),
+        -0x80..0 => Ok(value as i8 as u8),
+        _ => Err(position.error(format!("{value} doesn't fit into a byte"))),
+    }
+}
+
+fn word(value: i64, position: &Position) -> AssemblyResult<u16> {
+    match value {
+        0..=0xFFFF => Ok(value as u16),
+        -0x8000..0 => Ok(value as i16 as u16),
+        _ => Err(position.error(format!("{value} doesn't fit into a word"))),
+    }
+}
+
+fn string(token: &Token, position: &Position) -> AssemblyResult<Vec<u8>> {
+    unescape(token.text, '"').ok_or_else(|| {
+        position
+            .at(token)
+            .error(format!("invalid string {}", token.text))
+    })
+}
+
+/// The bytes of a quoted string with `\n`, `\r`, `\t`, `\0`, `\\` and quote escapes
+fn unescape(text: &str, quote: char) -> Option<Vec<u8>> {
+    let inner = text.strip_prefix(quote)?.strip_suffix(quote)?;
+    let mut bytes = Vec::new();
+    let mut chars = inner.chars();
+    while let Some(char) = chars.next() {
+        let char = match char {
+            '\\' => match chars.next()? {
+                'n' => '\n',
+                'r' => '\r',
+                't' => '\t',
+                '0' => '\0',
+                escaped @ ('\\' | '"' | '\'') => escaped,
+                _ => return None,
+            },
+            char if char == quote => return None,
+            char => char,
+        };
+        let mut buffer = [0; 4];
+        bytes.extend_from_slice(char.encode_utf8(&mut buffer).as_bytes());
+    }
+    Some(bytes)
+}
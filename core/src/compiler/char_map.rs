@@ -0,0 +1,53 @@
+use std::collections::HashMap;
+
+/// Encodes the characters of strings embedded by the compiler, defaults to ASCII
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CharMap {
+    map: HashMap<char, u8>,
+}
+
+impl Default for CharMap {
+    fn default() -> Self {
+        Self::ascii()
+    }
+}
+
+impl CharMap {
+    /// Maps no characters
+    pub fn empty() -> Self {
+        Self {
+            map: HashMap::new(),
+        }
+    }
+
+    /// Maps the ASCII characters to themselves
+    pub fn ascii() -> Self {
+        Self {
+            map: (0..0x80u8).map(|byte| (byte as char, byte)).collect(),
+        }
+    }
+
+    pub fn with(mut self, char: char, byte: u8) -> Self {
+        self.map.insert(char, byte);
+        self
+    }
+
+    /// Maps consecutive characters to the bytes from `first` on, like a font in tile order
+    pub fn with_sequence(mut self, chars: &str, first: u8) -> Self {
+        for (char, byte) in chars.chars().zip(first..=u8::MAX) {
+            self.map.insert(char, byte);
+        }
+        self
+    }
+
+    pub fn get(&self, char: char) -> Option<u8> {
+        self.map.get(&char).copied()
+    }
+
+    /// Encodes the text, or returns the first character that isn't mapped
+    pub fn encode(&self, text: &str) -> Result<Vec<u8>, char> {
+        text.chars()
+            .map(|char| self.get(char).ok_or(char))
+            .collect()
+    }
+}
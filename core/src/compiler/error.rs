@@ -0,0 +1,98 @@
+use crate::compiler::lang::Span;
+use crate::console::components::rom::ROM_SIZE;
+use std::fmt::{Display, Formatter};
+use std::panic::Location;
+use thiserror::Error;
+
+pub type CompileResult<T> = Result<T, CompileError>;
+pub type AssemblyResult<T> = Result<T, AssemblyError>;
+pub type LangResult<T> = Result<T, LangError>;
+
+/// Every problem found while compiling, compilation continues past the first one
+#[derive(Debug, Error, PartialEq, Eq)]
+pub struct CompileError {
+    pub errors: Vec<NodeError>,
+}
+
+impl CompileError {
+    pub fn kinds(&self) -> impl Iterator<Item = &CompileErrorKind> {
+        self.errors.iter().map(|error| &error.kind)
+    }
+}
+
+impl Display for CompileError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let count = self.errors.len();
+        write!(f, "Compilation failed with {count} error(s)")?;
+        for error in &self.errors {
+            write!(f, "\n{error}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A problem with a single node
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("{location}: node {node}: {kind}")]
+pub struct NodeError {
+    pub kind: CompileErrorKind,
+    /// The index of the node in the order it was pushed
+    pub node: usize,
+    /// The builder call that pushed the node
+    pub location: &'static Location<'static>,
+}
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum CompileErrorKind {
+    #[error("Label '{0}' is defined more than once")]
+    DuplicateLabel(String),
+    #[error("Label '{0}' is referenced but never defined")]
+    UndefinedLabel(String),
+    #[error("'{0}' outside of a loop")]
+    OutsideLoop(&'static str),
+    #[error("'return' outside of a function")]
+    OutsideFunction,
+    #[error("Variable '{0}' is allocated more than once")]
+    DuplicateVariable(String),
+    #[error("Variable '{0}' doesn't fit in the RAM left for variables")]
+    OutOfRAM(String),
+    #[error("Invalid operand: {0}")]
+    InvalidOperand(String),
+    #[error("Character {0:?} isn't in the character map")]
+    UnmappedCharacter(char),
+    #[error("The string is {0} bytes long, but the length prefix holds at most 255")]
+    StringTooLong(usize),
+    #[error("Can't include '{path}': {reason}")]
+    IncludeFailed { path: String, reason: String },
+    #[error("Interrupt handlers are for a single interrupt, not 0b{0:08b}")]
+    InvalidInterrupt(u8),
+    #[error("Invalid instruction: {0}")]
+    InvalidInstruction(String),
+    #[error("The node at 0x{0:04X} extends past the end of the address space")]
+    PositionOverflow(u16),
+    #[error("Address 0x{0:04X} is written by more than one node")]
+    Overlap(u16),
+    #[error("The binary needs {0} bytes, but the ROM only holds {ROM_SIZE}")]
+    ROMSizeExceeded(usize),
+}
+
+/// A problem in assembly source, lines and columns start at 1
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("{file}:{line}:{column}: {message}")]
+pub struct AssemblyError {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+/// A problem in a program of the high-level language, the span covers the offending source
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("{file}:{line}:{column}: {message}")]
+pub struct LangError {
+    pub file: String,
+    pub span: Span,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
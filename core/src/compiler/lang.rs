@@ -0,0 +1,121 @@
+use crate::compiler::error::{LangError, LangResult};
+use crate::compiler::Compiler;
+use crate::error::LMVC8Result;
+use std::path::{Path, PathBuf};
+
+pub mod ast;
+mod checker;
+mod codegen;
+mod lexer;
+mod parser;
+
+/// The file name used in errors for source that wasn't read from a file
+const DEFAULT_FILE_NAME: &str = "<source>";
+
+/// Lowers programs in a small typed language to compiler nodes.
+///
+/// Programs are made of global `let name: u8 = 0;` variables and functions like
+/// `fn add(a: u8, b: u8) -> u8 { return a + b; }`, execution starts at `fn main()`.
+/// Statements are `let`, assignments, `if`/`else`, `while`, `return` and calls,
+/// expressions are `+`, `-`, casts with `as` and the intrinsics `peek(address)` and `poke(address, value)`.
+/// Comparisons (`==`, `!=`, `<`, `<=`, `>`, `>=`) are only allowed as conditions, comments start with `//`.
+///
+/// Every variable, including parameters and locals, lives at a fixed address in RAM,
+/// so functions can't call themselves, not even indirectly.
+pub struct Frontend {
+    source: String,
+    path: Option<PathBuf>,
+}
+
+impl Frontend {
+    pub const EXTENSION: &'static str = "lm8";
+
+    pub fn new(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            path: None,
+        }
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> LMVC8Result<Self> {
+        let source = std::fs::read_to_string(path.as_ref())?;
+        Ok(Self::new(source).with_path(path))
+    }
+
+    /// The path shows up in errors and in the source locations of the debug symbols
+    pub fn with_path(mut self, path: impl AsRef<Path>) -> Self {
+        self.path = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    pub fn compile(self) -> LangResult<Compiler> {
+        let file = self
+            .path
+            .as_ref()
+            .map_or(DEFAULT_FILE_NAME.to_string(), |path| {
+                path.display().to_string()
+            });
+        let source_map = SourceMap::new(&self.source, file);
+
+        let lower = || {
+            let tokens = lexer::tokenize(&self.source)?;
+            let mut program = parser::parse(&tokens)?;
+            checker::check(&mut program)?;
+            Ok(codegen::generate(&program, &source_map))
+        };
+        lower().map_err(|(span, message)| source_map.error(span, message))
+    }
+}
+
+/// A byte range in the source
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    /// The span from the start of this one to the end of the other one
+    pub fn to(self, other: Span) -> Span {
+        Span::new(self.start, other.end)
+    }
+}
+
+/// A problem found by one of the passes, turned into a `LangError` by the frontend
+type SpannedError = (Span, String);
+type SpannedResult<T> = Result<T, SpannedError>;
+
+/// Turns byte offsets into lines and columns, both start at 1
+struct SourceMap {
+    file: String,
+    line_starts: Vec<usize>,
+}
+
+impl SourceMap {
+    fn new(source: &str, file: String) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(index, _)| index + 1))
+            .collect();
+        Self { file, line_starts }
+    }
+
+    fn line_column(&self, offset: usize) -> (usize, usize) {
+        let line = self.line_starts.partition_point(|&start| start <= offset);
+        (line, offset - self.line_starts[line - 1] + 1)
+    }
+
+    fn error(&self, span: Span, message: String) -> LangError {
+        let (line, column) = self.line_column(span.start);
+        LangError {
+            file: self.file.clone(),
+            span,
+            line,
+            column,
+            message,
+        }
+    }
+}
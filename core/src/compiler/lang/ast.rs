@@ -0,0 +1,134 @@
+use crate::compiler::lang::Span;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Type {
+    U8,
+    U16,
+}
+
+impl Display for Type {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Type::U8 => write!(f, "u8"),
+            Type::U16 => write!(f, "u16"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Program {
+    pub globals: Vec<Let>,
+    pub functions: Vec<Function>,
+}
+
+#[derive(Debug)]
+pub struct Function {
+    pub name: Ident,
+    pub params: Vec<Param>,
+    pub return_type: Option<Type>,
+    pub body: Vec<Stmt>,
+}
+
+#[derive(Debug)]
+pub struct Param {
+    pub name: Ident,
+    pub ty: Type,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ident {
+    pub name: String,
+    pub span: Span,
+}
+
+/// A variable declaration, global or local
+#[derive(Debug)]
+pub struct Let {
+    pub name: Ident,
+    pub ty: Type,
+    pub value: Expr,
+}
+
+#[derive(Debug)]
+pub enum Stmt {
+    Let(Let),
+    Assign {
+        name: Ident,
+        value: Expr,
+    },
+    If {
+        condition: Condition,
+        then: Vec<Stmt>,
+        otherwise: Vec<Stmt>,
+    },
+    While {
+        condition: Condition,
+        body: Vec<Stmt>,
+    },
+    Return {
+        value: Option<Expr>,
+        span: Span,
+    },
+    Expr(Expr),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+}
+
+#[derive(Debug)]
+pub struct Condition {
+    pub lhs: Expr,
+    pub comparison: Comparison,
+    pub rhs: Expr,
+}
+
+#[derive(Debug)]
+pub struct Expr {
+    pub kind: ExprKind,
+    pub span: Span,
+    /// Filled in by the type checker, `None` for calls without a return value
+    pub ty: Option<Type>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+}
+
+#[derive(Debug)]
+pub enum ExprKind {
+    Number(u32),
+    Var(String),
+    Binary {
+        op: BinaryOp,
+        lhs: Box<Expr>,
+        rhs: Box<Expr>,
+    },
+    Cast {
+        value: Box<Expr>,
+        ty: Type,
+    },
+    Call {
+        name: Ident,
+        args: Vec<Expr>,
+    },
+}
+
+impl Expr {
+    pub fn new(kind: ExprKind, span: Span) -> Self {
+        Self {
+            kind,
+            span,
+            ty: None,
+        }
+    }
+}
@@ -0,0 +1,344 @@
+use crate::compiler::lang::ast::{
+    Condition, Expr, ExprKind, Function, Ident, Let, Program, Stmt, Type,
+};
+use crate::compiler::lang::{Span, SpannedResult};
+use std::collections::{HashMap, HashSet};
+
+/// The functions built into the language, they can't be redefined
+pub const INTRINSICS: [&str; 2] = ["peek", "poke"];
+
+#[derive(Debug, Clone)]
+struct Signature {
+    params: Vec<Type>,
+    return_type: Option<Type>,
+}
+
+/// Checks names and types, and fills in the type of every expression
+pub fn check(program: &mut Program) -> SpannedResult<()> {
+    let mut checker = Checker::default();
+    checker.declare_intrinsics();
+    for function in &program.functions {
+        checker.declare_function(function)?;
+    }
+    match checker.functions.get("main") {
+        Some(signature) if signature.params.is_empty() && signature.return_type.is_none() => {}
+        Some(_) => {
+            let main = program
+                .functions
+                .iter()
+                .find(|function| function.name.name == "main")
+                .expect("main was declared");
+            return Err((
+                main.name.span,
+                "'main' can't take parameters or return a value".to_string(),
+            ));
+        }
+        None => return Err((Span::default(), "there is no 'main' function".to_string())),
+    }
+
+    for global in &mut program.globals {
+        checker.check_let(global, true)?;
+    }
+    for function in &mut program.functions {
+        checker.check_function(function)?;
+    }
+    checker.check_recursion(&program.functions)
+}
+
+#[derive(Default)]
+struct Checker {
+    globals: HashMap<String, Type>,
+    functions: HashMap<String, Signature>,
+    /// The parameters and locals of the function being checked
+    locals: HashMap<String, Type>,
+    return_type: Option<Type>,
+    /// The function being checked, calls from global initializers have none
+    current: Option<String>,
+    /// The functions each function calls, with the span of the first call
+    calls: HashMap<String, Vec<Ident>>,
+}
+
+impl Checker {
+    fn declare_intrinsics(&mut self) {
+        self.functions.insert(
+            "peek".to_string(),
+            Signature {
+                params: vec![Type::U16],
+                return_type: Some(Type::U8),
+            },
+        );
+        self.functions.insert(
+            "poke".to_string(),
+            Signature {
+                params: vec![Type::U16, Type::U8],
+                return_type: None,
+            },
+        );
+    }
+
+    fn declare_function(&mut self, function: &Function) -> SpannedResult<()> {
+        let name = &function.name;
+        if INTRINSICS.contains(&name.name.as_str()) {
+            return Err((name.span, format!("'{}' is a built-in function", name.name)));
+        }
+        let signature = Signature {
+            params: function.params.iter().map(|param| param.ty).collect(),
+            return_type: function.return_type,
+        };
+        if self
+            .functions
+            .insert(name.name.clone(), signature)
+            .is_some()
+        {
+            return Err((
+                name.span,
+                format!("function '{}' is defined more than once", name.name),
+            ));
+        }
+        Ok(())
+    }
+
+    fn check_function(&mut self, function: &mut Function) -> SpannedResult<()> {
+        self.locals.clear();
+        for param in &function.params {
+            if self
+                .locals
+                .insert(param.name.name.clone(), param.ty)
+                .is_some()
+            {
+                return Err((
+                    param.name.span,
+                    format!("parameter '{}' is defined more than once", param.name.name),
+                ));
+            }
+        }
+        self.return_type = function.return_type;
+        self.current = Some(function.name.name.clone());
+
+        self.check_block(&mut function.body)?;
+        if function.return_type.is_some() && !always_returns(&function.body) {
+            return Err((
+                function.name.span,
+                format!(
+                    "function '{}' can end without returning a value",
+                    function.name.name
+                ),
+            ));
+        }
+        self.current = None;
+        Ok(())
+    }
+
+    fn check_block(&mut self, stmts: &mut [Stmt]) -> SpannedResult<()> {
+        stmts.iter_mut().try_for_each(|stmt| self.check_stmt(stmt))
+    }
+
+    fn check_stmt(&mut self, stmt: &mut Stmt) -> SpannedResult<()> {
+        match stmt {
+            Stmt::Let(declaration) => self.check_let(declaration, false),
+            Stmt::Assign { name, value } => {
+                let ty = self.variable(name)?;
+                self.check_value(value, ty)
+            }
+            Stmt::If {
+                condition,
+                then,
+                otherwise,
+            } => {
+                self.check_condition(condition)?;
+                self.check_block(then)?;
+                self.check_block(otherwise)
+            }
+            Stmt::While { condition, body } => {
+                self.check_condition(condition)?;
+                self.check_block(body)
+            }
+            Stmt::Return { value, span } => match (value, self.return_type) {
+                (Some(value), Some(ty)) => self.check_value(value, ty),
+                (None, None) => Ok(()),
+                (Some(value), None) => Err((
+                    value.span,
+                    "the function doesn't return a value".to_string(),
+                )),
+                (None, Some(ty)) => Err((*span, format!("expected a {ty} to return"))),
+            },
+            Stmt::Expr(expr) => match expr.kind {
+                ExprKind::Call { .. } => self.check_expr(expr, None).map(|_| ()),
+                _ => Err((
+                    expr.span,
+                    "only calls can be used as statements".to_string(),
+                )),
+            },
+        }
+    }
+
+    fn check_let(&mut self, declaration: &mut Let, global: bool) -> SpannedResult<()> {
+        self.check_value(&mut declaration.value, declaration.ty)?;
+        let name = &declaration.name;
+        let variables = if global {
+            &mut self.globals
+        } else {
+            &mut self.locals
+        };
+        if variables
+            .insert(name.name.clone(), declaration.ty)
+            .is_some()
+        {
+            return Err((
+                name.span,
+                format!("variable '{}' is defined more than once", name.name),
+            ));
+        }
+        Ok(())
+    }
+
+    fn check_condition(&mut self, condition: &mut Condition) -> SpannedResult<()> {
+        self.check_pair(&mut condition.lhs, &mut condition.rhs, None)
+            .map(|_| ())
+    }
+
+    fn variable(&self, name: &Ident) -> SpannedResult<Type> {
+        self.locals
+            .get(&name.name)
+            .or_else(|| self.globals.get(&name.name))
+            .copied()
+            .ok_or_else(|| (name.span, format!("unknown variable '{}'", name.name)))
+    }
+
+    /// Checks an expression that has to have a value of the type
+    fn check_value(&mut self, expr: &mut Expr, expected: Type) -> SpannedResult<()> {
+        match self.check_expr(expr, Some(expected))? {
+            Some(ty) if ty == expected => Ok(()),
+            Some(ty) => Err((expr.span, format!("expected {expected}, found {ty}"))),
+            None => Err((expr.span, "the call doesn't return a value".to_string())),
+        }
+    }
+
+    /// The type of the expression, number literals take the expected type if they fit
+    fn check_expr(
+        &mut self,
+        expr: &mut Expr,
+        expected: Option<Type>,
+    ) -> SpannedResult<Option<Type>> {
+        let ty = match &mut expr.kind {
+            ExprKind::Number(value) => {
+                let ty = expected.unwrap_or(if *value <= 0xFF { Type::U8 } else { Type::U16 });
+                let max = match ty {
+                    Type::U8 => u8::MAX as u32,
+                    Type::U16 => u16::MAX as u32,
+                };
+                if *value > max {
+                    return Err((expr.span, format!("{value} doesn't fit in a {ty}")));
+                }
+                Some(ty)
+            }
+            ExprKind::Var(name) => Some(self.variable(&Ident {
+                name: name.clone(),
+                span: expr.span,
+            })?),
+            ExprKind::Binary { lhs, rhs, .. } => Some(self.check_pair(lhs, rhs, expected)?),
+            ExprKind::Cast { value, ty } => {
+                if self.check_expr(value, None)?.is_none() {
+                    return Err((value.span, "the call doesn't return a value".to_string()));
+                }
+                Some(*ty)
+            }
+            ExprKind::Call { name, args } => {
+                let Some(signature) = self.functions.get(&name.name).cloned() else {
+                    return Err((name.span, format!("unknown function '{}'", name.name)));
+                };
+                if args.len() != signature.params.len() {
+                    return Err((
+                        expr.span,
+                        format!(
+                            "'{}' takes {} argument(s), but {} were given",
+                            name.name,
+                            signature.params.len(),
+                            args.len()
+                        ),
+                    ));
+                }
+                for (arg, ty) in args.iter_mut().zip(signature.params) {
+                    self.check_value(arg, ty)?;
+                }
+                if let Some(current) = &self.current {
+                    let calls = self.calls.entry(current.clone()).or_default();
+                    if !calls.iter().any(|call| call.name == name.name) {
+                        calls.push(name.clone());
+                    }
+                }
+                signature.return_type
+            }
+        };
+        expr.ty = ty;
+        Ok(ty)
+    }
+
+    /// Both sides of an operator have the same type, a number on the left takes the type of the right
+    fn check_pair(
+        &mut self,
+        lhs: &mut Expr,
+        rhs: &mut Expr,
+        expected: Option<Type>,
+    ) -> SpannedResult<Type> {
+        let (lhs_type, rhs_type) = if matches!(lhs.kind, ExprKind::Number(_)) {
+            let rhs_type = self.check_expr(rhs, expected)?;
+            (self.check_expr(lhs, rhs_type.or(expected))?, rhs_type)
+        } else {
+            let lhs_type = self.check_expr(lhs, expected)?;
+            (lhs_type, self.check_expr(rhs, lhs_type.or(expected))?)
+        };
+        match (lhs_type, rhs_type) {
+            (Some(lhs_type), Some(rhs_type)) if lhs_type == rhs_type => Ok(lhs_type),
+            (Some(lhs_type), Some(rhs_type)) => {
+                Err((rhs.span, format!("expected {lhs_type}, found {rhs_type}")))
+            }
+            (None, _) => Err((lhs.span, "the call doesn't return a value".to_string())),
+            (_, None) => Err((rhs.span, "the call doesn't return a value".to_string())),
+        }
+    }
+
+    /// Every variable has a fixed address, so a function can't be active twice
+    fn check_recursion(&self, functions: &[Function]) -> SpannedResult<()> {
+        for function in functions {
+            let name = &function.name.name;
+            for call in self.calls.get(name).into_iter().flatten() {
+                if self.reaches(&call.name, name, &mut HashSet::new()) {
+                    return Err((
+                        call.span,
+                        format!(
+                            "'{name}' calls itself through '{}', functions can't be recursive",
+                            call.name
+                        ),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn reaches<'a>(&'a self, from: &'a str, target: &str, visited: &mut HashSet<&'a str>) -> bool {
+        if from == target {
+            return true;
+        }
+        if !visited.insert(from) {
+            return false;
+        }
+        self.calls
+            .get(from)
+            .into_iter()
+            .flatten()
+            .any(|call| self.reaches(&call.name, target, visited))
+    }
+}
+
+/// Whether every path through the statements ends in a `return`
+fn always_returns(stmts: &[Stmt]) -> bool {
+    match stmts.last() {
+        Some(Stmt::Return { .. }) => true,
+        Some(Stmt::If {
+            then, otherwise, ..
+        }) => always_returns(then) && always_returns(otherwise),
+        _ => false,
+    }
+}
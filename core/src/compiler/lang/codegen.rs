@@ -0,0 +1,318 @@
+use crate::compiler::lang::ast::{
+    BinaryOp, Comparison, Condition, Expr, ExprKind, Function, Let, Program, Stmt, Type,
+};
+use crate::compiler::lang::{SourceMap, Span};
+use crate::compiler::node::{NodeType, SymbolNode};
+use crate::compiler::variables::{VarU16, VarU8};
+use crate::compiler::Compiler;
+use crate::console::components::cpu::instructions::Condition as Flag;
+use crate::console::components::cpu::registers::{R16, R16S, R8};
+use std::collections::HashMap;
+
+/// Lowers a checked program. Values are computed in A (`u8`) or BC (`u16`),
+/// the left side of an operator waits on the stack while the right side is computed.
+pub fn generate(program: &Program, source_map: &SourceMap) -> Compiler {
+    let mut codegen = Codegen {
+        compiler: Compiler::new(),
+        source_map,
+        globals: HashMap::new(),
+        locals: HashMap::new(),
+        params: HashMap::new(),
+        function: String::new(),
+        label_count: 0,
+    };
+
+    // Parameters are allocated up front, calls can come before the function
+    for function in &program.functions {
+        let params = function
+            .params
+            .iter()
+            .map(|param| {
+                codegen.allocate(
+                    format!("{}.{}", function.name.name, param.name.name),
+                    param.ty,
+                )
+            })
+            .collect();
+        codegen.params.insert(function.name.name.clone(), params);
+    }
+
+    for global in &program.globals {
+        codegen.declare(global, global.name.name.clone(), true);
+    }
+    codegen.emit(|c| c.call_label("main").halt());
+    for function in &program.functions {
+        codegen.function(function);
+    }
+    codegen.compiler
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Variable {
+    U8(VarU8),
+    U16(VarU16),
+}
+
+struct Codegen<'a> {
+    compiler: Compiler,
+    source_map: &'a SourceMap,
+    globals: HashMap<String, Variable>,
+    /// The parameters and locals of the current function
+    locals: HashMap<String, Variable>,
+    params: HashMap<String, Vec<(String, Variable)>>,
+    function: String,
+    label_count: usize,
+}
+
+impl Codegen<'_> {
+    fn emit(&mut self, f: impl FnOnce(Compiler) -> Compiler) {
+        self.compiler = f(std::mem::take(&mut self.compiler));
+    }
+
+    fn allocate(&mut self, name: String, ty: Type) -> (String, Variable) {
+        let variable = match ty {
+            Type::U8 => Variable::U8(self.compiler.var_u8(name.clone())),
+            Type::U16 => Variable::U16(self.compiler.var_u16(name.clone())),
+        };
+        (name, variable)
+    }
+
+    fn source_location(&mut self, span: Span) {
+        let (line, column) = self.source_map.line_column(span.start);
+        let file = self.source_map.file.clone();
+        self.emit(|c| c.source_location(file, line as u32, column as u32));
+    }
+
+    /// A label unique to the current function
+    fn new_label(&mut self, kind: &str) -> String {
+        self.label_count += 1;
+        format!("{}.{kind}{}", self.function, self.label_count)
+    }
+
+    fn function(&mut self, function: &Function) {
+        let name = function.name.name.clone();
+        self.function = name.clone();
+        self.locals = self.params[&name]
+            .iter()
+            .zip(&function.params)
+            .map(|((_, variable), param)| (param.name.name.clone(), *variable))
+            .collect();
+
+        self.emit(|c| {
+            c.push_node(NodeType::Symbol(SymbolNode::FunctionStart(name.clone())))
+                .label(name.clone())
+        });
+        self.block(&function.body);
+        self.emit(|c| {
+            c.label(format!("{name}.return"))
+                .ret()
+                .push_node(NodeType::Symbol(SymbolNode::FunctionEnd))
+        });
+    }
+
+    fn block(&mut self, stmts: &[Stmt]) {
+        for stmt in stmts {
+            self.stmt(stmt);
+        }
+    }
+
+    fn stmt(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::Let(declaration) => {
+                let name = format!("{}.{}", self.function, declaration.name.name);
+                self.declare(declaration, name, false);
+            }
+            Stmt::Assign { name, value } => {
+                self.source_location(name.span);
+                self.expr(value);
+                let variable = self.variable(&name.name);
+                self.store(variable);
+            }
+            Stmt::If {
+                condition,
+                then,
+                otherwise,
+            } => {
+                self.source_location(condition.lhs.span);
+                let else_label = self.new_label("else");
+                let end_label = format!("{else_label}.end");
+                self.branch_unless(condition, &else_label);
+                self.block(then);
+                self.emit(|c| c.jump_label(end_label.clone()).label(else_label));
+                self.block(otherwise);
+                self.emit(|c| c.label(end_label));
+            }
+            Stmt::While { condition, body } => {
+                let loop_label = self.new_label("while");
+                let end_label = format!("{loop_label}.end");
+                self.emit(|c| c.label(loop_label.clone()));
+                self.source_location(condition.lhs.span);
+                self.branch_unless(condition, &end_label);
+                self.block(body);
+                self.emit(|c| c.jump_label(loop_label).label(end_label));
+            }
+            Stmt::Return { value, span } => {
+                self.source_location(*span);
+                if let Some(value) = value {
+                    self.expr(value);
+                }
+                let return_label = format!("{}.return", self.function);
+                self.emit(|c| c.jump_label(return_label));
+            }
+            Stmt::Expr(expr) => {
+                self.source_location(expr.span);
+                self.expr(expr);
+            }
+        }
+    }
+
+    /// Allocates the variable and stores its initial value
+    fn declare(&mut self, declaration: &Let, name: String, global: bool) {
+        self.source_location(declaration.name.span);
+        self.expr(&declaration.value);
+        let (_, variable) = self.allocate(name, declaration.ty);
+        let variables = if global {
+            &mut self.globals
+        } else {
+            &mut self.locals
+        };
+        variables.insert(declaration.name.name.clone(), variable);
+        self.store(variable);
+    }
+
+    fn variable(&self, name: &str) -> Variable {
+        *self
+            .locals
+            .get(name)
+            .or_else(|| self.globals.get(name))
+            .expect("The checker resolved the variable")
+    }
+
+    fn store(&mut self, variable: Variable) {
+        self.emit(|c| match variable {
+            Variable::U8(variable) => c.store_u8(variable, R8::A),
+            Variable::U16(variable) => c.store_u16(variable, R16::BC),
+        });
+    }
+
+    /// Jumps to the label if the condition doesn't hold
+    fn branch_unless(&mut self, condition: &Condition, label: &str) {
+        self.operands(&condition.lhs, &condition.rhs);
+        self.combine(type_of(&condition.lhs), BinaryOp::Sub);
+        match condition.comparison {
+            Comparison::Equal => self.emit(|c| c.jump_if_label(Flag::NotZero, label)),
+            Comparison::NotEqual => self.emit(|c| c.jump_if_label(Flag::Zero, label)),
+            Comparison::Less => self.emit(|c| c.jump_if_label(Flag::NotCarry, label)),
+            Comparison::GreaterEqual => self.emit(|c| c.jump_if_label(Flag::Carry, label)),
+            Comparison::Greater => self.emit(|c| {
+                c.jump_if_label(Flag::Carry, label)
+                    .jump_if_label(Flag::Zero, label)
+            }),
+            Comparison::LessEqual => {
+                let holds_label = self.new_label("holds");
+                self.emit(|c| {
+                    c.jump_if_label(Flag::Carry, holds_label.clone())
+                        .jump_if_label(Flag::NotZero, label)
+                        .label(holds_label)
+                });
+            }
+        }
+    }
+
+    /// Computes the left side onto the stack and the right side into the accumulator
+    fn operands(&mut self, lhs: &Expr, rhs: &Expr) {
+        self.expr(lhs);
+        self.push(type_of(lhs));
+        self.expr(rhs);
+    }
+
+    /// Applies the operator to the left side on the stack and the right side in the accumulator
+    fn combine(&mut self, ty: Type, op: BinaryOp) {
+        self.emit(|c| match (ty, op) {
+            (Type::U8, op) => {
+                let c = c.load_r8(R8::B, R8::A).stack_pop(R16S::AF);
+                match op {
+                    BinaryOp::Add => c.add_r8(R8::B),
+                    BinaryOp::Sub => c.sub_r8(R8::B),
+                }
+            }
+            (Type::U16, op) => {
+                let c = c.load_r16(R16::DE, R16::BC).stack_pop(R16S::BC);
+                match op {
+                    BinaryOp::Add => c.add_r16(R16::DE),
+                    BinaryOp::Sub => c.sub_r16(R16::DE),
+                }
+            }
+        });
+    }
+
+    fn push(&mut self, ty: Type) {
+        self.emit(|c| match ty {
+            Type::U8 => c.stack_push(R16S::AF),
+            Type::U16 => c.stack_push(R16S::BC),
+        });
+    }
+
+    /// Computes the value into A or BC
+    fn expr(&mut self, expr: &Expr) {
+        match &expr.kind {
+            ExprKind::Number(value) => {
+                let value = *value;
+                self.emit(|c| match type_of(expr) {
+                    Type::U8 => c.load_r8i(R8::A, value as u8),
+                    Type::U16 => c.load_r16i(R16::BC, value as u16),
+                });
+            }
+            ExprKind::Var(name) => {
+                let variable = self.variable(name);
+                self.emit(|c| match variable {
+                    Variable::U8(variable) => c.load_u8(R8::A, variable),
+                    Variable::U16(variable) => c.load_u16(R16::BC, variable),
+                });
+            }
+            ExprKind::Binary { op, lhs, rhs } => {
+                self.operands(lhs, rhs);
+                self.combine(type_of(expr), *op);
+            }
+            ExprKind::Cast { value, ty } => {
+                self.expr(value);
+                match (type_of(value), ty) {
+                    (Type::U8, Type::U16) => {
+                        self.emit(|c| c.load_r8(R8::C, R8::A).load_r8i(R8::B, 0))
+                    }
+                    (Type::U16, Type::U8) => self.emit(|c| c.load_r8(R8::A, R8::C)),
+                    _ => {}
+                }
+            }
+            ExprKind::Call { name, args } => match name.name.as_str() {
+                "peek" => {
+                    self.expr(&args[0]);
+                    self.emit(|c| c.load_r16(R16::HL, R16::BC).load_r8(R8::A, R8::HL));
+                }
+                "poke" => {
+                    self.operands(&args[0], &args[1]);
+                    self.emit(|c| c.stack_pop(R16S::HL).load_r8(R8::HL, R8::A));
+                }
+                function => {
+                    for arg in args {
+                        self.expr(arg);
+                        self.push(type_of(arg));
+                    }
+                    let params = self.params[function].clone();
+                    for (_, param) in params.into_iter().rev() {
+                        self.emit(|c| match param {
+                            Variable::U8(param) => c.stack_pop(R16S::AF).store_u8(param, R8::A),
+                            Variable::U16(param) => c.stack_pop(R16S::BC).store_u16(param, R16::BC),
+                        });
+                    }
+                    let function = function.to_string();
+                    self.emit(|c| c.call_label(function));
+                }
+            },
+        }
+    }
+}
+
+fn type_of(expr: &Expr) -> Type {
+    expr.ty.expect("The checker typed the expression")
+}
@@ -0,0 +1,164 @@
+use crate::compiler::lang::{Span, SpannedResult};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenKind {
+    Ident(String),
+    Number(u32),
+    Let,
+    Fn,
+    If,
+    Else,
+    While,
+    Return,
+    As,
+    U8,
+    U16,
+    LeftParen,
+    RightParen,
+    LeftBrace,
+    RightBrace,
+    Comma,
+    Colon,
+    Semicolon,
+    Arrow,
+    Assign,
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Plus,
+    Minus,
+    /// The end of the source
+    End,
+}
+
+#[derive(Debug, Clone)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: Span,
+}
+
+/// Splits the source into tokens, the last one is always `End`
+pub fn tokenize(source: &str) -> SpannedResult<Vec<Token>> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let start = index;
+        let byte = bytes[index];
+        if byte.is_ascii_whitespace() {
+            index += 1;
+            continue;
+        }
+        if source[index..].starts_with("//") {
+            index = source[index..]
+                .find('\n')
+                .map_or(bytes.len(), |end| index + end);
+            continue;
+        }
+
+        let kind = if byte.is_ascii_alphabetic() || byte == b'_' {
+            index = scan(bytes, index, |byte| {
+                byte.is_ascii_alphanumeric() || byte == b'_'
+            });
+            keyword(&source[start..index])
+                .unwrap_or_else(|| TokenKind::Ident(source[start..index].to_string()))
+        } else if byte.is_ascii_digit() {
+            index = scan(bytes, index, |byte| {
+                byte.is_ascii_alphanumeric() || byte == b'_'
+            });
+            let span = Span::new(start, index);
+            TokenKind::Number(
+                number(&source[start..index])
+                    .ok_or_else(|| (span, format!("invalid number '{}'", &source[start..index])))?,
+            )
+        } else {
+            let (kind, len) = symbol(&source[index..]).ok_or_else(|| {
+                let char = source[index..].chars().next().unwrap_or_default();
+                (
+                    Span::new(start, start + char.len_utf8()),
+                    format!("unexpected character '{char}'"),
+                )
+            })?;
+            index += len;
+            kind
+        };
+        tokens.push(Token {
+            kind,
+            span: Span::new(start, index),
+        });
+    }
+
+    tokens.push(Token {
+        kind: TokenKind::End,
+        span: Span::new(bytes.len(), bytes.len()),
+    });
+    Ok(tokens)
+}
+
+fn scan(bytes: &[u8], start: usize, accept: impl Fn(u8) -> bool) -> usize {
+    bytes[start..]
+        .iter()
+        .position(|&byte| !accept(byte))
+        .map_or(bytes.len(), |len| start + len)
+}
+
+fn keyword(text: &str) -> Option<TokenKind> {
+    Some(match text {
+        "let" => TokenKind::Let,
+        "fn" => TokenKind::Fn,
+        "if" => TokenKind::If,
+        "else" => TokenKind::Else,
+        "while" => TokenKind::While,
+        "return" => TokenKind::Return,
+        "as" => TokenKind::As,
+        "u8" => TokenKind::U8,
+        "u16" => TokenKind::U16,
+        _ => return None,
+    })
+}
+
+/// Decimal, `0x` hexadecimal or `0b` binary, `_` separates digits.
+/// Values that don't fit in a word are left to the type checker.
+fn number(text: &str) -> Option<u32> {
+    let digits = text.replace('_', "");
+    let (digits, radix) = match digits.get(..2) {
+        Some("0x") => (&digits[2..], 16),
+        Some("0b") => (&digits[2..], 2),
+        _ => (&digits[..], 10),
+    };
+    u32::from_str_radix(digits, radix).ok()
+}
+
+/// The punctuation or operator at the start of the text and its length
+fn symbol(text: &str) -> Option<(TokenKind, usize)> {
+    const TWO: [(&str, TokenKind); 5] = [
+        ("->", TokenKind::Arrow),
+        ("==", TokenKind::Equal),
+        ("!=", TokenKind::NotEqual),
+        ("<=", TokenKind::LessEqual),
+        (">=", TokenKind::GreaterEqual),
+    ];
+    if let Some((_, kind)) = TWO.iter().find(|(symbol, _)| text.starts_with(symbol)) {
+        return Some((kind.clone(), 2));
+    }
+    let kind = match text.as_bytes()[0] {
+        b'(' => TokenKind::LeftParen,
+        b')' => TokenKind::RightParen,
+        b'{' => TokenKind::LeftBrace,
+        b'}' => TokenKind::RightBrace,
+        b',' => TokenKind::Comma,
+        b':' => TokenKind::Colon,
+        b';' => TokenKind::Semicolon,
+        b'=' => TokenKind::Assign,
+        b'<' => TokenKind::Less,
+        b'>' => TokenKind::Greater,
+        b'+' => TokenKind::Plus,
+        b'-' => TokenKind::Minus,
+        _ => return None,
+    };
+    Some((kind, 1))
+}
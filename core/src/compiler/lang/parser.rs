@@ -0,0 +1,309 @@
+use crate::compiler::lang::ast::{
+    BinaryOp, Comparison, Condition, Expr, ExprKind, Function, Ident, Let, Param, Program, Stmt,
+    Type,
+};
+use crate::compiler::lang::lexer::{Token, TokenKind};
+use crate::compiler::lang::{Span, SpannedResult};
+
+pub fn parse(tokens: &[Token]) -> SpannedResult<Program> {
+    let mut parser = Parser { tokens, index: 0 };
+    let mut program = Program {
+        globals: Vec::new(),
+        functions: Vec::new(),
+    };
+    loop {
+        match parser.peek() {
+            TokenKind::Let => program.globals.push(parser.parse_let()?),
+            TokenKind::Fn => program.functions.push(parser.parse_function()?),
+            TokenKind::End => return Ok(program),
+            _ => return Err(parser.unexpected("'let' or 'fn'")),
+        }
+    }
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    index: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> &TokenKind {
+        &self.tokens[self.index].kind
+    }
+
+    fn peek_at(&self, offset: usize) -> &TokenKind {
+        let index = (self.index + offset).min(self.tokens.len() - 1);
+        &self.tokens[index].kind
+    }
+
+    fn span(&self) -> Span {
+        self.tokens[self.index].span
+    }
+
+    /// The span of the token consumed last
+    fn previous_span(&self) -> Span {
+        self.tokens[self.index.saturating_sub(1)].span
+    }
+
+    fn advance(&mut self) -> &Token {
+        let token = &self.tokens[self.index];
+        if token.kind != TokenKind::End {
+            self.index += 1;
+        }
+        token
+    }
+
+    fn eat(&mut self, kind: TokenKind) -> bool {
+        let matches = *self.peek() == kind;
+        if matches {
+            self.advance();
+        }
+        matches
+    }
+
+    fn expect(&mut self, kind: TokenKind, expected: &str) -> SpannedResult<Span> {
+        if *self.peek() == kind {
+            Ok(self.advance().span)
+        } else {
+            Err(self.unexpected(expected))
+        }
+    }
+
+    fn unexpected(&self, expected: &str) -> (Span, String) {
+        let found = match self.peek() {
+            TokenKind::End => "the end of the source".to_string(),
+            TokenKind::Ident(name) => format!("'{name}'"),
+            TokenKind::Number(value) => format!("'{value}'"),
+            _ => "this token".to_string(),
+        };
+        (self.span(), format!("expected {expected}, found {found}"))
+    }
+
+    fn parse_ident(&mut self) -> SpannedResult<Ident> {
+        match self.peek() {
+            TokenKind::Ident(name) => {
+                let name = name.clone();
+                let span = self.advance().span;
+                Ok(Ident { name, span })
+            }
+            _ => Err(self.unexpected("a name")),
+        }
+    }
+
+    fn parse_type(&mut self) -> SpannedResult<Type> {
+        let ty = match self.peek() {
+            TokenKind::U8 => Type::U8,
+            TokenKind::U16 => Type::U16,
+            _ => return Err(self.unexpected("'u8' or 'u16'")),
+        };
+        self.advance();
+        Ok(ty)
+    }
+
+    /// `let name: type = value;`
+    fn parse_let(&mut self) -> SpannedResult<Let> {
+        self.expect(TokenKind::Let, "'let'")?;
+        let name = self.parse_ident()?;
+        self.expect(TokenKind::Colon, "':'")?;
+        let ty = self.parse_type()?;
+        self.expect(TokenKind::Assign, "'='")?;
+        let value = self.parse_expr()?;
+        self.expect(TokenKind::Semicolon, "';'")?;
+        Ok(Let { name, ty, value })
+    }
+
+    /// `fn name(param: type, ...) -> type { ... }`
+    fn parse_function(&mut self) -> SpannedResult<Function> {
+        self.expect(TokenKind::Fn, "'fn'")?;
+        let name = self.parse_ident()?;
+        self.expect(TokenKind::LeftParen, "'('")?;
+        let mut params = Vec::new();
+        while *self.peek() != TokenKind::RightParen {
+            if !params.is_empty() {
+                self.expect(TokenKind::Comma, "',' or ')'")?;
+            }
+            let name = self.parse_ident()?;
+            self.expect(TokenKind::Colon, "':'")?;
+            params.push(Param {
+                name,
+                ty: self.parse_type()?,
+            });
+        }
+        self.advance();
+        let return_type = if self.eat(TokenKind::Arrow) {
+            Some(self.parse_type()?)
+        } else {
+            None
+        };
+        let body = self.parse_block()?;
+        Ok(Function {
+            name,
+            params,
+            return_type,
+            body,
+        })
+    }
+
+    fn parse_block(&mut self) -> SpannedResult<Vec<Stmt>> {
+        self.expect(TokenKind::LeftBrace, "'{'")?;
+        let mut stmts = Vec::new();
+        while !self.eat(TokenKind::RightBrace) {
+            if *self.peek() == TokenKind::End {
+                return Err(self.unexpected("'}'"));
+            }
+            stmts.push(self.parse_stmt()?);
+        }
+        Ok(stmts)
+    }
+
+    fn parse_stmt(&mut self) -> SpannedResult<Stmt> {
+        match self.peek() {
+            TokenKind::Let => Ok(Stmt::Let(self.parse_let()?)),
+            TokenKind::If => self.parse_if(),
+            TokenKind::While => {
+                self.advance();
+                let condition = self.parse_condition()?;
+                let body = self.parse_block()?;
+                Ok(Stmt::While { condition, body })
+            }
+            TokenKind::Return => {
+                let start = self.advance().span;
+                let value = match self.peek() {
+                    TokenKind::Semicolon => None,
+                    _ => Some(self.parse_expr()?),
+                };
+                let end = self.expect(TokenKind::Semicolon, "';'")?;
+                Ok(Stmt::Return {
+                    value,
+                    span: start.to(end),
+                })
+            }
+            TokenKind::Ident(_) if *self.peek_at(1) == TokenKind::Assign => {
+                let name = self.parse_ident()?;
+                self.advance();
+                let value = self.parse_expr()?;
+                self.expect(TokenKind::Semicolon, "';'")?;
+                Ok(Stmt::Assign { name, value })
+            }
+            _ => {
+                let expr = self.parse_expr()?;
+                self.expect(TokenKind::Semicolon, "';'")?;
+                Ok(Stmt::Expr(expr))
+            }
+        }
+    }
+
+    /// `if condition { ... } else if condition { ... } else { ... }`
+    fn parse_if(&mut self) -> SpannedResult<Stmt> {
+        self.expect(TokenKind::If, "'if'")?;
+        let condition = self.parse_condition()?;
+        let then = self.parse_block()?;
+        let otherwise = if !self.eat(TokenKind::Else) {
+            Vec::new()
+        } else if *self.peek() == TokenKind::If {
+            vec![self.parse_if()?]
+        } else {
+            self.parse_block()?
+        };
+        Ok(Stmt::If {
+            condition,
+            then,
+            otherwise,
+        })
+    }
+
+    fn parse_condition(&mut self) -> SpannedResult<Condition> {
+        let lhs = self.parse_expr()?;
+        let comparison = match self.peek() {
+            TokenKind::Equal => Comparison::Equal,
+            TokenKind::NotEqual => Comparison::NotEqual,
+            TokenKind::Less => Comparison::Less,
+            TokenKind::LessEqual => Comparison::LessEqual,
+            TokenKind::Greater => Comparison::Greater,
+            TokenKind::GreaterEqual => Comparison::GreaterEqual,
+            _ => return Err(self.unexpected("a comparison")),
+        };
+        self.advance();
+        let rhs = self.parse_expr()?;
+        Ok(Condition {
+            lhs,
+            comparison,
+            rhs,
+        })
+    }
+
+    /// `+` and `-`, evaluated from left to right
+    fn parse_expr(&mut self) -> SpannedResult<Expr> {
+        let mut lhs = self.parse_cast()?;
+        loop {
+            let op = match self.peek() {
+                TokenKind::Plus => BinaryOp::Add,
+                TokenKind::Minus => BinaryOp::Sub,
+                _ => return Ok(lhs),
+            };
+            self.advance();
+            let rhs = self.parse_cast()?;
+            let span = lhs.span.to(rhs.span);
+            lhs = Expr::new(
+                ExprKind::Binary {
+                    op,
+                    lhs: Box::new(lhs),
+                    rhs: Box::new(rhs),
+                },
+                span,
+            );
+        }
+    }
+
+    /// `value as type`
+    fn parse_cast(&mut self) -> SpannedResult<Expr> {
+        let mut value = self.parse_primary()?;
+        while self.eat(TokenKind::As) {
+            let ty = self.parse_type()?;
+            let span = value.span.to(self.previous_span());
+            value = Expr::new(
+                ExprKind::Cast {
+                    value: Box::new(value),
+                    ty,
+                },
+                span,
+            );
+        }
+        Ok(value)
+    }
+
+    fn parse_primary(&mut self) -> SpannedResult<Expr> {
+        match self.peek() {
+            TokenKind::Number(value) => {
+                let value = *value;
+                let span = self.advance().span;
+                Ok(Expr::new(ExprKind::Number(value), span))
+            }
+            TokenKind::Ident(_) => {
+                let name = self.parse_ident()?;
+                if !self.eat(TokenKind::LeftParen) {
+                    let span = name.span;
+                    return Ok(Expr::new(ExprKind::Var(name.name), span));
+                }
+                let mut args = Vec::new();
+                while *self.peek() != TokenKind::RightParen {
+                    if !args.is_empty() {
+                        self.expect(TokenKind::Comma, "',' or ')'")?;
+                    }
+                    args.push(self.parse_expr()?);
+                }
+                let end = self.advance().span;
+                let span = name.span.to(end);
+                Ok(Expr::new(ExprKind::Call { name, args }, span))
+            }
+            TokenKind::LeftParen => {
+                let start = self.advance().span;
+                let mut expr = self.parse_expr()?;
+                let end = self.expect(TokenKind::RightParen, "')'")?;
+                expr.span = start.to(end);
+                Ok(expr)
+            }
+            _ => Err(self.unexpected("an expression")),
+        }
+    }
+}
@@ -1,5 +1,9 @@
 mod base;
 mod control_flow;
+mod data;
 #[cfg(feature = "debugger")]
 mod debug;
+mod functions;
 mod instructions;
+mod interrupts;
+mod symbols;
@@ -1,36 +1,74 @@
+use crate::compiler::error::CompileErrorKind;
 use crate::compiler::node::{Node, NodeType};
 use crate::compiler::Compiler;
 use crate::console::components::cpu::instructions::CPUInstruction;
+use crate::console::config::HardwareProfile;
 
 impl Compiler {
     pub fn new() -> Self {
         Self::default()
     }
 
+    #[track_caller]
     pub fn push_node(mut self, node_type: NodeType) -> Self {
         let address = self.push_position;
-        self.push_position += node_type.size();
+        // Overflowing nodes are reported on compilation
+        self.push_position = address.wrapping_add(node_type.size());
         let node = Node::new(node_type, address);
         self.nodes.push(node);
         self
     }
 
+    #[track_caller]
     pub fn push_data(self, data: Vec<u8>) -> Self {
         self.push_node(NodeType::Data(data))
     }
 
+    #[track_caller]
     pub fn push_byte(self, byte: u8) -> Self {
         self.push_node(NodeType::Data(vec![byte]))
     }
 
+    #[track_caller]
     pub fn push_word(self, word: u16) -> Self {
         self.push_node(NodeType::Data(word.to_le_bytes().to_vec()))
     }
 
+    /// Push the address of a label as a word, the label may be defined later
+    #[track_caller]
+    pub fn push_label_word(self, label: impl Into<String>) -> Self {
+        self.push_node(NodeType::LabelWord(label.into()))
+    }
+
+    #[track_caller]
     pub fn push_instruction(self, instruction: CPUInstruction) -> Self {
         self.push_node(NodeType::Instruction(instruction))
     }
 
+    /// The byte the gaps between nodes placed at different positions are filled with
+    pub fn with_fill_byte(mut self, fill_byte: u8) -> Self {
+        self.fill_byte = fill_byte;
+        self
+    }
+
+    /// The hardware the program runs on, variables that don't fit its RAM are reported on compilation
+    #[track_caller]
+    pub fn with_profile(mut self, profile: HardwareProfile) -> Self {
+        self.profile = profile;
+        let end = self.variables_end();
+        let errors = self
+            .variables
+            .iter()
+            .filter(|variable| variable.end >= end)
+            .map(|variable| CompileErrorKind::OutOfRAM(variable.name.clone()))
+            .collect::<Vec<_>>();
+        for kind in errors {
+            self.nodes
+                .push(Node::new(NodeType::Error(kind), self.push_position));
+        }
+        self
+    }
+
     pub fn set_push_position(mut self, position: u16) -> Self {
         self.push_position = position;
         self
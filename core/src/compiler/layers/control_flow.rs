@@ -1,4 +1,8 @@
-use crate::compiler::Compiler;
+use crate::compiler::error::CompileErrorKind;
+use crate::compiler::node::NodeType;
+use crate::compiler::{Compiler, LoopLabels};
+use crate::console::components::cpu::instructions::Condition;
+use crate::console::components::cpu::registers::R8;
 
 impl Compiler {
     /// Repeats a set of compiler functions a specified amount of times
@@ -20,4 +24,107 @@ impl Compiler {
         let previous_position = self.push_position;
         f(self.set_push_position(position)).set_push_position(previous_position)
     }
+
+    /// Runs `then` if the condition holds for the current flags, otherwise runs `otherwise`
+    #[track_caller]
+    pub fn if_flag<T, E>(mut self, condition: Condition, then: T, otherwise: E) -> Self
+    where
+        T: Fn(Self) -> Self,
+        E: Fn(Self) -> Self,
+    {
+        let id = self.next_label_id();
+        let else_label = format!("if_{id}.else");
+        let end_label = format!("if_{id}.end");
+
+        let compiler = self.jump_if_label(condition.inverse(), else_label.clone());
+        let compiler = then(compiler)
+            .jump_label(end_label.clone())
+            .label(else_label);
+        otherwise(compiler).label(end_label)
+    }
+
+    /// Runs the body as long as the condition holds for the flags at the start of an iteration
+    #[track_caller]
+    pub fn loop_while<F>(mut self, condition: Condition, body: F) -> Self
+    where
+        F: Fn(Self) -> Self,
+    {
+        let id = self.next_label_id();
+        let start_label = format!("loop_{id}.start");
+        let end_label = format!("loop_{id}.end");
+
+        self.label(start_label.clone())
+            .jump_if_label(condition.inverse(), end_label.clone())
+            .loop_body(start_label.clone(), end_label.clone(), body)
+            .jump_label(start_label)
+            .label(end_label)
+    }
+
+    /// Runs the body the specified amount of times, counting down in the register.\
+    /// The body has to keep the value of the register.
+    #[track_caller]
+    pub fn for_counter<F>(mut self, r8: R8, times: u8, body: F) -> Self
+    where
+        F: Fn(Self) -> Self,
+    {
+        if times == 0 {
+            return self;
+        }
+        let id = self.next_label_id();
+        let start_label = format!("for_{id}.start");
+        let next_label = format!("for_{id}.next");
+        let end_label = format!("for_{id}.end");
+
+        self.load_r8i(r8, times)
+            .label(start_label.clone())
+            .loop_body(next_label.clone(), end_label.clone(), body)
+            .label(next_label)
+            .decrement_r8(r8)
+            .jump_if_label(Condition::NotZero, start_label)
+            .label(end_label)
+    }
+
+    /// Leave the innermost loop
+    #[track_caller]
+    pub fn break_loop(self) -> Self {
+        match self.loops.last() {
+            Some(labels) => {
+                let label = labels.break_label.clone();
+                self.jump_label(label)
+            }
+            None => self.push_node(NodeType::Error(CompileErrorKind::OutsideLoop("break"))),
+        }
+    }
+
+    /// Skip to the next iteration of the innermost loop
+    #[track_caller]
+    pub fn continue_loop(self) -> Self {
+        match self.loops.last() {
+            Some(labels) => {
+                let label = labels.continue_label.clone();
+                self.jump_label(label)
+            }
+            None => self.push_node(NodeType::Error(CompileErrorKind::OutsideLoop("continue"))),
+        }
+    }
+
+    fn loop_body<F>(mut self, continue_label: String, break_label: String, body: F) -> Self
+    where
+        F: Fn(Self) -> Self,
+    {
+        self.loops.push(LoopLabels {
+            continue_label,
+            break_label,
+        });
+        let mut compiler = body(self);
+        compiler.loops.pop();
+        compiler
+    }
+
+    /// Generated labels are numbered to keep them unique
+    fn next_label_id(&mut self) -> usize {
+        let id = self.label_count;
+        self.label_count += 1;
+        id
+    }
 }
@@ -0,0 +1,107 @@
+use crate::compiler::char_map::CharMap;
+use crate::compiler::error::CompileErrorKind;
+use crate::compiler::node::{NodeType, SymbolNode};
+use crate::compiler::{Compiler, ALIGN_DATA};
+use std::path::Path;
+
+// Every helper labels its data with the name and records it as a data region
+impl Compiler {
+    /// The character map strings are encoded with
+    pub fn with_char_map(mut self, char_map: CharMap) -> Self {
+        self.char_map = char_map;
+        self
+    }
+
+    /// A string prefixed with its length in bytes, at most 255
+    #[track_caller]
+    pub fn string_prefixed(self, name: impl Into<String>, text: &str) -> Self {
+        let bytes = match self.char_map.encode(text) {
+            Ok(bytes) => bytes,
+            Err(char) => return self.data_error(CompileErrorKind::UnmappedCharacter(char)),
+        };
+        let Ok(len) = u8::try_from(bytes.len()) else {
+            return self.data_error(CompileErrorKind::StringTooLong(bytes.len()));
+        };
+        self.data_start(name)
+            .push_byte(len)
+            .push_data(bytes)
+            .data_end()
+    }
+
+    /// A string followed by a zero byte
+    #[track_caller]
+    pub fn string_null_terminated(self, name: impl Into<String>, text: &str) -> Self {
+        let bytes = match self.char_map.encode(text) {
+            Ok(bytes) => bytes,
+            Err(char) => return self.data_error(CompileErrorKind::UnmappedCharacter(char)),
+        };
+        self.data_start(name)
+            .push_data(bytes)
+            .push_byte(0x00)
+            .data_end()
+    }
+
+    /// The addresses of the labels as words, the labels may be defined later
+    #[track_caller]
+    pub fn jump_table<I>(self, name: impl Into<String>, labels: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let mut compiler = self.data_start(name);
+        for label in labels {
+            compiler = compiler.push_label_word(label);
+        }
+        compiler.data_end()
+    }
+
+    /// The contents of a file, read when the helper is called
+    #[track_caller]
+    pub fn include_bytes(self, name: impl Into<String>, path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        match std::fs::read(path) {
+            Ok(bytes) => self.data_start(name).push_data(bytes).data_end(),
+            Err(err) => self.data_error(CompileErrorKind::IncludeFailed {
+                path: path.display().to_string(),
+                reason: err.to_string(),
+            }),
+        }
+    }
+
+    /// Pads with the fill byte up to the next multiple of the alignment
+    #[track_caller]
+    pub fn align(self, alignment: u16) -> Self {
+        if alignment == 0 {
+            return self.data_error(CompileErrorKind::InvalidOperand(
+                "The alignment can't be 0".to_string(),
+            ));
+        }
+        let padding = (alignment - self.push_position % alignment) % alignment;
+        if padding == 0 {
+            return self;
+        }
+        let fill_byte = self.fill_byte;
+        self.push_node(NodeType::Symbol(SymbolNode::DataStart(
+            ALIGN_DATA.to_string(),
+        )))
+        .push_data(vec![fill_byte; padding as usize])
+        .data_end()
+    }
+
+    #[track_caller]
+    fn data_start(self, name: impl Into<String>) -> Self {
+        let name = name.into();
+        self.label(name.clone())
+            .push_node(NodeType::Symbol(SymbolNode::DataStart(name)))
+    }
+
+    #[track_caller]
+    fn data_end(self) -> Self {
+        self.push_node(NodeType::Symbol(SymbolNode::DataEnd))
+    }
+
+    #[track_caller]
+    fn data_error(self, kind: CompileErrorKind) -> Self {
+        self.push_node(NodeType::Error(kind))
+    }
+}
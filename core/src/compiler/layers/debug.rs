@@ -2,6 +2,7 @@ use crate::compiler::node::NodeType;
 use crate::compiler::Compiler;
 
 impl Compiler {
+    #[track_caller]
     pub fn breakpoint(self) -> Self {
         self.push_node(NodeType::BreakPoint)
     }
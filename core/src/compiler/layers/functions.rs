@@ -0,0 +1,55 @@
+use crate::compiler::error::CompileErrorKind;
+use crate::compiler::node::{NodeType, SymbolNode};
+use crate::compiler::Compiler;
+use crate::console::components::cpu::registers::R16S;
+
+impl Compiler {
+    /// Emits a function that call sites reach with `call_label(name)`, execution skips over it where it's defined.\
+    /// The clobbered register pairs are saved on entry and restored before returning,
+    /// saving AF also restores the flags.
+    #[track_caller]
+    pub fn function<F>(self, name: impl Into<String>, clobbers: &[R16S], body: F) -> Self
+    where
+        F: Fn(Self) -> Self,
+    {
+        let name = name.into();
+        let end_label = format!("{name}.end");
+        let return_label = format!("{name}.return");
+
+        let mut compiler = self
+            .jump_label(end_label.clone())
+            .push_node(NodeType::Symbol(SymbolNode::FunctionStart(name.clone())))
+            .label(name);
+        for r16s in clobbers {
+            compiler = compiler.stack_push(*r16s);
+        }
+
+        // Loops around the definition can't be left from inside the function
+        let loops = std::mem::take(&mut compiler.loops);
+        compiler.functions.push(return_label.clone());
+        compiler = body(compiler);
+        compiler.functions.pop();
+        compiler.loops = loops;
+
+        compiler = compiler.label(return_label);
+        for r16s in clobbers.iter().rev() {
+            compiler = compiler.stack_pop(*r16s);
+        }
+        compiler
+            .ret()
+            .push_node(NodeType::Symbol(SymbolNode::FunctionEnd))
+            .label(end_label)
+    }
+
+    /// Return from the innermost function, restoring the clobbered registers
+    #[track_caller]
+    pub fn function_return(self) -> Self {
+        match self.functions.last() {
+            Some(label) => {
+                let label = label.clone();
+                self.jump_label(label)
+            }
+            None => self.push_node(NodeType::Error(CompileErrorKind::OutsideFunction)),
+        }
+    }
+}
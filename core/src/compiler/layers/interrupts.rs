@@ -0,0 +1,118 @@
+use crate::compiler::error::CompileErrorKind;
+use crate::compiler::node::{NodeType, SymbolNode};
+use crate::compiler::Compiler;
+use crate::console::components::bus::Bus;
+use crate::console::components::cpu::instructions::CPUInstruction;
+use crate::console::components::cpu::interrupts::{InterruptFlags, IV_END, IV_SIZE, IV_TIMER};
+use crate::console::components::cpu::registers::{R16, R16S, R8};
+
+/// Where the program continues after skipping the vector area
+const VECTORS_END_LABEL: &str = "interrupt_vectors.end";
+
+impl Compiler {
+    /// Handles an interrupt, saving all registers and enabling interrupts again on return.\
+    /// Handlers that fit are placed at the vector, larger ones where `on_interrupt` is called with a jump at the vector.
+    ///
+    /// The first call emits the startup code: it writes `INTERRUPT_ENABLE`, enables interrupts and
+    /// skips the vector area if the program would run into it, so it belongs at the start of the program.
+    /// The startup code clobbers A and HL.
+    #[track_caller]
+    pub fn on_interrupt<F>(self, interrupt: InterruptFlags, handler: F) -> Self
+    where
+        F: Fn(Self) -> Self,
+    {
+        let Some(vector) = interrupt.vector() else {
+            return self.push_node(NodeType::Error(CompileErrorKind::InvalidInterrupt(
+                interrupt.bits(),
+            )));
+        };
+        let (name, _) = interrupt
+            .iter_names()
+            .next()
+            .expect("A single interrupt has a name");
+        let label = format!("interrupt_{}", name.to_ascii_lowercase());
+
+        let mut compiler = self.enable_interrupt(interrupt);
+        let position = compiler.push_position;
+        let node_count = compiler.nodes.len();
+        compiler = compiler
+            .set_push_position(vector)
+            .interrupt_handler(&label, &handler);
+        let size = compiler.push_position.wrapping_sub(vector);
+        if size <= IV_SIZE {
+            return compiler.set_push_position(position);
+        }
+
+        // Too large for the vector, move it out of the vector area and jump to it from there
+        let handler_nodes = compiler.nodes.split_off(node_count);
+        let end_label = format!("{label}.end");
+        let mut compiler = compiler
+            .set_push_position(vector)
+            .jump_label(label)
+            .set_push_position(position)
+            .jump_label(end_label.clone());
+        let start = compiler.push_position;
+        compiler
+            .nodes
+            .extend(handler_nodes.into_iter().map(|mut node| {
+                node.address = node.address.wrapping_sub(vector).wrapping_add(start);
+                node
+            }));
+        compiler
+            .set_push_position(start.wrapping_add(size))
+            .label(end_label)
+    }
+
+    #[track_caller]
+    fn interrupt_handler<F>(self, label: &str, handler: &F) -> Self
+    where
+        F: Fn(Self) -> Self,
+    {
+        let mut compiler = self
+            .push_node(NodeType::Symbol(SymbolNode::FunctionStart(
+                label.to_string(),
+            )))
+            .label(label);
+        for r16s in R16S::ALL {
+            compiler = compiler.stack_push(r16s);
+        }
+        // The handler runs on its own, it can't leave the loops or functions around it
+        let loops = std::mem::take(&mut compiler.loops);
+        let functions = std::mem::take(&mut compiler.functions);
+        compiler = handler(compiler);
+        compiler.loops = loops;
+        compiler.functions = functions;
+        for r16s in R16S::ALL.into_iter().rev() {
+            compiler = compiler.stack_pop(r16s);
+        }
+        compiler
+            .ret_interrupt()
+            .push_node(NodeType::Symbol(SymbolNode::FunctionEnd))
+    }
+
+    /// Adds the interrupt to the value the startup code writes, emitting the startup code on the first call
+    #[track_caller]
+    fn enable_interrupt(mut self, interrupt: InterruptFlags) -> Self {
+        if let Some(index) = self.interrupt_enable {
+            if let NodeType::Data(data) = &mut self.nodes[index].node_type {
+                data[0] |= interrupt.bits();
+            }
+            return self;
+        }
+
+        let mut compiler = self.push_instruction(CPUInstruction::LoadR8i(R8::A));
+        compiler.interrupt_enable = Some(compiler.nodes.len());
+        compiler = compiler
+            .push_byte(interrupt.bits())
+            .load_r16i(R16::HL, Bus::INTERRUPT_ENABLE)
+            .load_r8(R8::HL, R8::A)
+            .enable_interrupts();
+        if compiler.push_position > IV_TIMER {
+            return compiler;
+        }
+        compiler
+            .jump_label(VECTORS_END_LABEL)
+            .set_push_position(IV_END)
+            .label(VECTORS_END_LABEL)
+    }
+}
@@ -0,0 +1,47 @@
+use crate::compiler::node::{NodeType, SymbolNode};
+use crate::compiler::Compiler;
+use crate::console::cartridge::symbols::SourceLocation;
+
+impl Compiler {
+    /// Name the current position, the name shows up in the disassembly and can be used for breakpoints
+    #[track_caller]
+    pub fn label(self, name: impl Into<String>) -> Self {
+        self.push_symbol(SymbolNode::Label(name.into()))
+    }
+
+    /// Record everything the function pushes as the body of a named function
+    #[track_caller]
+    pub fn function_symbol<F>(self, name: impl Into<String>, f: F) -> Self
+    where
+        F: Fn(Self) -> Self,
+    {
+        let compiler = self.push_symbol(SymbolNode::FunctionStart(name.into()));
+        f(compiler).push_symbol(SymbolNode::FunctionEnd)
+    }
+
+    /// Record everything the function pushes as data, so it isn't disassembled as code
+    #[track_caller]
+    pub fn data_region<F>(self, name: impl Into<String>, f: F) -> Self
+    where
+        F: Fn(Self) -> Self,
+    {
+        let compiler = self.push_symbol(SymbolNode::DataStart(name.into()));
+        f(compiler).push_symbol(SymbolNode::DataEnd)
+    }
+
+    /// Attribute the following code to a position in a source file
+    #[track_caller]
+    pub fn source_location(self, file: impl Into<String>, line: u32, column: u32) -> Self {
+        self.push_symbol(SymbolNode::Source(SourceLocation {
+            address: 0,
+            file: file.into(),
+            line,
+            column,
+        }))
+    }
+
+    #[track_caller]
+    fn push_symbol(self, symbol: SymbolNode) -> Self {
+        self.push_node(NodeType::Symbol(symbol))
+    }
+}
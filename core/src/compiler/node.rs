@@ -1,12 +1,32 @@
+use crate::compiler::error::{CompileErrorKind, NodeError};
 use crate::compiler::CompilationContext;
+use crate::console::cartridge::symbols::SourceLocation;
 use crate::console::components::cpu::instructions::CPUInstruction;
+use std::panic::Location;
 
 #[derive(Debug)]
 pub enum NodeType {
     #[cfg(feature = "debugger")]
     BreakPoint,
     Data(Vec<u8>),
+    /// A mistake made while building, reported on compilation
+    Error(CompileErrorKind),
     Instruction(CPUInstruction),
+    /// The address of a label as a little endian word, filled in on compilation
+    LabelWord(String),
+    Symbol(SymbolNode),
+}
+
+/// Debug information for the address of the node, takes no space in the binary
+#[derive(Debug)]
+pub enum SymbolNode {
+    Label(String),
+    FunctionStart(String),
+    FunctionEnd,
+    DataStart(String),
+    DataEnd,
+    /// The address of the location is filled in on compilation
+    Source(SourceLocation),
 }
 
 impl NodeType {
@@ -14,7 +34,11 @@ impl NodeType {
         match self {
             NodeType::BreakPoint => 0,
             NodeType::Data(data) => data.len() as u16,
-            NodeType::Instruction(instr) => instr.byte_count() as u16,
+            NodeType::Error(_) => 0,
+            // Operands are pushed as separate data nodes
+            NodeType::Instruction(_) => 1,
+            NodeType::LabelWord(_) => 2,
+            NodeType::Symbol(_) => 0,
         }
     }
 }
@@ -23,19 +47,48 @@ impl NodeType {
 pub struct Node {
     pub node_type: NodeType,
     pub address: u16,
+    /// The builder call that pushed the node
+    pub location: &'static Location<'static>,
 }
 
 impl Node {
+    #[track_caller]
     pub fn new(node_type: NodeType, address: u16) -> Self {
-        Self { node_type, address }
+        Self {
+            node_type,
+            address,
+            location: Location::caller(),
+        }
     }
 
-    pub fn compile(&self, ctx: &mut CompilationContext) {
-        match &self.node_type {
-            #[cfg(feature = "debugger")]
-            NodeType::BreakPoint => {}
-            NodeType::Data(data) => ctx.data.extend(data),
-            NodeType::Instruction(instr) => ctx.data.push(u8::try_from(*instr).unwrap()),
+    pub fn error(&self, index: usize, kind: CompileErrorKind) -> NodeError {
+        NodeError {
+            kind,
+            node: index,
+            location: self.location,
         }
     }
+
+    /// The bytes of the node, placed at its address by the compiler
+    pub fn compile(&self, ctx: &mut CompilationContext) -> Result<Vec<u8>, CompileErrorKind> {
+        Ok(match &self.node_type {
+            #[cfg(feature = "debugger")]
+            NodeType::BreakPoint => Vec::new(),
+            NodeType::Data(data) => data.clone(),
+            NodeType::Error(kind) => return Err(kind.clone()),
+            NodeType::Instruction(instr) => {
+                vec![u8::try_from(*instr).map_err(CompileErrorKind::InvalidInstruction)?]
+            }
+            NodeType::LabelWord(name) => ctx
+                .labels
+                .get(name)
+                .ok_or_else(|| CompileErrorKind::UndefinedLabel(name.clone()))?
+                .to_le_bytes()
+                .to_vec(),
+            NodeType::Symbol(symbol) => {
+                ctx.symbols.push(self.address, symbol);
+                Vec::new()
+            }
+        })
+    }
 }
@@ -0,0 +1,490 @@
+use crate::compiler::node::{Node, NodeType, SymbolNode};
+use crate::compiler::{Compiler, ALIGN_DATA};
+use crate::console::components::cpu::instructions::CPUInstruction;
+use crate::console::components::cpu::registers::R8;
+use crate::console::components::rom::ROM_SIZE;
+use std::collections::BTreeMap;
+use std::panic::Location;
+
+/// The operand bytes following an instruction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operand {
+    Byte(u8),
+    Word(u16),
+    /// The address of a label, filled in on compilation
+    Label(String),
+}
+
+/// An instruction along with its operand, the unit rewrite rules work on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Op {
+    pub instruction: CPUInstruction,
+    pub operand: Option<Operand>,
+}
+
+impl Op {
+    pub fn new(instruction: CPUInstruction) -> Self {
+        Self {
+            instruction,
+            operand: None,
+        }
+    }
+
+    pub fn with_operand(instruction: CPUInstruction, operand: Operand) -> Self {
+        Self {
+            instruction,
+            operand: Some(operand),
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.instruction.byte_count()
+    }
+}
+
+/// Replaces the first `consumed` ops of a window
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rewrite {
+    pub consumed: usize,
+    pub replacement: Vec<Op>,
+}
+
+/// A rewrite rule, it must not change what the ops do.\
+/// The window never spans a label, so jumps can't land between the ops it sees.
+/// Rewrites that don't save any bytes are ignored.
+pub trait PeepholeRule {
+    fn name(&self) -> &'static str;
+
+    /// A rewrite of the ops at the start of the window, if the rule applies there
+    fn rewrite(&self, window: &[Op]) -> Option<Rewrite>;
+}
+
+/// `NOP` does nothing
+pub struct RemoveNoOps;
+
+impl PeepholeRule for RemoveNoOps {
+    fn name(&self) -> &'static str {
+        "remove-nops"
+    }
+
+    fn rewrite(&self, window: &[Op]) -> Option<Rewrite> {
+        matches!(window.first()?.instruction, CPUInstruction::NoOp).then(|| Rewrite {
+            consumed: 1,
+            replacement: Vec::new(),
+        })
+    }
+}
+
+/// `PUSH rr; POP rr` leaves the registers as they were
+pub struct PushPop;
+
+impl PeepholeRule for PushPop {
+    fn name(&self) -> &'static str {
+        "push-pop"
+    }
+
+    fn rewrite(&self, window: &[Op]) -> Option<Rewrite> {
+        match (&window.first()?.instruction, &window.get(1)?.instruction) {
+            (CPUInstruction::Push(pushed), CPUInstruction::Pop(popped)) if pushed == popped => {
+                Some(Rewrite {
+                    consumed: 2,
+                    replacement: Vec::new(),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// `LD x, y; LD y, x` only needs the first load.\
+/// Loads through `[HL]` are kept, the memory could be a device register.
+pub struct RedundantLoads;
+
+impl PeepholeRule for RedundantLoads {
+    fn name(&self) -> &'static str {
+        "redundant-loads"
+    }
+
+    fn rewrite(&self, window: &[Op]) -> Option<Rewrite> {
+        let CPUInstruction::LoadR8((target, source)) = window.first()?.instruction else {
+            return None;
+        };
+        if target == R8::HL || source == R8::HL {
+            return None;
+        }
+        match window.get(1)?.instruction {
+            CPUInstruction::LoadR8((back_target, back_source))
+                if back_target == source && back_source == target =>
+            {
+                Some(Rewrite {
+                    consumed: 2,
+                    replacement: vec![window[0].clone()],
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// `CALL x; RET` returns to the same place as `JP x`
+pub struct TailCall;
+
+impl PeepholeRule for TailCall {
+    fn name(&self) -> &'static str {
+        "tail-call"
+    }
+
+    fn rewrite(&self, window: &[Op]) -> Option<Rewrite> {
+        match (&window.first()?.instruction, &window.get(1)?.instruction) {
+            (CPUInstruction::Call, CPUInstruction::Return) => Some(Rewrite {
+                consumed: 2,
+                replacement: vec![Op {
+                    instruction: CPUInstruction::Jump,
+                    operand: window[0].operand.clone(),
+                }],
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Runs rewrite rules over the nodes of a compiler, see `Compiler::optimize`
+pub struct Optimizer {
+    rules: Vec<Box<dyn PeepholeRule>>,
+}
+
+impl Default for Optimizer {
+    /// All built-in rules
+    fn default() -> Self {
+        Self::empty()
+            .with_rule(RemoveNoOps)
+            .with_rule(PushPop)
+            .with_rule(RedundantLoads)
+            .with_rule(TailCall)
+    }
+}
+
+impl Optimizer {
+    pub fn empty() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// Rules are tried in the order they were added
+    pub fn with_rule(mut self, rule: impl PeepholeRule + 'static) -> Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    /// Rewrites the ops until no rule applies anymore
+    fn run(&self, units: &mut Vec<CodeUnit>, report: &mut OptimizationReport) {
+        let mut index = 0;
+        while index < units.len() {
+            let window: Vec<Op> = units[index..]
+                .iter()
+                .filter_map(|unit| unit.op.clone())
+                .collect();
+            let Some((name, rewrite, saved)) = self.rules.iter().find_map(|rule| {
+                let rewrite = rule.rewrite(&window)?;
+                let consumed = window.get(..rewrite.consumed)?;
+                let saved = size_of(consumed).checked_sub(size_of(&rewrite.replacement))?;
+                (saved > 0).then_some((rule.name(), rewrite, saved))
+            }) else {
+                index += 1;
+                continue;
+            };
+
+            report.bytes_saved += saved;
+            *report.rewrites.entry(name).or_default() += 1;
+            let padding = if report.padded { saved } else { 0 };
+            Self::apply(units, index, rewrite, padding);
+            // The rewrite may have completed a pattern starting at the previous op
+            index = index.saturating_sub(1);
+        }
+    }
+
+    fn apply(units: &mut Vec<CodeUnit>, index: usize, rewrite: Rewrite, padding: usize) {
+        let consumed: Vec<CodeUnit> = units.splice(index..index + rewrite.consumed, []).collect();
+        let location = consumed[0].location;
+        let mut fixed_address = consumed[0].fixed_address;
+        let mut leading: Vec<Node> = consumed.into_iter().flat_map(|unit| unit.leading).collect();
+
+        let replacement: Vec<CodeUnit> = rewrite
+            .replacement
+            .into_iter()
+            .map(|op| CodeUnit {
+                op: Some(op),
+                leading: std::mem::take(&mut leading),
+                location,
+                fixed_address: fixed_address.take(),
+            })
+            .collect();
+        let replaced = replacement.len();
+        units.splice(index..index, replacement);
+        // NOPs in place of the saved bytes keep the code after them where it was
+        leading.extend((0..padding).map(|_| Node {
+            node_type: NodeType::Instruction(CPUInstruction::NoOp),
+            address: 0,
+            location,
+        }));
+        if replaced > 0 && leading.is_empty() {
+            return;
+        }
+
+        // The symbols and the address of the removed ops move on to the next op
+        match units.get_mut(index + replaced) {
+            Some(next) => {
+                leading.append(&mut next.leading);
+                next.leading = leading;
+                next.fixed_address = next.fixed_address.or(fixed_address);
+            }
+            None => units.push(CodeUnit {
+                op: None,
+                leading,
+                location,
+                fixed_address,
+            }),
+        }
+    }
+}
+
+fn size_of(ops: &[Op]) -> usize {
+    ops.iter().map(Op::size).sum()
+}
+
+/// What an optimization pass changed
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct OptimizationReport {
+    pub bytes_saved: usize,
+    /// The program holds numeric words that could be ROM addresses, like `jump(0x0010)`.
+    /// Moving code would leave them pointing at the wrong bytes,
+    /// so everything stayed in place and the saved bytes were filled with NOPs.
+    pub padded: bool,
+    /// How often each rule was applied, by name
+    pub rewrites: BTreeMap<&'static str, usize>,
+}
+
+/// An op with the symbols before it, the op is `None` once everything after the symbols was removed
+struct CodeUnit {
+    op: Option<Op>,
+    /// Symbols other than labels, like source locations, and the NOPs padding saved bytes, placed right before the op
+    leading: Vec<Node>,
+    location: &'static Location<'static>,
+    /// The position was set explicitly, so the op stays there instead of moving up
+    fixed_address: Option<u16>,
+}
+
+/// Collects the optimized nodes, moving them up into the saved bytes
+struct Emitter {
+    nodes: Vec<Node>,
+    position: u16,
+    /// The node the startup code of `on_interrupt` writes its flags to
+    interrupt_enable: Option<usize>,
+    new_interrupt_enable: Option<usize>,
+}
+
+impl Emitter {
+    /// Continues at an explicitly set position, even if no node ends up there
+    fn move_to(&mut self, fixed_address: Option<u16>) {
+        if let Some(address) = fixed_address {
+            self.position = address;
+        }
+    }
+
+    fn emit(&mut self, node_type: NodeType, location: &'static Location<'static>) {
+        let address = self.position;
+        self.position = address.wrapping_add(node_type.size());
+        self.nodes.push(Node {
+            node_type,
+            address,
+            location,
+        });
+    }
+
+    fn emit_node(&mut self, index: usize, node: Node) {
+        if self.interrupt_enable == Some(index) {
+            self.new_interrupt_enable = Some(self.nodes.len());
+        }
+        self.emit(node.node_type, node.location);
+    }
+
+    fn emit_unit(&mut self, unit: CodeUnit) {
+        self.move_to(unit.fixed_address);
+        for node in unit.leading {
+            self.emit(node.node_type, node.location);
+        }
+        let Some(op) = unit.op else {
+            return;
+        };
+        self.emit(NodeType::Instruction(op.instruction), unit.location);
+        let operand = match op.operand {
+            None => return,
+            Some(Operand::Byte(byte)) => NodeType::Data(vec![byte]),
+            Some(Operand::Word(word)) => NodeType::Data(word.to_le_bytes().to_vec()),
+            Some(Operand::Label(label)) => NodeType::LabelWord(label),
+        };
+        self.emit(operand, unit.location);
+    }
+}
+
+/// The ops between two nodes the optimizer can't look past
+#[derive(Default)]
+struct Run {
+    units: Vec<CodeUnit>,
+    /// Symbols waiting for the next op
+    symbols: Vec<Node>,
+    symbols_address: Option<u16>,
+}
+
+impl Run {
+    fn flush(
+        &mut self,
+        optimizer: &Optimizer,
+        report: &mut OptimizationReport,
+        emitter: &mut Emitter,
+    ) {
+        let mut units = std::mem::take(&mut self.units);
+        optimizer.run(&mut units, report);
+        for unit in units {
+            emitter.emit_unit(unit);
+        }
+        emitter.move_to(self.symbols_address.take());
+        for node in std::mem::take(&mut self.symbols) {
+            emitter.emit(node.node_type, node.location);
+        }
+    }
+}
+
+impl Compiler {
+    /// Rewrites redundant instruction sequences with the optimizer's rules, call it once the program is built.\
+    /// Code moves up into the saved bytes, up to the next position that was set explicitly.
+    /// Labels move along with the code they name and `align` padding grows, so the data after it stays in place.
+    /// Numeric addresses can't be adjusted, so if the program holds a word in ROM range,
+    /// the code stays in place and the saved bytes are padded with NOPs instead.
+    pub fn optimize(mut self, optimizer: &Optimizer) -> (Self, OptimizationReport) {
+        let mut report = OptimizationReport {
+            padded: self.has_numeric_rom_words(),
+            ..OptimizationReport::default()
+        };
+        let mut emitter = Emitter {
+            nodes: Vec::with_capacity(self.nodes.len()),
+            position: 0,
+            interrupt_enable: self.interrupt_enable,
+            new_interrupt_enable: None,
+        };
+        let mut run = Run::default();
+        let mut expected_address = None;
+        let mut after_align_start = false;
+        let mut nodes = std::mem::take(&mut self.nodes)
+            .into_iter()
+            .enumerate()
+            .peekable();
+
+        while let Some((index, node)) = nodes.next() {
+            let explicit = expected_address != Some(node.address);
+            expected_address = Some(node.address.wrapping_add(node.node_type.size()));
+            if explicit {
+                run.flush(optimizer, &mut report, &mut emitter);
+            }
+            let fixed_address = explicit.then_some(node.address);
+            let align_start = matches!(
+                &node.node_type,
+                NodeType::Symbol(SymbolNode::DataStart(name)) if name == ALIGN_DATA
+            );
+            let aligning = std::mem::replace(&mut after_align_start, align_start);
+
+            match &node.node_type {
+                NodeType::Symbol(symbol) if !matches!(symbol, SymbolNode::Label(_)) => {
+                    run.symbols_address = run.symbols_address.or(fixed_address);
+                    run.symbols.push(node);
+                    continue;
+                }
+                NodeType::Instruction(instruction) => {
+                    let instruction = *instruction;
+                    let operand_size = instruction.byte_count() - 1;
+                    let operand = match nodes.peek() {
+                        _ if operand_size == 0 => Some(None),
+                        Some((next_index, next))
+                            if Some(*next_index) != self.interrupt_enable
+                                && expected_address == Some(next.address) =>
+                        {
+                            Self::operand(&next.node_type, operand_size).map(Some)
+                        }
+                        _ => None,
+                    };
+                    if let Some(operand) = operand {
+                        if operand.is_some() {
+                            // The operand node was peeked above
+                            nodes.next();
+                            expected_address = expected_address
+                                .map(|address| address.wrapping_add(operand_size as u16));
+                        }
+                        run.units.push(CodeUnit {
+                            op: Some(Op {
+                                instruction,
+                                operand,
+                            }),
+                            leading: std::mem::take(&mut run.symbols),
+                            location: node.location,
+                            fixed_address: run.symbols_address.take().or(fixed_address),
+                        });
+                        continue;
+                    }
+                }
+                NodeType::Data(data) if aligning => {
+                    // The padding takes up the bytes saved before it, the aligned data stays where it was
+                    run.flush(optimizer, &mut report, &mut emitter);
+                    emitter.move_to(fixed_address);
+                    let end = node.address.wrapping_add(data.len() as u16);
+                    let padding = vec![data[0]; end.wrapping_sub(emitter.position) as usize];
+                    emitter.emit(NodeType::Data(padding), node.location);
+                    continue;
+                }
+                _ => {}
+            }
+
+            run.flush(optimizer, &mut report, &mut emitter);
+            emitter.move_to(fixed_address);
+            emitter.emit_node(index, node);
+        }
+        run.flush(optimizer, &mut report, &mut emitter);
+
+        self.nodes = emitter.nodes;
+        self.push_position = emitter.position;
+        self.interrupt_enable = emitter.new_interrupt_enable;
+        (self, report)
+    }
+
+    /// Whether a word operand or a pushed word could be a ROM address.\
+    /// Data regions like strings and tables aren't checked, addresses in there aren't adjusted either.
+    fn has_numeric_rom_words(&self) -> bool {
+        let mut open_data = 0usize;
+        self.nodes.iter().any(|node| match &node.node_type {
+            NodeType::Symbol(SymbolNode::DataStart(_)) => {
+                open_data += 1;
+                false
+            }
+            NodeType::Symbol(SymbolNode::DataEnd) => {
+                open_data = open_data.saturating_sub(1);
+                false
+            }
+            NodeType::Data(data) if open_data == 0 => match data[..] {
+                [low, high] => (u16::from_le_bytes([low, high]) as usize) < ROM_SIZE,
+                _ => false,
+            },
+            _ => false,
+        })
+    }
+
+    /// The operand an operand node of the given size holds
+    fn operand(node_type: &NodeType, size: usize) -> Option<Operand> {
+        match node_type {
+            NodeType::Data(data) if data.len() == size => Some(match data[..] {
+                [byte] => Operand::Byte(byte),
+                [low, high] => Operand::Word(u16::from_le_bytes([low, high])),
+                _ => return None,
+            }),
+            NodeType::LabelWord(label) if size == 2 => Some(Operand::Label(label.clone())),
+            _ => None,
+        }
+    }
+}
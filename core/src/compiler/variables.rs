@@ -0,0 +1,162 @@
+use crate::compiler::error::CompileErrorKind;
+use crate::compiler::node::{Node, NodeType};
+use crate::compiler::Compiler;
+use crate::console::cartridge::symbols::SymbolRange;
+use crate::console::components::bus::Bus;
+use crate::console::components::cpu::registers::{R16, R8};
+
+/// A byte in RAM
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct VarU8 {
+    pub address: u16,
+}
+
+/// A little endian word in RAM
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct VarU16 {
+    pub address: u16,
+}
+
+/// Consecutive bytes in RAM
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Array {
+    pub address: u16,
+    pub len: u16,
+}
+
+impl Array {
+    /// The byte at a constant index
+    pub fn element(&self, index: u16) -> Option<VarU8> {
+        (index < self.len).then(|| VarU8 {
+            address: self.address + index,
+        })
+    }
+}
+
+impl Compiler {
+    /// The RAM kept free for the stack, below the boot SP of the target profile
+    pub const STACK_SIZE: u16 = 0x0100;
+
+    /// The end of the RAM variables are allocated in, exclusive
+    pub fn variables_end(&self) -> u16 {
+        self.profile.config().boot_sp - Self::STACK_SIZE + 1
+    }
+
+    #[track_caller]
+    pub fn var_u8(&mut self, name: impl Into<String>) -> VarU8 {
+        VarU8 {
+            address: self.allocate(name.into(), 1),
+        }
+    }
+
+    #[track_caller]
+    pub fn var_u16(&mut self, name: impl Into<String>) -> VarU16 {
+        VarU16 {
+            address: self.allocate(name.into(), 2),
+        }
+    }
+
+    #[track_caller]
+    pub fn array(&mut self, name: impl Into<String>, len: u16) -> Array {
+        Array {
+            address: self.allocate(name.into(), len),
+            len,
+        }
+    }
+
+    /// Reserves the next free bytes of RAM, problems are reported on compilation
+    #[track_caller]
+    fn allocate(&mut self, name: String, size: u16) -> u16 {
+        let address = self
+            .variables
+            .last()
+            .map_or(Bus::RAM_START, |variable| variable.end + 1);
+        let error = if self.variables.iter().any(|variable| variable.name == name) {
+            Some(CompileErrorKind::DuplicateVariable(name.clone()))
+        } else if size == 0 || self.variables_end() - address < size {
+            Some(CompileErrorKind::OutOfRAM(name.clone()))
+        } else {
+            None
+        };
+        if let Some(kind) = error {
+            self.nodes
+                .push(Node::new(NodeType::Error(kind), self.push_position));
+            return address;
+        }
+
+        self.variables.push(SymbolRange {
+            name,
+            start: address,
+            end: address + size - 1,
+        });
+        address
+    }
+}
+
+// Loads and stores, they go through HL
+impl Compiler {
+    /// Loads the variable into the register, clobbers HL
+    #[track_caller]
+    pub fn load_u8(self, r8: R8, variable: VarU8) -> Self {
+        self.load_r16i(R16::HL, variable.address)
+            .load_r8(r8, R8::HL)
+    }
+
+    /// Stores the register in the variable, clobbers HL so H and L can't be stored
+    #[track_caller]
+    pub fn store_u8(self, variable: VarU8, r8: R8) -> Self {
+        if matches!(r8, R8::H | R8::L | R8::HL) {
+            return self.push_node(NodeType::Error(CompileErrorKind::InvalidOperand(format!(
+                "{r8:?} can't be stored, the store goes through HL"
+            ))));
+        }
+        self.load_r16i(R16::HL, variable.address)
+            .load_r8(R8::HL, r8)
+    }
+
+    /// Loads the variable into BC, DE or HL, clobbers HL and, when loading HL, A
+    #[track_caller]
+    pub fn load_u16(self, r16: R16, variable: VarU16) -> Self {
+        let compiler = self.load_r16i(R16::HL, variable.address);
+        match r16 {
+            R16::BC | R16::DE => {
+                let (high, low) = Self::r16_bytes(r16);
+                compiler
+                    .load_r8(low, R8::HL)
+                    .increment_r16(R16::HL)
+                    .load_r8(high, R8::HL)
+            }
+            R16::HL => compiler
+                .load_r8(R8::A, R8::HL)
+                .increment_r16(R16::HL)
+                .load_r8(R8::H, R8::HL)
+                .load_r8(R8::L, R8::A),
+            R16::SP => compiler.push_node(NodeType::Error(CompileErrorKind::InvalidOperand(
+                "SP can't be loaded from a variable".to_string(),
+            ))),
+        }
+    }
+
+    /// Stores BC or DE in the variable, clobbers HL
+    #[track_caller]
+    pub fn store_u16(self, variable: VarU16, r16: R16) -> Self {
+        if matches!(r16, R16::HL | R16::SP) {
+            return self.push_node(NodeType::Error(CompileErrorKind::InvalidOperand(format!(
+                "{r16:?} can't be stored, the store goes through HL"
+            ))));
+        }
+        let (high, low) = Self::r16_bytes(r16);
+        self.load_r16i(R16::HL, variable.address)
+            .load_r8(R8::HL, low)
+            .increment_r16(R16::HL)
+            .load_r8(R8::HL, high)
+    }
+
+    fn r16_bytes(r16: R16) -> (R8, R8) {
+        match r16 {
+            R16::BC => (R8::B, R8::C),
+            R16::DE => (R8::D, R8::E),
+            R16::HL | R16::SP => (R8::H, R8::L),
+        }
+    }
+}
@@ -1,45 +1,160 @@
 use crate::console::cartridge::Cartridge;
+use crate::console::components::boot_rom::BootROM;
+use crate::console::components::cpu::step_flags::CPUStepFlags;
 use crate::console::components::rom::ROM;
+use crate::console::components::sram::SRAM;
+use crate::console::components::watchdog::{WatchdogAction, WatchdogControl};
+use crate::console::config::{ConsoleConfig, Peripherals};
 use crate::console::input::ConsoleInput;
+use crate::console::link::{NullLink, SerialLink};
 use crate::console::step::ConsoleStep;
-use crate::error::LMVC8Result;
+use crate::error::{LMVC8Error, LMVC8Result};
 use components::{bus, cpu};
 
 pub mod cartridge;
 pub mod components;
+pub mod config;
 pub mod input;
+pub mod link;
 pub mod step;
 pub mod types;
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Clone)]
 pub struct Console {
     pub cpu: cpu::CPU,
     pub bus: bus::Bus,
+    config: ConsoleConfig,
+    /// PC and SP the program starts with, from the cartridge or the config
+    reset_vector: ResetVector,
+}
+
+/// Registers the program expects to be set up on entry
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ResetVector {
+    pub pc: u16,
+    pub sp: u16,
+}
+
+impl Default for Console {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Console {
     pub fn new() -> Self {
-        Self::default()
+        Self::with_config(ConsoleConfig::default()).expect("The default config is valid")
     }
 
+    pub fn with_config(config: ConsoleConfig) -> LMVC8Result<Self> {
+        let mut console = Self {
+            cpu: cpu::CPU::default(),
+            bus: bus::Bus::with_config(&config)?,
+            config,
+            reset_vector: ResetVector {
+                pc: config.boot_pc,
+                sp: config.boot_sp,
+            },
+        };
+        console.install_boot_rom();
+        console.reset();
+        Ok(console)
+    }
+
+    pub fn config(&self) -> &ConsoleConfig {
+        &self.config
+    }
+
+    pub fn reset_vector(&self) -> ResetVector {
+        self.reset_vector
+    }
+
+    /// With a boot ROM the CPU starts there, which then hands over to the reset vector
     pub fn reset(&mut self) {
         self.cpu.reset();
+        let pc = match self.bus.boot_rom {
+            Some(_) => bus::Bus::BOOT_ROM_START,
+            None => self.reset_vector.pc,
+        };
+        self.cpu.boot(pc, self.reset_vector.sp);
         self.bus.reset();
     }
 
+    fn install_boot_rom(&mut self) {
+        if self.config.boot_rom {
+            let splash = self.config.peripherals.contains(Peripherals::DEBUG_PORT);
+            self.bus.boot_rom = Some(BootROM::new(
+                self.reset_vector.pc,
+                self.reset_vector.sp,
+                splash,
+            ));
+        }
+    }
+
     pub fn load_cartridge(&mut self, cartridge: Cartridge) -> LMVC8Result<()> {
+        if !self.config.supports(cartridge.profile) {
+            return Err(LMVC8Error::UnsupportedHardwareProfile(cartridge.profile));
+        }
+
+        self.reset_vector = ResetVector {
+            pc: cartridge.entry_point.unwrap_or(self.config.boot_pc),
+            sp: cartridge.stack_pointer.unwrap_or(self.config.boot_sp),
+        };
+        self.install_boot_rom();
         self.reset();
+        let save_ram = cartridge.save_ram;
         self.bus.rom = ROM::from_cartridge(cartridge)?;
+        self.bus.sram = save_ram.then(SRAM::default);
         Ok(())
     }
 
+    #[inline(always)]
     pub fn step(&mut self) -> ConsoleStep {
-        let cpu_step_flags = self.cpu.step(&mut self.bus);
+        self.step_linked(&mut NullLink)
+    }
+
+    /// Step with the serial port connected to the specified link
+    #[inline(always)]
+    pub fn step_linked(&mut self, link: &mut impl SerialLink) -> ConsoleStep {
+        // The CPU is stalled while the DMA controller owns the bus
+        let cpu_step_flags = if self.bus.dma.is_active() {
+            self.bus.dma_step();
+            CPUStepFlags::empty()
+        } else {
+            self.cpu.step(&mut self.bus)
+        };
         let cycles = self.bus.take_step_cycles();
 
+        if self.bus.serial.clock(cycles) {
+            let incoming = link.exchange(self.bus.serial.data.into());
+            self.bus.serial_complete(incoming);
+        }
+
+        if self.bus.rtc.tick(cycles) {
+            self.bus.ia.set_rtc();
+        }
+
+        let watchdog = self.bus.watchdog.tick(cycles);
+        if let Some(action) = watchdog {
+            self.watchdog_expired(action);
+        }
+
         ConsoleStep {
             cycles,
             cpu_step_flags,
+            watchdog,
+        }
+    }
+
+    #[cold]
+    fn watchdog_expired(&mut self, action: WatchdogAction) {
+        match action {
+            WatchdogAction::Reset => {
+                self.reset();
+                // Lets the program tell a watchdog reset apart from a power on
+                self.bus.watchdog.set_control(WatchdogControl::EXPIRED);
+            }
+            WatchdogAction::Trap => self.bus.ia.set_watchdog(),
         }
     }
 
@@ -52,7 +167,28 @@ impl Console {
         }
     }
 
+    /// Catches the RTC up with the host clock, only has an effect if the RTC is driven by it
+    pub fn sync_rtc(&mut self) {
+        if self.bus.rtc.sync_host() {
+            self.bus.ia.set_rtc();
+        }
+    }
+
     pub fn input(&mut self, input: ConsoleInput) {
         self.bus.input(input);
     }
+
+    /// Everything the program wrote to the debug port since the output was last taken
+    pub fn debug_output(&self) -> &[u8] {
+        self.bus.debug_port.output()
+    }
+
+    pub fn take_debug_output(&mut self) -> Vec<u8> {
+        self.bus.debug_port.take_output()
+    }
+
+    /// Takes the debug output as text, a partly written character stays until the rest follows
+    pub fn take_debug_text(&mut self) -> String {
+        self.bus.debug_port.take_text()
+    }
 }
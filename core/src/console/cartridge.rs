@@ -1,22 +1,282 @@
-use crate::error::LMVC8Result;
-use std::path::Path;
+use crate::console::cartridge::lmc::{LmcHeader, LMC_MIN_VERSION, LMC_TEXT_SIZE, LMC_VERSION};
+use crate::console::cartridge::patch::PatchFormat;
+use crate::console::cartridge::rom_image::{RomImage, RomImageFormat};
+use crate::console::cartridge::symbols::DebugSymbols;
+use crate::console::components::rom::ROM_SIZE;
+use crate::console::config::HardwareProfile;
+use crate::error::{LMVC8Error, LMVC8Result};
+use binrw::{BinRead, BinWrite};
+use std::io::{Cursor, Write};
+use std::path::{Path, PathBuf};
+
+pub mod lmc;
+pub mod patch;
+pub mod rom_image;
+pub mod symbols;
 
 #[derive(Debug)]
 pub struct Cartridge {
     pub binary: Vec<u8>,
+    pub title: String,
+    pub author: String,
+    /// Whether the cartridge provides battery-backed RAM
+    pub save_ram: bool,
+    /// The file this cartridge was loaded from, if any
+    pub path: Option<PathBuf>,
+    /// The hardware the cartridge requires
+    pub profile: HardwareProfile,
+    /// Where execution starts after a reset, defaults to the console's boot PC
+    pub entry_point: Option<u16>,
+    /// SP after a reset, defaults to the console's boot SP
+    pub stack_pointer: Option<u16>,
+    /// Labels and source locations, if the cartridge was built with them
+    pub symbols: Option<DebugSymbols>,
 }
 
 impl Cartridge {
+    pub const EXTENSION: &'static str = "lmc";
+    pub const SAVE_EXTENSION: &'static str = "sav";
+
     pub fn new(binary: Vec<u8>) -> Self {
-        Self { binary }
+        Self {
+            binary,
+            title: String::new(),
+            author: String::new(),
+            save_ram: false,
+            path: None,
+            profile: HardwareProfile::default(),
+            entry_point: None,
+            stack_pointer: None,
+            symbols: None,
+        }
+    }
+
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    pub fn with_author(mut self, author: impl Into<String>) -> Self {
+        self.author = author.into();
+        self
+    }
+
+    pub fn with_save_ram(mut self, save_ram: bool) -> Self {
+        self.save_ram = save_ram;
+        self
+    }
+
+    pub fn with_profile(mut self, profile: HardwareProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    pub fn with_entry_point(mut self, entry_point: u16) -> Self {
+        self.entry_point = Some(entry_point);
+        self
+    }
+
+    pub fn with_stack_pointer(mut self, stack_pointer: u16) -> Self {
+        self.stack_pointer = Some(stack_pointer);
+        self
+    }
+
+    pub fn with_symbols(mut self, symbols: DebugSymbols) -> Self {
+        self.symbols = Some(symbols);
+        self
+    }
+
+    /// Parses and validates an `.lmc` cartridge
+    pub fn from_lmc(data: &[u8]) -> LMVC8Result<Self> {
+        let mut reader = Cursor::new(data);
+        let header = LmcHeader::read(&mut reader).map_err(|err| match err.root_cause() {
+            binrw::Error::BadMagic { .. } => LMVC8Error::InvalidCartridgeMagic,
+            root if root.is_eof() => LMVC8Error::TruncatedCartridge,
+            root => LMVC8Error::InvalidCartridgeHeader(root.to_string()),
+        })?;
+
+        if !(LMC_MIN_VERSION..=LMC_VERSION).contains(&header.version) {
+            return Err(LMVC8Error::UnsupportedCartridgeVersion(header.version));
+        }
+        let profile = HardwareProfile::try_from(header.profile)?;
+        if header.rom_size as usize > ROM_SIZE {
+            return Err(LMVC8Error::ROMSizeExceeded);
+        }
+
+        let rom_start = reader.position() as usize;
+        let rom_end = rom_start + header.rom_size as usize;
+        let Some(binary) = data.get(rom_start..rom_end) else {
+            return Err(LMVC8Error::TruncatedCartridge);
+        };
+        let crc = lmc::crc32(binary);
+        if crc != header.crc {
+            return Err(LMVC8Error::CartridgeChecksumMismatch {
+                expected: header.crc,
+                actual: crc,
+            });
+        }
+
+        reader.set_position(rom_end as u64);
+        let symbols = if header.flags & LmcHeader::FLAG_DEBUG_SYMBOLS != 0 {
+            let symbols = DebugSymbols::read_args(&mut reader, (header.version,))
+                .map_err(|err| LMVC8Error::InvalidDebugSymbols(err.root_cause().to_string()))?;
+            Some(symbols)
+        } else {
+            None
+        };
+        let trailing = data.len() - reader.position() as usize;
+        if trailing > 0 {
+            return Err(LMVC8Error::InvalidCartridgeHeader(format!(
+                "{trailing} bytes of trailing data after the cartridge contents"
+            )));
+        }
+
+        Ok(Self {
+            binary: binary.to_vec(),
+            title: lmc::decode_text(&header.title),
+            author: lmc::decode_text(&header.author),
+            save_ram: header.flags & LmcHeader::FLAG_SAVE_RAM != 0,
+            path: None,
+            profile,
+            entry_point: Some(header.entry_point),
+            stack_pointer: (header.stack_pointer != 0).then_some(header.stack_pointer),
+            symbols,
+        })
+    }
+
+    /// Encodes the cartridge as `.lmc`, without an entry point the profile's boot PC is stored
+    pub fn to_lmc(&self) -> LMVC8Result<Vec<u8>> {
+        if self.binary.len() > ROM_SIZE {
+            return Err(LMVC8Error::ROMSizeExceeded);
+        }
+        let text = |name: &str, value: &str| {
+            lmc::encode_text(value).ok_or_else(|| {
+                LMVC8Error::InvalidCartridgeHeader(format!(
+                    "the {name} is longer than {LMC_TEXT_SIZE} bytes"
+                ))
+            })
+        };
+
+        let header = LmcHeader {
+            version: LMC_VERSION,
+            title: text("title", &self.title)?,
+            author: text("author", &self.author)?,
+            profile: self.profile.into(),
+            flags: self.lmc_flags(),
+            entry_point: self.entry_point.unwrap_or(self.profile.config().boot_pc),
+            stack_pointer: self.stack_pointer.unwrap_or(0),
+            rom_size: self.binary.len() as u16,
+            crc: lmc::crc32(&self.binary),
+        };
+
+        let mut writer = Cursor::new(Vec::with_capacity(LmcHeader::SIZE + self.binary.len()));
+        header
+            .write(&mut writer)
+            .map_err(|err| LMVC8Error::InvalidCartridgeHeader(err.to_string()))?;
+        writer
+            .write_all(&self.binary)
+            .expect("Writing to memory can't fail");
+        if let Some(symbols) = &self.symbols {
+            symbols
+                .write(&mut writer)
+                .map_err(|err| LMVC8Error::InvalidDebugSymbols(err.to_string()))?;
+        }
+        Ok(writer.into_inner())
+    }
+
+    fn lmc_flags(&self) -> u8 {
+        let mut flags = 0;
+        if self.save_ram {
+            flags |= LmcHeader::FLAG_SAVE_RAM;
+        }
+        if self.symbols.is_some() {
+            flags |= LmcHeader::FLAG_DEBUG_SYMBOLS;
+        }
+        flags
+    }
+
+    /// Applies an IPS or BPS patch to the ROM contents, BPS patches are validated against their checksums
+    pub fn apply_patch(&mut self, patch: &[u8]) -> LMVC8Result<()> {
+        let binary = patch::apply(&self.binary, patch)?;
+        if binary.len() > ROM_SIZE {
+            return Err(LMVC8Error::ROMSizeExceeded);
+        }
+        self.binary = binary;
+        Ok(())
     }
 
+    pub fn apply_patch_file(&mut self, path: &Path) -> LMVC8Result<()> {
+        self.apply_patch(&std::fs::read(path)?)
+    }
+
+    /// Creates a patch turning this cartridge's ROM contents into the target
+    pub fn create_patch(&self, format: PatchFormat, target: &[u8]) -> LMVC8Result<Vec<u8>> {
+        patch::create(format, &self.binary, target)
+    }
+
+    /// Writes the cartridge as `.lmc`
     pub fn dump_to_file(&self, path: &Path) -> LMVC8Result<()> {
-        Ok(std::fs::write(path, self.binary.clone())?)
+        Ok(std::fs::write(path, self.to_lmc()?)?)
+    }
+
+    /// Writes only the ROM contents
+    pub fn dump_raw_to_file(&self, path: &Path) -> LMVC8Result<()> {
+        Ok(std::fs::write(path, &self.binary)?)
     }
 
+    /// Loads an `.lmc` cartridge
     pub fn load_from_file(path: &Path) -> LMVC8Result<Self> {
+        let data = std::fs::read(path)?;
+        let mut cartridge = Self::from_lmc(&data)?;
+        cartridge.path = Some(path.to_path_buf());
+        Ok(cartridge)
+    }
+
+    /// Loads a headerless ROM image, it can't declare save RAM, so it always gets it.\
+    /// Everything else is the default of [`Cartridge::new`].
+    pub fn load_raw_from_file(path: &Path) -> LMVC8Result<Self> {
         let binary = std::fs::read(path)?;
-        Ok(Self::new(binary))
+        let mut cartridge = Self::new(binary).with_save_ram(true);
+        cartridge.path = Some(path.to_path_buf());
+        Ok(cartridge)
+    }
+
+    /// Imports an Intel HEX or S-record image, gaps between the records are zero-filled
+    pub fn from_rom_image(format: RomImageFormat, text: &str) -> LMVC8Result<Self> {
+        let image = RomImage::parse(format, text)?;
+        let mut cartridge = Self::new(image.binary).with_title(image.title);
+        cartridge.entry_point = image.entry_point;
+        Ok(cartridge)
+    }
+
+    /// Exports the ROM contents as Intel HEX or S-records, long zero runs are left out
+    pub fn to_rom_image(&self, format: RomImageFormat) -> String {
+        RomImage {
+            binary: self.binary.clone(),
+            entry_point: self.entry_point,
+            title: self.title.clone(),
+        }
+        .write(format)
+    }
+
+    pub fn load_rom_image_file(path: &Path, format: RomImageFormat) -> LMVC8Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        let mut cartridge = Self::from_rom_image(format, &text)?;
+        cartridge.path = Some(path.to_path_buf());
+        Ok(cartridge)
+    }
+
+    pub fn dump_rom_image_file(&self, path: &Path, format: RomImageFormat) -> LMVC8Result<()> {
+        Ok(std::fs::write(path, self.to_rom_image(format))?)
+    }
+
+    /// The `.sav` file next to the cartridge file, if the cartridge has save RAM and was loaded from disk
+    pub fn save_path(&self) -> Option<PathBuf> {
+        if !self.save_ram {
+            return None;
+        }
+        self.path
+            .as_ref()
+            .map(|path| path.with_extension(Self::SAVE_EXTENSION))
     }
 }
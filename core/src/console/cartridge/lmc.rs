@@ -0,0 +1,66 @@
+use binrw::{BinRead, BinWrite};
+
+/// Current version of the `.lmc` container, version 2 added variables to the debug symbols
+pub const LMC_VERSION: u8 = 2;
+/// Oldest version of the `.lmc` container that can still be loaded
+pub const LMC_MIN_VERSION: u8 = 1;
+/// Fixed size of the title and author fields, shorter values are zero-padded
+pub const LMC_TEXT_SIZE: usize = 32;
+
+/// Header of an `.lmc` cartridge file, the ROM contents and optional sections follow right after it.\
+/// All values are little endian.
+#[derive(Debug, Clone, PartialEq, Eq, BinRead, BinWrite)]
+#[brw(little, magic = b"LMC8")]
+pub struct LmcHeader {
+    pub version: u8,
+    pub title: [u8; LMC_TEXT_SIZE],
+    pub author: [u8; LMC_TEXT_SIZE],
+    /// See [`HardwareProfile`](crate::console::config::HardwareProfile) for the values
+    pub profile: u8,
+    pub flags: u8,
+    pub entry_point: u16,
+    /// Zero to use the console's initial SP
+    pub stack_pointer: u16,
+    pub rom_size: u16,
+    /// CRC-32 of the ROM contents
+    pub crc: u32,
+}
+
+impl LmcHeader {
+    /// Header size in bytes, including the magic number
+    pub const SIZE: usize = 4 + 1 + 2 * LMC_TEXT_SIZE + 1 + 1 + 2 + 2 + 2 + 4;
+
+    /// The cartridge provides battery-backed RAM
+    pub const FLAG_SAVE_RAM: u8 = 0x01;
+    /// A [`DebugSymbols`](crate::console::cartridge::symbols::DebugSymbols) section follows the ROM contents
+    pub const FLAG_DEBUG_SYMBOLS: u8 = 0x02;
+}
+
+/// Pads the text to the field size, fails if it doesn't fit
+pub fn encode_text(text: &str) -> Option<[u8; LMC_TEXT_SIZE]> {
+    let bytes = text.as_bytes();
+    if bytes.len() > LMC_TEXT_SIZE {
+        return None;
+    }
+    let mut field = [0; LMC_TEXT_SIZE];
+    field[..bytes.len()].copy_from_slice(bytes);
+    Some(field)
+}
+
+pub fn decode_text(field: &[u8; LMC_TEXT_SIZE]) -> String {
+    let len = field.iter().position(|&b| b == 0).unwrap_or(LMC_TEXT_SIZE);
+    String::from_utf8_lossy(&field[..len]).into_owned()
+}
+
+/// CRC-32 (IEEE 802.3), as used by zip and png
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
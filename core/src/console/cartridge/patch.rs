@@ -0,0 +1,370 @@
+use crate::console::cartridge::lmc::crc32;
+use crate::console::components::rom::ROM_SIZE;
+use crate::error::{LMVC8Error, LMVC8Result};
+
+const IPS_MAGIC: &[u8] = b"PATCH";
+const IPS_EOF: &[u8] = b"EOF";
+/// The largest offset an IPS record can address
+const IPS_MAX_OFFSET: usize = 0xFF_FFFF;
+const IPS_MAX_RECORD: usize = 0xFFFF;
+
+const BPS_MAGIC: &[u8] = b"BPS1";
+const BPS_FOOTER_SIZE: usize = 12;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PatchFormat {
+    /// International Patching System, small but without any checksums
+    Ips,
+    /// Beat Patching System, validates the source, target and patch with CRC-32
+    Bps,
+}
+
+impl PatchFormat {
+    pub const ALL: [Self; 2] = [Self::Ips, Self::Bps];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Ips => "ips",
+            Self::Bps => "bps",
+        }
+    }
+
+    /// Identifies the format by the magic number of the patch
+    pub fn detect(patch: &[u8]) -> Option<Self> {
+        if patch.starts_with(IPS_MAGIC) {
+            Some(Self::Ips)
+        } else if patch.starts_with(BPS_MAGIC) {
+            Some(Self::Bps)
+        } else {
+            None
+        }
+    }
+}
+
+/// Applies a patch of any supported format
+pub fn apply(source: &[u8], patch: &[u8]) -> LMVC8Result<Vec<u8>> {
+    match PatchFormat::detect(patch) {
+        Some(PatchFormat::Ips) => apply_ips(source, patch),
+        Some(PatchFormat::Bps) => apply_bps(source, patch),
+        None => Err(invalid("unknown patch format")),
+    }
+}
+
+pub fn create(format: PatchFormat, source: &[u8], target: &[u8]) -> LMVC8Result<Vec<u8>> {
+    match format {
+        PatchFormat::Ips => create_ips(source, target),
+        PatchFormat::Bps => Ok(create_bps(source, target)),
+    }
+}
+
+fn invalid(reason: impl Into<String>) -> LMVC8Error {
+    LMVC8Error::InvalidPatch(reason.into())
+}
+
+/// Reads the patch front to back, running out of data is an error
+struct PatchReader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> PatchReader<'a> {
+    fn new(data: &'a [u8], offset: usize) -> Self {
+        Self { data, offset }
+    }
+
+    fn bytes(&mut self, count: usize) -> LMVC8Result<&'a [u8]> {
+        let bytes = self
+            .data
+            .get(self.offset..self.offset + count)
+            .ok_or_else(|| invalid("unexpected end of patch"))?;
+        self.offset += count;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> LMVC8Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16_be(&mut self) -> LMVC8Result<usize> {
+        let bytes = self.bytes(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]) as usize)
+    }
+
+    fn u24_be(&mut self) -> LMVC8Result<usize> {
+        let bytes = self.bytes(3)?;
+        Ok(u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]) as usize)
+    }
+
+    /// BPS variable-length number
+    fn number(&mut self) -> LMVC8Result<usize> {
+        let mut value = 0usize;
+        let mut shift = 1usize;
+        loop {
+            let byte = self.u8()?;
+            value = (byte as usize & 0x7F)
+                .checked_mul(shift)
+                .and_then(|part| value.checked_add(part))
+                .ok_or_else(|| invalid("number overflows"))?;
+            if byte & 0x80 != 0 {
+                return Ok(value);
+            }
+            shift = shift
+                .checked_mul(0x80)
+                .ok_or_else(|| invalid("number overflows"))?;
+            value = value
+                .checked_add(shift)
+                .ok_or_else(|| invalid("number overflows"))?;
+        }
+    }
+
+    /// BPS relative offset, the lowest bit is the sign
+    fn signed_number(&mut self) -> LMVC8Result<isize> {
+        let value = self.number()?;
+        let magnitude = (value >> 1) as isize;
+        Ok(if value & 1 != 0 {
+            -magnitude
+        } else {
+            magnitude
+        })
+    }
+
+    fn is_at(&self, offset: usize) -> bool {
+        self.offset >= offset
+    }
+}
+
+pub fn apply_ips(source: &[u8], patch: &[u8]) -> LMVC8Result<Vec<u8>> {
+    if !patch.starts_with(IPS_MAGIC) {
+        return Err(invalid("missing IPS header"));
+    }
+
+    let mut target = source.to_vec();
+    let mut reader = PatchReader::new(patch, IPS_MAGIC.len());
+    loop {
+        if reader.bytes(IPS_EOF.len())? == IPS_EOF {
+            break;
+        }
+        reader.offset -= IPS_EOF.len();
+
+        let offset = reader.u24_be()?;
+        let size = reader.u16_be()?;
+        let (data, size) = if size == 0 {
+            // Run-length encoded record
+            let size = reader.u16_be()?;
+            (None, size)
+        } else {
+            (Some(reader.bytes(size)?), size)
+        };
+        // Offsets reach up to 16MiB, don't grow the target past what the ROM can hold
+        if offset + size > ROM_SIZE {
+            return Err(LMVC8Error::ROMSizeExceeded);
+        }
+
+        if target.len() < offset + size {
+            target.resize(offset + size, 0);
+        }
+        match data {
+            Some(data) => target[offset..offset + size].copy_from_slice(data),
+            None => target[offset..offset + size].fill(reader.u8()?),
+        }
+    }
+
+    // Truncation extension
+    if !reader.is_at(patch.len()) {
+        let size = reader.u24_be()?;
+        target.truncate(size);
+    }
+
+    Ok(target)
+}
+
+pub fn create_ips(source: &[u8], target: &[u8]) -> LMVC8Result<Vec<u8>> {
+    if target.len() > IPS_MAX_OFFSET + 1 {
+        return Err(invalid("the target is too large for IPS"));
+    }
+
+    let mut patch = IPS_MAGIC.to_vec();
+    let differs = |index: usize| source.get(index) != Some(&target[index]);
+    let mut index = 0;
+    while index < target.len() {
+        if !differs(index) {
+            index += 1;
+            continue;
+        }
+
+        // An offset spelling "EOF" would end the patch early
+        let start = if index == 0x45_4F46 { index - 1 } else { index };
+        let mut end = index;
+        while end < target.len() && end - start < IPS_MAX_RECORD && differs(end) {
+            end += 1;
+        }
+        patch.extend_from_slice(&(start as u32).to_be_bytes()[1..]);
+        patch.extend_from_slice(&((end - start) as u16).to_be_bytes());
+        patch.extend_from_slice(&target[start..end]);
+        index = end;
+    }
+    patch.extend_from_slice(IPS_EOF);
+
+    if target.len() < source.len() {
+        patch.extend_from_slice(&(target.len() as u32).to_be_bytes()[1..]);
+    }
+
+    Ok(patch)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum BpsAction {
+    SourceRead = 0,
+    TargetRead = 1,
+    SourceCopy = 2,
+    TargetCopy = 3,
+}
+
+pub fn apply_bps(source: &[u8], patch: &[u8]) -> LMVC8Result<Vec<u8>> {
+    if !patch.starts_with(BPS_MAGIC) {
+        return Err(invalid("missing BPS header"));
+    }
+    if patch.len() < BPS_MAGIC.len() + BPS_FOOTER_SIZE {
+        return Err(invalid("unexpected end of patch"));
+    }
+
+    let footer_start = patch.len() - BPS_FOOTER_SIZE;
+    let checksum = |index: usize| {
+        let offset = footer_start + index * 4;
+        u32::from_le_bytes(patch[offset..offset + 4].try_into().unwrap())
+    };
+    check("patch", checksum(2), crc32(&patch[..patch.len() - 4]))?;
+    check("source", checksum(0), crc32(source))?;
+
+    let mut reader = PatchReader::new(patch, BPS_MAGIC.len());
+    let source_size = reader.number()?;
+    let target_size = reader.number()?;
+    let metadata_size = reader.number()?;
+    reader.bytes(metadata_size)?;
+    if source_size != source.len() {
+        return Err(invalid(format!(
+            "the patch is for a {source_size} byte source, but it has {} bytes",
+            source.len()
+        )));
+    }
+
+    // The sizes aren't trusted before the target checksum is, so don't preallocate
+    let mut target = Vec::new();
+    let mut source_offset = 0isize;
+    let mut target_offset = 0isize;
+    while !reader.is_at(footer_start) {
+        let command = reader.number()?;
+        let length = (command >> 2) + 1;
+        let action = match command & 0b11 {
+            0 => BpsAction::SourceRead,
+            1 => BpsAction::TargetRead,
+            2 => BpsAction::SourceCopy,
+            _ => BpsAction::TargetCopy,
+        };
+        if target.len() + length > target_size {
+            return Err(invalid("the patch writes past the end of the target"));
+        }
+
+        match action {
+            BpsAction::SourceRead => {
+                let start = target.len();
+                let data = source
+                    .get(start..start + length)
+                    .ok_or_else(|| invalid("source read out of bounds"))?;
+                target.extend_from_slice(data);
+            }
+            BpsAction::TargetRead => target.extend_from_slice(reader.bytes(length)?),
+            BpsAction::SourceCopy => {
+                let out_of_bounds = || invalid("source copy out of bounds");
+                source_offset = source_offset
+                    .checked_add(reader.signed_number()?)
+                    .ok_or_else(out_of_bounds)?;
+                let start = usize::try_from(source_offset).map_err(|_| out_of_bounds())?;
+                let data = start
+                    .checked_add(length)
+                    .and_then(|end| source.get(start..end))
+                    .ok_or_else(out_of_bounds)?;
+                target.extend_from_slice(data);
+                // The copy is in bounds, so the offset stays below the source size
+                source_offset += length as isize;
+            }
+            BpsAction::TargetCopy => {
+                target_offset = target_offset
+                    .checked_add(reader.signed_number()?)
+                    .ok_or_else(|| invalid("target copy out of bounds"))?;
+                // Byte by byte, the copy may overlap the bytes it produces
+                for _ in 0..length {
+                    let byte = usize::try_from(target_offset)
+                        .ok()
+                        .and_then(|offset| target.get(offset).copied())
+                        .ok_or_else(|| invalid("target copy out of bounds"))?;
+                    target.push(byte);
+                    target_offset += 1;
+                }
+            }
+        }
+    }
+
+    if target.len() != target_size {
+        return Err(invalid("the patch doesn't produce the whole target"));
+    }
+    check("target", checksum(1), crc32(&target))?;
+
+    Ok(target)
+}
+
+/// Encodes runs of unchanged bytes as source reads and everything else literally
+pub fn create_bps(source: &[u8], target: &[u8]) -> Vec<u8> {
+    let mut patch = BPS_MAGIC.to_vec();
+    push_number(&mut patch, source.len());
+    push_number(&mut patch, target.len());
+    push_number(&mut patch, 0);
+
+    let unchanged = |index: usize| source.get(index) == Some(&target[index]);
+    let mut index = 0;
+    while index < target.len() {
+        let start = index;
+        let action = if unchanged(index) {
+            BpsAction::SourceRead
+        } else {
+            BpsAction::TargetRead
+        };
+        while index < target.len() && unchanged(index) == (action == BpsAction::SourceRead) {
+            index += 1;
+        }
+
+        push_number(&mut patch, ((index - start - 1) << 2) | action as usize);
+        if action == BpsAction::TargetRead {
+            patch.extend_from_slice(&target[start..index]);
+        }
+    }
+
+    patch.extend_from_slice(&crc32(source).to_le_bytes());
+    patch.extend_from_slice(&crc32(target).to_le_bytes());
+    patch.extend_from_slice(&crc32(&patch).to_le_bytes());
+    patch
+}
+
+fn push_number(patch: &mut Vec<u8>, mut value: usize) {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            patch.push(byte | 0x80);
+            return;
+        }
+        patch.push(byte);
+        value -= 1;
+    }
+}
+
+fn check(checksum: &'static str, expected: u32, actual: u32) -> LMVC8Result<()> {
+    if expected != actual {
+        return Err(LMVC8Error::PatchChecksumMismatch {
+            checksum,
+            expected,
+            actual,
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,311 @@
+use crate::console::components::rom::ROM_SIZE;
+use crate::error::{LMVC8Error, LMVC8Result};
+use std::fmt::Write;
+
+/// Data bytes per record when exporting
+const RECORD_SIZE: usize = 16;
+/// Zero runs at least this long are left out, importing fills them in again
+const MIN_GAP: usize = RECORD_SIZE;
+
+/// Text formats flashing tools use for ROM images
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RomImageFormat {
+    IntelHex,
+    /// Motorola S-record
+    SRec,
+}
+
+impl RomImageFormat {
+    pub const ALL: [Self; 2] = [Self::IntelHex, Self::SRec];
+
+    pub fn extensions(&self) -> &'static [&'static str] {
+        match self {
+            Self::IntelHex => &["hex", "ihx"],
+            Self::SRec => &["srec", "s19", "mot"],
+        }
+    }
+
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        let extension = extension.to_ascii_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|format| format.extensions().contains(&extension.as_str()))
+    }
+}
+
+/// The contents of a ROM image
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RomImage {
+    pub binary: Vec<u8>,
+    /// Intel HEX leaves it out when there is none, S-records always store one and write none as 0x0000
+    pub entry_point: Option<u16>,
+    /// Only stored by S-records
+    pub title: String,
+}
+
+impl RomImage {
+    pub fn parse(format: RomImageFormat, text: &str) -> LMVC8Result<Self> {
+        let mut image = Self::default();
+        let mut records = 0;
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let record = Record {
+                line: index + 1,
+                text: line,
+            };
+            let done = match format {
+                RomImageFormat::IntelHex => record.parse_intel_hex(&mut image)?,
+                RomImageFormat::SRec => record.parse_srec(&mut image, &mut records)?,
+            };
+            if done {
+                return Ok(image);
+            }
+        }
+        Err(LMVC8Error::InvalidRomImage {
+            line: text.lines().count(),
+            reason: "missing end of file record".to_string(),
+        })
+    }
+
+    pub fn write(&self, format: RomImageFormat) -> String {
+        match format {
+            RomImageFormat::IntelHex => self.write_intel_hex(),
+            RomImageFormat::SRec => self.write_srec(),
+        }
+    }
+
+    fn write_intel_hex(&self) -> String {
+        let mut text = String::new();
+        for (address, data) in data_runs(&self.binary) {
+            write_intel_hex_record(&mut text, address as u16, 0x00, data);
+        }
+        if let Some(entry_point) = self.entry_point {
+            write_intel_hex_record(&mut text, 0, 0x05, &(entry_point as u32).to_be_bytes());
+        }
+        write_intel_hex_record(&mut text, 0, 0x01, &[]);
+        text
+    }
+
+    fn write_srec(&self) -> String {
+        let mut text = String::new();
+        write_srec_record(&mut text, '0', 0, self.title.as_bytes());
+        let mut count = 0u16;
+        for (address, data) in data_runs(&self.binary) {
+            write_srec_record(&mut text, '1', address as u16, data);
+            count += 1;
+        }
+        write_srec_record(&mut text, '5', count, &[]);
+        // The S9 record ends the file and can't leave out the address, so no entry point reads back as 0x0000
+        write_srec_record(&mut text, '9', self.entry_point.unwrap_or(0), &[]);
+        text
+    }
+
+    fn store(&mut self, address: usize, data: &[u8]) -> LMVC8Result<()> {
+        let end = address + data.len();
+        if end > ROM_SIZE {
+            return Err(LMVC8Error::ROMSizeExceeded);
+        }
+        if self.binary.len() < end {
+            self.binary.resize(end, 0);
+        }
+        self.binary[address..end].copy_from_slice(data);
+        Ok(())
+    }
+}
+
+/// Splits the binary into record-sized runs, leaving out long zero runs.\
+/// The last byte is always included, so importing restores the full length.
+fn data_runs(binary: &[u8]) -> Vec<(usize, &[u8])> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    while start < binary.len() {
+        let gap = binary[start..]
+            .iter()
+            .take_while(|&&byte| byte == 0)
+            .count();
+        if gap >= MIN_GAP && start + gap < binary.len() {
+            start += gap;
+            continue;
+        }
+        if start + gap == binary.len() && gap >= MIN_GAP {
+            start = binary.len() - 1;
+        }
+        let end = (start + RECORD_SIZE).min(binary.len());
+        runs.push((start, &binary[start..end]));
+        start = end;
+    }
+    runs
+}
+
+fn write_intel_hex_record(text: &mut String, address: u16, kind: u8, data: &[u8]) {
+    let mut bytes = vec![data.len() as u8];
+    bytes.extend_from_slice(&address.to_be_bytes());
+    bytes.push(kind);
+    bytes.extend_from_slice(data);
+    let checksum = bytes
+        .iter()
+        .fold(0u8, |sum, byte| sum.wrapping_add(*byte))
+        .wrapping_neg();
+    bytes.push(checksum);
+
+    text.push(':');
+    push_hex(text, &bytes);
+}
+
+fn write_srec_record(text: &mut String, kind: char, address: u16, data: &[u8]) {
+    let mut bytes = vec![(data.len() + 3) as u8];
+    bytes.extend_from_slice(&address.to_be_bytes());
+    bytes.extend_from_slice(data);
+    let checksum = !bytes.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte));
+    bytes.push(checksum);
+
+    text.push('S');
+    text.push(kind);
+    push_hex(text, &bytes);
+}
+
+fn push_hex(text: &mut String, bytes: &[u8]) {
+    for byte in bytes {
+        write!(text, "{byte:02X}").expect("Writing to a string can't fail");
+    }
+    text.push('\n');
+}
+
+/// A single line of the image
+struct Record<'a> {
+    line: usize,
+    text: &'a str,
+}
+
+impl Record<'_> {
+    fn error(&self, reason: impl Into<String>) -> LMVC8Error {
+        LMVC8Error::InvalidRomImage {
+            line: self.line,
+            reason: reason.into(),
+        }
+    }
+
+    fn bytes(&self, hex: &str) -> LMVC8Result<Vec<u8>> {
+        if !hex.is_ascii() || !hex.len().is_multiple_of(2) {
+            return Err(self.error("expected pairs of hex digits"));
+        }
+        (0..hex.len())
+            .step_by(2)
+            .map(|index| {
+                u8::from_str_radix(&hex[index..index + 2], 16).map_err(|_| {
+                    self.error(format!("invalid hex digits '{}'", &hex[index..index + 2]))
+                })
+            })
+            .collect()
+    }
+
+    fn check(&self, expected: u8, actual: u8) -> LMVC8Result<()> {
+        if expected != actual {
+            return Err(LMVC8Error::RomImageChecksumMismatch {
+                line: self.line,
+                expected,
+                actual,
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns whether this was the end of file record
+    fn parse_intel_hex(&self, image: &mut RomImage) -> LMVC8Result<bool> {
+        let hex = self
+            .text
+            .strip_prefix(':')
+            .ok_or_else(|| self.error("records start with ':'"))?;
+        let bytes = self.bytes(hex)?;
+        if bytes.len() < 5 || bytes.len() != bytes[0] as usize + 5 {
+            return Err(self.error("the record length doesn't match its byte count"));
+        }
+
+        let (checksum, bytes) = bytes.split_last().unwrap();
+        let sum = bytes.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte));
+        self.check(*checksum, sum.wrapping_neg())?;
+
+        let address = u16::from_be_bytes([bytes[1], bytes[2]]) as usize;
+        let data = &bytes[4..];
+        match bytes[3] {
+            0x00 => image.store(address, data)?,
+            0x01 => return Ok(true),
+            // Extended segment and linear addresses, only the first 64KiB can hold ROM contents
+            0x02 | 0x04 if data.len() == 2 => {
+                if data != [0, 0] {
+                    return Err(LMVC8Error::ROMSizeExceeded);
+                }
+            }
+            0x03 | 0x05 if data.len() == 4 => {
+                let start = u32::from_be_bytes(data.try_into().unwrap());
+                let entry_point = match bytes[3] {
+                    // CS:IP
+                    0x03 => (start >> 16) * 16 + (start & 0xFFFF),
+                    _ => start,
+                };
+                image.entry_point = Some(
+                    u16::try_from(entry_point)
+                        .map_err(|_| self.error("the start address is out of range"))?,
+                );
+            }
+            kind => return Err(self.error(format!("unsupported record type {kind:02X}"))),
+        }
+        Ok(false)
+    }
+
+    /// Returns whether this was the termination record
+    fn parse_srec(&self, image: &mut RomImage, records: &mut usize) -> LMVC8Result<bool> {
+        let mut chars = self.text.chars();
+        let (Some('S'), Some(kind)) = (chars.next(), chars.next()) else {
+            return Err(self.error("records start with 'S' and the record type"));
+        };
+        let bytes = self.bytes(chars.as_str())?;
+        if bytes.is_empty() || bytes.len() != bytes[0] as usize + 1 {
+            return Err(self.error("the record length doesn't match its byte count"));
+        }
+
+        let (checksum, bytes) = bytes.split_last().unwrap();
+        let sum = bytes.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte));
+        self.check(*checksum, !sum)?;
+
+        let address_size = match kind {
+            '0' | '1' | '5' | '9' => 2,
+            '2' | '6' | '8' => 3,
+            '3' | '7' => 4,
+            _ => return Err(self.error(format!("unsupported record type S{kind}"))),
+        };
+        if bytes.len() < address_size + 1 {
+            return Err(self.error("the record is too short for its address"));
+        }
+        let address = bytes[1..=address_size]
+            .iter()
+            .fold(0usize, |address, byte| address << 8 | *byte as usize);
+        let data = &bytes[address_size + 1..];
+
+        match kind {
+            '0' => image.title = String::from_utf8_lossy(data).into_owned(),
+            '1' | '2' | '3' => {
+                image.store(address, data)?;
+                *records += 1;
+            }
+            '5' | '6' => {
+                if address != *records {
+                    return Err(self.error(format!(
+                        "the record count is {address}, but {records} data records were read"
+                    )));
+                }
+            }
+            _ => {
+                image.entry_point = Some(
+                    u16::try_from(address)
+                        .map_err(|_| self.error("the start address is out of range"))?,
+                );
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
@@ -0,0 +1,131 @@
+use binrw::{binrw, NullString};
+use std::ops::RangeInclusive;
+
+/// Debug information of a compiled cartridge, stored in an optional section of the `.lmc` file.\
+/// Reading takes the container version, sections of version 1 files have no variables.
+#[binrw]
+#[brw(little, magic = b"SYMS")]
+#[br(import(version: u8))]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DebugSymbols {
+    #[bw(calc = labels.len() as u16)]
+    label_count: u16,
+    #[br(count = label_count)]
+    pub labels: Vec<Label>,
+    #[bw(calc = functions.len() as u16)]
+    function_count: u16,
+    #[br(count = function_count)]
+    pub functions: Vec<SymbolRange>,
+    #[bw(calc = data.len() as u16)]
+    data_count: u16,
+    #[br(count = data_count)]
+    pub data: Vec<SymbolRange>,
+    #[bw(calc = sources.len() as u16)]
+    source_count: u16,
+    #[br(count = source_count)]
+    pub sources: Vec<SourceLocation>,
+    #[br(if(version >= 2))]
+    #[bw(calc = variables.len() as u16)]
+    variable_count: u16,
+    #[br(count = variable_count)]
+    pub variables: Vec<SymbolRange>,
+}
+
+#[binrw]
+#[brw(little)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label {
+    #[br(map = |name: NullString| name.to_string())]
+    #[bw(map = |name: &String| NullString::from(name.as_str()))]
+    pub name: String,
+    pub address: u16,
+}
+
+/// A named, inclusive address range, used for functions, data and variables
+#[binrw]
+#[brw(little)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolRange {
+    #[br(map = |name: NullString| name.to_string())]
+    #[bw(map = |name: &String| NullString::from(name.as_str()))]
+    pub name: String,
+    pub start: u16,
+    pub end: u16,
+}
+
+impl SymbolRange {
+    pub fn range(&self) -> RangeInclusive<u16> {
+        self.start..=self.end
+    }
+}
+
+/// The source position the code at an address was generated from
+#[binrw]
+#[brw(little)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLocation {
+    pub address: u16,
+    #[br(map = |file: NullString| file.to_string())]
+    #[bw(map = |file: &String| NullString::from(file.as_str()))]
+    pub file: String,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl DebugSymbols {
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+            && self.functions.is_empty()
+            && self.data.is_empty()
+            && self.sources.is_empty()
+            && self.variables.is_empty()
+    }
+
+    /// The first label at the address
+    pub fn label_at(&self, address: u16) -> Option<&str> {
+        self.labels
+            .iter()
+            .find(|label| label.address == address)
+            .map(|label| label.name.as_str())
+    }
+
+    /// Resolves a label or function name
+    pub fn address_of(&self, name: &str) -> Option<u16> {
+        self.labels
+            .iter()
+            .find(|label| label.name == name)
+            .map(|label| label.address)
+            .or_else(|| {
+                self.functions
+                    .iter()
+                    .find(|function| function.name == name)
+                    .map(|function| function.start)
+            })
+    }
+
+    pub fn function_at(&self, address: u16) -> Option<&SymbolRange> {
+        self.functions
+            .iter()
+            .find(|function| function.range().contains(&address))
+    }
+
+    /// Whether the address holds data rather than code
+    pub fn is_data(&self, address: u16) -> bool {
+        self.data.iter().any(|data| data.range().contains(&address))
+    }
+
+    /// The RAM variable holding the address
+    pub fn variable_at(&self, address: u16) -> Option<&SymbolRange> {
+        self.variables
+            .iter()
+            .find(|variable| variable.range().contains(&address))
+    }
+
+    /// The closest source location at or before the address
+    pub fn source_at(&self, address: u16) -> Option<&SourceLocation> {
+        self.sources
+            .iter()
+            .filter(|source| source.address <= address)
+            .max_by_key(|source| source.address)
+    }
+}
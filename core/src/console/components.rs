@@ -1,5 +1,13 @@
+pub mod boot_rom;
 pub mod bus;
 pub mod cpu;
+pub mod debug_port;
+pub mod dma;
 pub mod input_controller;
 pub mod ram;
+pub mod rng;
 pub mod rom;
+pub mod rtc;
+pub mod serial;
+pub mod sram;
+pub mod watchdog;
@@ -0,0 +1,61 @@
+use crate::console::components::bus::{Bus, MemoryMapped};
+use crate::console::components::cpu::instructions::CPUInstruction;
+use crate::console::components::cpu::registers::{R16, R8};
+use crate::console::types::address::Address;
+use crate::console::types::byte::Byte;
+
+pub const BOOT_ROM_SIZE: usize = 0x100;
+
+/// Written to the debug port on boot, if the console has one
+pub const SPLASH: &[u8] = b"LMVC8\n";
+
+/// Built-in program the console runs after a reset.\
+/// It greets on the debug port, sets up the stack and then jumps to the cartridge entry point.
+#[derive(Debug, Clone)]
+pub struct BootROM {
+    data: [u8; BOOT_ROM_SIZE],
+}
+
+impl BootROM {
+    pub fn new(entry_point: u16, stack_pointer: u16, splash: bool) -> Self {
+        let mut program = Vec::with_capacity(BOOT_ROM_SIZE);
+        let mut instruction = |instruction: CPUInstruction, operand: &[u8]| {
+            program.push(u8::try_from(instruction).expect("Boot ROM instructions are encodable"));
+            program.extend_from_slice(operand);
+        };
+
+        if splash {
+            instruction(
+                CPUInstruction::LoadR16i(R16::HL),
+                &Bus::DEBUG_CHAR.to_le_bytes(),
+            );
+            for &char in SPLASH {
+                instruction(CPUInstruction::LoadR8i(R8::A), &[char]);
+                instruction(CPUInstruction::LoadR8((R8::HL, R8::A)), &[]);
+            }
+        }
+        instruction(
+            CPUInstruction::LoadR16i(R16::SP),
+            &stack_pointer.to_le_bytes(),
+        );
+        instruction(CPUInstruction::Jump, &entry_point.to_le_bytes());
+
+        let mut data = [0; BOOT_ROM_SIZE];
+        data[..program.len()].copy_from_slice(&program);
+        Self { data }
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl MemoryMapped for BootROM {
+    #[inline(always)]
+    fn read(&mut self, addr: Address) -> Byte {
+        self.data[(u16::from(addr) & 0x00FF) as usize].into()
+    }
+
+    #[inline(always)]
+    fn write(&mut self, _addr: Address, _value: Byte) {}
+}
@@ -1,22 +1,59 @@
+use crate::console::components::boot_rom::BootROM;
+use crate::console::components::bus::memory_map::{DeviceId, MemoryMap, MemoryRegion};
 use crate::console::components::cpu::interrupts::InterruptFlags;
+use crate::console::components::debug_port::DebugPort;
+use crate::console::components::dma::DMA;
 use crate::console::components::input_controller::InputController;
 use crate::console::components::ram::RAM;
+use crate::console::components::rng::RNG;
 use crate::console::components::rom::ROM;
+use crate::console::components::rtc::RTC;
+use crate::console::components::serial::SerialPort;
+use crate::console::components::sram::SRAM;
+use crate::console::components::watchdog::Watchdog;
+use crate::console::config::{ConsoleConfig, Peripherals};
 use crate::console::input::ConsoleInput;
 use crate::console::types::address::Address;
 use crate::console::types::byte::Byte;
-use std::ops::{RangeInclusive, Sub};
+use crate::error::{LMVC8Error, LMVC8Result};
+use std::any::Any;
+use std::fmt::Debug;
+use std::ops::RangeInclusive;
 
-#[derive(Debug, Default, Clone)]
+pub mod memory_map;
+
+#[derive(Debug, Clone)]
 pub struct Bus {
     pub rom: ROM,
     pub ram: RAM,
+    /// Cartridge RAM, only present if the loaded cartridge provides it
+    pub sram: Option<SRAM>,
+    /// Only present if the console is configured to boot through it
+    pub boot_rom: Option<BootROM>,
     pub ic: InputController,
+    pub dma: DMA,
+    pub serial: SerialPort,
+    pub debug_port: DebugPort,
+    pub rng: RNG,
+    /// Real-time clock, battery backed, so it keeps running across resets
+    pub rtc: RTC,
+    pub watchdog: Watchdog,
     /// Interrupt enable, memory mapped, but CPU-internal register
     pub ie: InterruptFlags,
     /// Interrupt active, memory mapped, but CPU-internal register
     pub ia: InterruptFlags,
     pub step_cycles: u64,
+    /// Last address backed by RAM, depends on the configured RAM size
+    ram_end: u16,
+    /// Devices registered at runtime, addressed by [`DeviceId::Custom`]
+    devices: Vec<Box<dyn Device>>,
+    memory_map: MemoryMap,
+}
+
+impl Default for Bus {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Bus {
@@ -26,9 +63,73 @@ impl Bus {
     pub const RANGE_ROM: RangeInclusive<u16> = Self::ROM_START..=Self::ROM_END;
     // RAM
     pub const RAM_START: u16 = 0x8000;
-    pub const RAM_END: u16 = 0xFFFA;
+    /// Was 0xFFFA before cartridge RAM, programs that used the upper 8KiB have to move down
+    pub const RAM_END: u16 = 0xDFFF;
     pub const RANGE_RAM: RangeInclusive<u16> = Self::RAM_START..=Self::RAM_END;
     pub const DEFAULT_SP: u16 = Self::RAM_END;
+    // Cartridge RAM
+    pub const SRAM_START: u16 = 0xE000;
+    pub const SRAM_END: u16 = 0xEFFF;
+    pub const RANGE_SRAM: RangeInclusive<u16> = Self::SRAM_START..=Self::SRAM_END;
+    // Boot ROM
+    pub const BOOT_ROM_START: u16 = 0xF000;
+    pub const BOOT_ROM_END: u16 = 0xF0FF;
+    pub const RANGE_BOOT_ROM: RangeInclusive<u16> = Self::BOOT_ROM_START..=Self::BOOT_ROM_END;
+    // DMA controller
+    pub const DMA_START: u16 = 0xFFA0;
+    pub const DMA_END: u16 = 0xFFA6;
+    pub const RANGE_DMA: RangeInclusive<u16> = Self::DMA_START..=Self::DMA_END;
+    pub const DMA_SOURCE_LOW: u16 = 0xFFA0;
+    pub const DMA_SOURCE_HIGH: u16 = 0xFFA1;
+    pub const DMA_DESTINATION_LOW: u16 = 0xFFA2;
+    pub const DMA_DESTINATION_HIGH: u16 = 0xFFA3;
+    pub const DMA_LENGTH_LOW: u16 = 0xFFA4;
+    pub const DMA_LENGTH_HIGH: u16 = 0xFFA5;
+    pub const DMA_CONTROL: u16 = 0xFFA6;
+    // Serial port
+    pub const SERIAL_START: u16 = 0xFFA8;
+    pub const SERIAL_END: u16 = 0xFFA9;
+    pub const RANGE_SERIAL: RangeInclusive<u16> = Self::SERIAL_START..=Self::SERIAL_END;
+    pub const SERIAL_DATA: u16 = 0xFFA8;
+    pub const SERIAL_CONTROL: u16 = 0xFFA9;
+    // Debug port
+    pub const DEBUG_START: u16 = 0xFFAA;
+    pub const DEBUG_END: u16 = 0xFFAB;
+    pub const RANGE_DEBUG: RangeInclusive<u16> = Self::DEBUG_START..=Self::DEBUG_END;
+    /// Appends the written byte to the debug output
+    pub const DEBUG_CHAR: u16 = 0xFFAA;
+    /// Appends the written byte as two hex digits to the debug output
+    pub const DEBUG_HEX: u16 = 0xFFAB;
+    // Random number generator
+    pub const RNG_START: u16 = 0xFFB0;
+    pub const RNG_END: u16 = 0xFFB2;
+    pub const RANGE_RNG: RangeInclusive<u16> = Self::RNG_START..=Self::RNG_END;
+    /// Reads the next random byte
+    pub const RNG_VALUE: u16 = 0xFFB0;
+    pub const RNG_SEED_LOW: u16 = 0xFFB1;
+    pub const RNG_SEED_HIGH: u16 = 0xFFB2;
+    // Real-time clock
+    pub const RTC_START: u16 = 0xFFB4;
+    pub const RTC_END: u16 = 0xFFBC;
+    pub const RANGE_RTC: RangeInclusive<u16> = Self::RTC_START..=Self::RTC_END;
+    pub const RTC_SECONDS: u16 = 0xFFB4;
+    pub const RTC_MINUTES: u16 = 0xFFB5;
+    pub const RTC_HOURS: u16 = 0xFFB6;
+    pub const RTC_DAY_LOW: u16 = 0xFFB7;
+    pub const RTC_DAY_HIGH: u16 = 0xFFB8;
+    pub const RTC_CONTROL: u16 = 0xFFB9;
+    pub const RTC_ALARM_SECONDS: u16 = 0xFFBA;
+    pub const RTC_ALARM_MINUTES: u16 = 0xFFBB;
+    pub const RTC_ALARM_HOURS: u16 = 0xFFBC;
+    // Watchdog
+    pub const WATCHDOG_START: u16 = 0xFFC0;
+    pub const WATCHDOG_END: u16 = 0xFFC3;
+    pub const RANGE_WATCHDOG: RangeInclusive<u16> = Self::WATCHDOG_START..=Self::WATCHDOG_END;
+    pub const WATCHDOG_TIMEOUT_LOW: u16 = 0xFFC0;
+    pub const WATCHDOG_TIMEOUT_HIGH: u16 = 0xFFC1;
+    /// Writing any value restarts the countdown
+    pub const WATCHDOG_KICK: u16 = 0xFFC2;
+    pub const WATCHDOG_CONTROL: u16 = 0xFFC3;
     // Input controller
     pub const IC_START: u16 = 0xFFFB;
     pub const IC_END: u16 = 0xFFFD;
@@ -43,6 +144,90 @@ impl Bus {
     pub const INTERRUPT_ACTIVE: u16 = 0xFFFE;
     pub const INTERRUPT_ENABLE: u16 = 0xFFFF;
 
+    pub fn new() -> Self {
+        Self::with_config(&ConsoleConfig::default()).expect("The default config is valid")
+    }
+
+    /// Builds the bus with the RAM and peripherals of the config
+    pub fn with_config(config: &ConsoleConfig) -> LMVC8Result<Self> {
+        config.validate()?;
+        let mut bus = Self {
+            rom: ROM::default(),
+            ram: RAM::default(),
+            sram: None,
+            boot_rom: None,
+            ic: InputController::default(),
+            dma: DMA::default(),
+            serial: SerialPort::default(),
+            debug_port: DebugPort::default(),
+            rng: RNG::default(),
+            rtc: RTC::default(),
+            watchdog: Watchdog::default(),
+            ie: InterruptFlags::default(),
+            ia: InterruptFlags::default(),
+            step_cycles: 0,
+            ram_end: config.ram_end(),
+            devices: Vec::new(),
+            memory_map: MemoryMap::new(),
+        };
+        bus.map_devices(config)?;
+        Ok(bus)
+    }
+
+    fn map_devices(&mut self, config: &ConsoleConfig) -> LMVC8Result<()> {
+        self.memory_map.map("ROM", Self::RANGE_ROM, DeviceId::ROM)?;
+        self.memory_map
+            .map("RAM", Self::RAM_START..=self.ram_end, DeviceId::RAM)?;
+        self.memory_map
+            .map("Cartridge RAM", Self::RANGE_SRAM, DeviceId::SRAM)?;
+        if config.boot_rom {
+            self.memory_map
+                .map("Boot ROM", Self::RANGE_BOOT_ROM, DeviceId::BootROM)?;
+        }
+
+        let peripherals = [
+            (Peripherals::DMA, "DMA", Self::RANGE_DMA, DeviceId::DMA),
+            (
+                Peripherals::SERIAL,
+                "Serial",
+                Self::RANGE_SERIAL,
+                DeviceId::Serial,
+            ),
+            (
+                Peripherals::DEBUG_PORT,
+                "Debug",
+                Self::RANGE_DEBUG,
+                DeviceId::DebugPort,
+            ),
+            (Peripherals::RNG, "RNG", Self::RANGE_RNG, DeviceId::RNG),
+            (Peripherals::RTC, "RTC", Self::RANGE_RTC, DeviceId::RTC),
+            (
+                Peripherals::WATCHDOG,
+                "Watchdog",
+                Self::RANGE_WATCHDOG,
+                DeviceId::Watchdog,
+            ),
+        ];
+        for (peripheral, name, range, device) in peripherals {
+            if config.peripherals.contains(peripheral) {
+                self.memory_map.map(name, range, device)?;
+            }
+        }
+
+        self.memory_map
+            .map("Input", Self::RANGE_IC, DeviceId::InputController)?;
+        self.memory_map.map(
+            "Interrupt Active",
+            Self::INTERRUPT_ACTIVE..=Self::INTERRUPT_ACTIVE,
+            DeviceId::InterruptActive,
+        )?;
+        self.memory_map.map(
+            "Interrupt Enable",
+            Self::INTERRUPT_ENABLE..=Self::INTERRUPT_ENABLE,
+            DeviceId::InterruptEnable,
+        )
+    }
+
     #[inline(always)]
     pub fn tick(&mut self) {
         self.step_cycles += 1;
@@ -51,6 +236,11 @@ impl Bus {
     #[inline(always)]
     pub fn reset(&mut self) {
         self.ram.reset();
+        self.dma = DMA::default();
+        self.serial = SerialPort::default();
+        self.debug_port.clear();
+        self.rng.reset();
+        self.watchdog = Watchdog::default();
     }
 
     #[inline(always)]
@@ -60,58 +250,195 @@ impl Bus {
         cycles
     }
 
-    #[allow(unreachable_patterns)]
     #[inline(always)]
     pub fn read(&mut self, addr: Address) -> Byte {
-        if !addr.is_in_range(Self::RANGE_SFR) {
+        // ROM and RAM are fixed and make up nearly all accesses, they skip the memory map
+        // RAM past the configured size is never written, so it reads as zero like unmapped memory
+        match u16::from(addr) {
+            Self::ROM_START..=Self::ROM_END => {
+                self.tick();
+                self.rom.read(addr)
+            }
+            Self::RAM_START..=Self::RAM_END => {
+                self.tick();
+                self.ram.read(Self::address_ram(addr))
+            }
+            _ => self.read_device(addr),
+        }
+    }
+
+    #[inline(never)]
+    fn read_device(&mut self, addr: Address) -> Byte {
+        let (device, addr) = self.memory_map.resolve(addr);
+        if !device.is_cpu_internal() {
             self.tick();
         }
 
-        match u16::from(addr) {
-            Self::ROM_START..=Self::ROM_END => self.rom.read(self.address_rom(addr)),
-            Self::RAM_START..=Self::RAM_END => self.ram.read(self.address_ram(addr)),
-            Self::IC_START..=Self::IC_END => self.ic.read(self.address_ic(addr)),
-            Self::INTERRUPT_ACTIVE => self.ia.into(),
-            Self::INTERRUPT_ENABLE => self.ie.into(),
-            _ => unreachable!(),
+        match device {
+            DeviceId::ROM => self.rom.read(addr),
+            DeviceId::RAM => self.ram.read(addr),
+            DeviceId::SRAM => match self.sram.as_mut() {
+                Some(sram) => sram.read(addr),
+                None => Byte::new(0),
+            },
+            DeviceId::BootROM => match self.boot_rom.as_mut() {
+                Some(boot_rom) => boot_rom.read(addr),
+                None => Byte::new(0),
+            },
+            DeviceId::DMA => self.dma.read(addr),
+            DeviceId::Serial => self.serial.read(addr),
+            DeviceId::DebugPort => self.debug_port.read(addr),
+            DeviceId::RNG => self.rng.read(addr),
+            DeviceId::RTC => self.rtc.read(addr),
+            DeviceId::Watchdog => self.watchdog.read(addr),
+            DeviceId::InputController => self.ic.read(addr),
+            DeviceId::InterruptActive => self.ia.into(),
+            DeviceId::InterruptEnable => self.ie.into(),
+            DeviceId::Custom(index) => self.devices[index as usize].read(addr),
+            DeviceId::Unmapped => Byte::new(0),
         }
     }
 
-    #[allow(unreachable_patterns)]
     #[inline(always)]
     pub fn write(&mut self, addr: Address, value: Byte) {
-        if !addr.is_in_range(Self::RANGE_SFR) {
+        // ROM and RAM are fixed and make up nearly all accesses, they skip the memory map
+        match u16::from(addr) {
+            Self::ROM_START..=Self::ROM_END => {
+                self.tick();
+                self.rom.write(addr, value)
+            }
+            raw @ Self::RAM_START..=Self::RAM_END if raw <= self.ram_end => {
+                self.tick();
+                self.ram.write(Self::address_ram(addr), value)
+            }
+            _ => self.write_device(addr, value),
+        }
+    }
+
+    #[inline(never)]
+    fn write_device(&mut self, addr: Address, value: Byte) {
+        let (device, addr) = self.memory_map.resolve(addr);
+        if !device.is_cpu_internal() {
             self.tick();
         }
 
-        match u16::from(addr) {
-            Self::ROM_START..=Self::ROM_END => {}
-            Self::RAM_START..=Self::RAM_END => self.ram.write(self.address_ram(addr), value),
-            Self::IC_START..=Self::IC_END => self.ic.write(self.address_ic(addr), value),
-            Self::INTERRUPT_ACTIVE => self.ia = value.into(),
-            Self::INTERRUPT_ENABLE => self.ie = value.into(),
-            _ => unreachable!(),
+        match device {
+            DeviceId::ROM => self.rom.write(addr, value),
+            DeviceId::RAM => self.ram.write(addr, value),
+            DeviceId::SRAM => {
+                if let Some(sram) = self.sram.as_mut() {
+                    sram.write(addr, value)
+                }
+            }
+            DeviceId::BootROM => {}
+            DeviceId::DMA => self.dma.write(addr, value),
+            DeviceId::Serial => self.serial.write(addr, value),
+            DeviceId::DebugPort => self.debug_port.write(addr, value),
+            DeviceId::RNG => self.rng.write(addr, value),
+            DeviceId::RTC => self.rtc.write(addr, value),
+            DeviceId::Watchdog => self.watchdog.write(addr, value),
+            DeviceId::InputController => self.ic.write(addr, value),
+            DeviceId::InterruptActive => self.ia = value.into(),
+            DeviceId::InterruptEnable => self.ie = value.into(),
+            DeviceId::Custom(index) => self.devices[index as usize].write(addr, value),
+            DeviceId::Unmapped => {}
         }
     }
 
-    pub fn input(&mut self, input: ConsoleInput) {
-        self.ia.set_input();
-        self.ic.input(input);
+    /// Map a device at the specified address range, the device will see addresses relative to the range start
+    pub fn register_device(
+        &mut self,
+        name: impl Into<String>,
+        range: RangeInclusive<u16>,
+        device: impl Device,
+    ) -> LMVC8Result<()> {
+        let index = u8::try_from(self.devices.len()).map_err(|_| LMVC8Error::MemoryMapFull)?;
+        self.memory_map.map(name, range, DeviceId::Custom(index))?;
+        self.devices.push(Box::new(device));
+        Ok(())
     }
 
-    #[inline(always)]
-    fn address_rom(&self, addr: Address) -> Address {
-        addr
+    /// Remove a region from the memory map, its addresses will be unmapped afterward.\
+    /// ROM and RAM are fixed and can't be unmapped.
+    pub fn unmap(&mut self, name: &str) -> LMVC8Result<MemoryRegion> {
+        let region = self
+            .memory_map
+            .regions()
+            .iter()
+            .find(|region| region.name == name)
+            .ok_or_else(|| LMVC8Error::UnknownMemoryRegion(name.to_string()))?;
+        if matches!(region.device, DeviceId::ROM | DeviceId::RAM) {
+            return Err(LMVC8Error::FixedMemoryRegion(name.to_string()));
+        }
+
+        Ok(self
+            .memory_map
+            .unmap(name)
+            .expect("The region exists in the memory map"))
     }
 
     #[inline(always)]
-    fn address_ram(&self, addr: Address) -> Address {
-        addr.sub(Self::RAM_START.into())
+    fn address_ram(addr: Address) -> Address {
+        Address::from(u16::from(addr) - Self::RAM_START)
     }
 
+    /// Access a device registered at runtime by the name of its region
+    pub fn device_mut<T: Device>(&mut self, name: &str) -> Option<&mut T> {
+        let index = self
+            .memory_map
+            .regions()
+            .iter()
+            .find_map(|region| match region.device {
+                DeviceId::Custom(index) if region.name == name => Some(index),
+                _ => None,
+            })?;
+        self.devices[index as usize].as_any_mut().downcast_mut()
+    }
+
+    /// All mapped regions, ordered by address
+    pub fn layout(&self) -> &[MemoryRegion] {
+        self.memory_map.regions()
+    }
+
+    pub fn memory_map(&self) -> &MemoryMap {
+        &self.memory_map
+    }
+
+    /// Transfers a single byte of the active DMA transfer, raises the DMA interrupt once it is done
     #[inline(always)]
-    fn address_ic(&self, addr: Address) -> Address {
-        addr.sub(Self::IC_START.into())
+    pub fn dma_step(&mut self) {
+        if !self.dma.is_done() {
+            let (source, destination) = self.dma.next_transfer();
+            let value = self.read(source);
+            self.write(destination, value);
+        }
+
+        if self.dma.is_done() {
+            self.dma.finish();
+            self.ia.set_dma();
+        }
+    }
+
+    /// Finish a transfer this side clocked, raises the serial interrupt
+    pub fn serial_complete(&mut self, incoming: u8) {
+        self.serial.complete(incoming.into());
+        self.ia.set_serial();
+    }
+
+    /// The other side clocked a transfer, returns the byte shifted out to it.\
+    /// If this side isn't listening, the line idles high and nothing is received.
+    pub fn serial_receive(&mut self, outgoing: u8) -> u8 {
+        if !self.serial.is_listening() {
+            return 0xFF;
+        }
+        let incoming = self.serial.data.into();
+        self.serial_complete(outgoing);
+        incoming
+    }
+
+    pub fn input(&mut self, input: ConsoleInput) {
+        self.ia.set_input();
+        self.ic.input(input);
     }
 }
 
@@ -119,3 +446,28 @@ pub trait MemoryMapped {
     fn read(&mut self, addr: Address) -> Byte;
     fn write(&mut self, addr: Address, value: Byte);
 }
+
+/// A memory mapped device which can be registered on the bus at runtime
+pub trait Device: MemoryMapped + Debug + Send + 'static {
+    fn clone_device(&self) -> Box<dyn Device>;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T> Device for T
+where
+    T: MemoryMapped + Debug + Clone + Send + 'static,
+{
+    fn clone_device(&self) -> Box<dyn Device> {
+        Box::new(self.clone())
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl Clone for Box<dyn Device> {
+    fn clone(&self) -> Self {
+        self.clone_device()
+    }
+}
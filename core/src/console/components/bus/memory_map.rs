@@ -0,0 +1,190 @@
+use crate::console::types::address::Address;
+use crate::error::{LMVC8Error, LMVC8Result};
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+
+/// Identifies which device of the bus handles an address
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DeviceId {
+    #[default]
+    Unmapped,
+    ROM,
+    RAM,
+    SRAM,
+    BootROM,
+    DMA,
+    Serial,
+    DebugPort,
+    RNG,
+    RTC,
+    Watchdog,
+    InputController,
+    InterruptActive,
+    InterruptEnable,
+    /// Index into the devices registered on the bus at runtime
+    Custom(u8),
+}
+
+impl DeviceId {
+    /// CPU-internal registers are memory mapped, but accessing them takes no bus cycle
+    #[inline(always)]
+    pub fn is_cpu_internal(&self) -> bool {
+        matches!(self, Self::InterruptActive | Self::InterruptEnable)
+    }
+}
+
+impl Display for DeviceId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Custom(index) => write!(f, "Custom #{index}"),
+            _ => write!(f, "{self:?}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryRegion {
+    pub name: String,
+    pub start: u16,
+    pub end: u16,
+    pub device: DeviceId,
+}
+
+impl MemoryRegion {
+    pub fn range(&self) -> RangeInclusive<u16> {
+        self.start..=self.end
+    }
+
+    pub fn size(&self) -> usize {
+        (self.end - self.start) as usize + 1
+    }
+
+    pub fn overlaps(&self, range: &RangeInclusive<u16>) -> bool {
+        self.start <= *range.end() && *range.start() <= self.end
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone)]
+struct Slot {
+    device: DeviceId,
+    start: u16,
+}
+
+/// Marks a page which is shared by multiple slots, it has to be resolved per address
+const SPLIT_PAGE: u8 = u8::MAX;
+
+/// Resolves addresses to the device mapped there and the address relative to the device.\
+/// Most 256-byte pages belong to a single region and resolve with a single lookup,
+/// only pages shared by multiple regions fall back to a per-address lookup.
+#[derive(Debug, Clone)]
+pub struct MemoryMap {
+    regions: Vec<MemoryRegion>,
+    /// Slot index for every page, slot 0 is unmapped
+    pages: [u8; 256],
+    /// Slot index for every address, only used for split pages
+    lookup: Box<[u8; 0x10000]>,
+    slots: [Slot; 256],
+}
+
+impl Default for MemoryMap {
+    fn default() -> Self {
+        Self {
+            regions: Vec::new(),
+            pages: [0; 256],
+            lookup: Box::new([0; 0x10000]),
+            slots: [Slot::default(); 256],
+        }
+    }
+}
+
+impl MemoryMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Find the device and the device relative address for an address
+    #[inline(always)]
+    pub fn resolve(&self, addr: Address) -> (DeviceId, Address) {
+        let addr = u16::from(addr);
+        let mut slot = self.pages[(addr >> 8) as usize];
+        if slot == SPLIT_PAGE {
+            slot = self.lookup[addr as usize];
+        }
+        let slot = self.slots[slot as usize];
+        (slot.device, (addr - slot.start).into())
+    }
+
+    pub fn map(
+        &mut self,
+        name: impl Into<String>,
+        range: RangeInclusive<u16>,
+        device: DeviceId,
+    ) -> LMVC8Result<()> {
+        let name = name.into();
+        if range.is_empty() {
+            return Err(LMVC8Error::EmptyMemoryRegion(name));
+        }
+        if let Some(other) = self.regions.iter().find(|region| region.overlaps(&range)) {
+            return Err(LMVC8Error::MemoryRegionOverlap {
+                name,
+                other: other.name.clone(),
+            });
+        }
+        if self.regions.len() >= SPLIT_PAGE as usize - 1 {
+            return Err(LMVC8Error::MemoryMapFull);
+        }
+
+        self.regions.push(MemoryRegion {
+            name,
+            start: *range.start(),
+            end: *range.end(),
+            device,
+        });
+        self.regions.sort_by_key(|region| region.start);
+        self.rebuild();
+        Ok(())
+    }
+
+    pub fn unmap(&mut self, name: &str) -> Option<MemoryRegion> {
+        let index = self.regions.iter().position(|region| region.name == name)?;
+        let region = self.regions.remove(index);
+        self.rebuild();
+        Some(region)
+    }
+
+    /// All mapped regions, ordered by address
+    pub fn regions(&self) -> &[MemoryRegion] {
+        &self.regions
+    }
+
+    pub fn region_at(&self, addr: Address) -> Option<&MemoryRegion> {
+        match self.lookup[u16::from(addr) as usize] {
+            0 => None,
+            slot => self.regions.get(slot as usize - 1),
+        }
+    }
+
+    fn rebuild(&mut self) {
+        self.lookup.fill(0);
+        self.slots = [Slot::default(); 256];
+        for (index, region) in self.regions.iter().enumerate() {
+            let slot = index + 1;
+            self.slots[slot] = Slot {
+                device: region.device,
+                start: region.start,
+            };
+            self.lookup[region.start as usize..=region.end as usize].fill(slot as u8);
+        }
+
+        for (page, entry) in self.pages.iter_mut().enumerate() {
+            let page_slots = &self.lookup[page << 8..(page + 1) << 8];
+            let first = page_slots[0];
+            *entry = if page_slots.iter().all(|slot| *slot == first) {
+                first
+            } else {
+                SPLIT_PAGE
+            };
+        }
+    }
+}
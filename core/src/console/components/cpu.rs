@@ -1,7 +1,7 @@
 use crate::console::components::bus::Bus;
 use crate::console::components::cpu::alu::ALU;
-use crate::console::components::cpu::instructions::CPUInstruction;
-use crate::console::components::cpu::interrupts::{InterruptFlags, IV_INPUT, IV_TIMER};
+use crate::console::components::cpu::instructions::{CPUInstruction, Condition};
+use crate::console::components::cpu::interrupts::InterruptFlags;
 use crate::console::components::cpu::registers::{GeneralRegisters, R16, R16S, R8};
 use crate::console::components::cpu::step_flags::CPUStepFlags;
 use crate::console::types::address::Address;
@@ -36,6 +36,12 @@ impl CPU {
         self.alu = ALU::default();
     }
 
+    /// Sets the registers the hardware initializes on boot
+    pub fn boot(&mut self, pc: u16, sp: u16) {
+        self.pc = Word::new(pc);
+        self.set_r16(R16::SP, sp);
+    }
+
     #[inline(always)]
     pub fn step(&mut self, bus: &mut Bus) -> CPUStepFlags {
         self.handle_interrupt(bus);
@@ -84,20 +90,27 @@ impl CPU {
             CPUInstruction::DisableInterrupts => self.disable_interrupts(),
             CPUInstruction::Call => self.call(bus),
             CPUInstruction::Return => self.ret(bus),
+            CPUInstruction::ReturnInterrupt => self.ret_interrupt(bus),
+            CPUInstruction::Jump => self.jump(bus),
+            CPUInstruction::JumpIf(condition) => self.jump_if(bus, condition),
         }
         false
     }
 
     #[inline(always)]
     fn handle_interrupt(&mut self, bus: &mut Bus) {
+        if !self.ime {
+            return;
+        }
+
         let interrupt_flags = self.read_ie(bus) & self.read_ia(bus);
         if let Some(interrupt) = interrupt_flags.first_set() {
             self.ime = false;
+            // Acknowledge the interrupt, so it won't be serviced again right away
+            bus.ia.remove(interrupt);
             self.push_word(bus, self.pc);
-            match interrupt {
-                InterruptFlags::TIMER => self.pc = IV_TIMER.into(),
-                InterruptFlags::INPUT => self.pc = IV_INPUT.into(),
-                _ => {}
+            if let Some(vector) = interrupt.vector() {
+                self.pc = vector.into();
             }
         }
     }
@@ -116,6 +129,7 @@ impl CPU {
         Word::from_le(low, high)
     }
 
+    /// SP points at the next free byte, the stack grows down from there
     #[inline(always)]
     fn push_byte(&mut self, bus: &mut Bus, byte: Byte) {
         bus.write(Address::from(self.registers.get_r16(R16::SP)), byte);
@@ -130,9 +144,8 @@ impl CPU {
 
     #[inline(always)]
     fn pop_byte(&mut self, bus: &mut Bus) -> Byte {
-        let byte = bus.read(Address::from(self.registers.get_r16(R16::SP)));
         self.registers.increment_r16(R16::SP);
-        byte
+        bus.read(Address::from(self.registers.get_r16(R16::SP)))
     }
 
     #[inline(always)]
@@ -213,12 +226,14 @@ impl CPU {
 
     #[inline(always)]
     pub fn increment_r8(&mut self, bus: &mut Bus, r8: R8) {
-        self.registers.increment_r8(bus, r8);
+        let value = self.registers.increment_r8(bus, r8);
+        self.alu.set_count_flags(value);
     }
 
     #[inline(always)]
     pub fn decrement_r8(&mut self, bus: &mut Bus, r8: R8) {
-        self.registers.decrement_r8(bus, r8);
+        let value = self.registers.decrement_r8(bus, r8);
+        self.alu.set_count_flags(value);
     }
 
     #[inline(always)]
@@ -265,6 +280,26 @@ impl CPU {
     pub fn ret(&mut self, bus: &mut Bus) {
         self.pc = self.pop_word(bus);
     }
+
+    /// Pending interrupts are only serviced once the return address is off the stack
+    #[inline(always)]
+    pub fn ret_interrupt(&mut self, bus: &mut Bus) {
+        self.ret(bus);
+        self.ime = true;
+    }
+
+    #[inline(always)]
+    pub fn jump(&mut self, bus: &mut Bus) {
+        self.pc = self.read_word(bus);
+    }
+
+    #[inline(always)]
+    pub fn jump_if(&mut self, bus: &mut Bus, condition: Condition) {
+        let address = self.read_word(bus);
+        if condition.is_met(self.alu.get_flags()) {
+            self.pc = address;
+        }
+    }
 }
 
 /// Outside access
@@ -284,6 +319,10 @@ impl CPU {
     pub fn get_alu(&self) -> ALU {
         self.alu
     }
+
+    pub fn get_ime(&self) -> bool {
+        self.ime
+    }
 }
 
 impl CPU {
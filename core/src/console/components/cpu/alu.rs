@@ -19,6 +19,13 @@ impl ALU {
         &mut self.flags
     }
 
+    /// INC and DEC leave the carry alone, so it can still be used after counting
+    #[inline(always)]
+    pub fn set_count_flags(&mut self, result: Byte) {
+        self.flags.set(ALUFlags::ZERO, result.is_zero());
+        self.flags.set(ALUFlags::NEGATIVE, result.is_negative());
+    }
+
     #[inline(always)]
     pub fn add_bytes(&mut self, a: Byte, b: Byte) -> Byte {
         let (result, carry) = a.add(b);
@@ -1,7 +1,8 @@
+use crate::console::components::cpu::alu::ALUFlags;
 use crate::console::components::cpu::registers::{R16, R16S, R8};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Default, Copy, Clone)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
 pub enum CPUInstruction {
     #[default]
     NoOp,
@@ -24,6 +25,58 @@ pub enum CPUInstruction {
     DisableInterrupts,
     Call,
     Return,
+    /// Return and enable interrupts in one step, ends interrupt handlers
+    ReturnInterrupt,
+    Jump,
+    JumpIf(Condition),
+}
+
+/// The flag state a conditional jump is taken on
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Condition {
+    Zero,
+    NotZero,
+    Carry,
+    NotCarry,
+}
+
+impl Condition {
+    pub const ALL: [Condition; 4] = [
+        Condition::Zero,
+        Condition::NotZero,
+        Condition::Carry,
+        Condition::NotCarry,
+    ];
+
+    /// The condition that holds exactly when this one doesn't
+    pub fn inverse(self) -> Self {
+        match self {
+            Self::Zero => Self::NotZero,
+            Self::NotZero => Self::Zero,
+            Self::Carry => Self::NotCarry,
+            Self::NotCarry => Self::Carry,
+        }
+    }
+
+    pub fn is_met(self, flags: ALUFlags) -> bool {
+        match self {
+            Self::Zero => flags.is_zero(),
+            Self::NotZero => !flags.is_zero(),
+            Self::Carry => flags.is_carry(),
+            Self::NotCarry => !flags.is_carry(),
+        }
+    }
+}
+
+impl Display for Condition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Zero => write!(f, "Z"),
+            Self::NotZero => write!(f, "NZ"),
+            Self::Carry => write!(f, "C"),
+            Self::NotCarry => write!(f, "NC"),
+        }
+    }
 }
 
 impl CPUInstruction {
@@ -45,9 +98,10 @@ impl CPUInstruction {
             | Self::Pop(_)
             | Self::EnableInterrupts
             | Self::DisableInterrupts
-            | Self::Return => 1,
+            | Self::Return
+            | Self::ReturnInterrupt => 1,
             Self::LoadR8i(_) => 2,
-            Self::LoadR16i(_) | Self::Call => 3,
+            Self::LoadR16i(_) | Self::Call | Self::Jump | Self::JumpIf(_) => 3,
         }
     }
 }
@@ -58,6 +112,7 @@ impl From<u8> for CPUInstruction {
             0x00 => CPUInstruction::NoOp,
             0x01 => CPUInstruction::EnableInterrupts,
             0x02 => CPUInstruction::Call,
+            0x03 => CPUInstruction::Jump,
             0x04 => CPUInstruction::AddR16(R16::BC),
             0x05 => CPUInstruction::AddR16(R16::DE),
             0x06 => CPUInstruction::AddR16(R16::HL),
@@ -73,6 +128,7 @@ impl From<u8> for CPUInstruction {
             0x10 => CPUInstruction::Halt,
             0x11 => CPUInstruction::DisableInterrupts,
             0x12 => CPUInstruction::Return,
+            0x13 => CPUInstruction::ReturnInterrupt,
             0x14 => CPUInstruction::SubR16(R16::BC),
             0x15 => CPUInstruction::SubR16(R16::DE),
             0x16 => CPUInstruction::SubR16(R16::HL),
@@ -197,6 +253,10 @@ impl From<u8> for CPUInstruction {
             0x8D => CPUInstruction::DecR8(R8::H),
             0x8E => CPUInstruction::DecR8(R8::L),
             0x8F => CPUInstruction::DecR8(R8::HL),
+            0x90 => CPUInstruction::JumpIf(Condition::Zero),
+            0x91 => CPUInstruction::JumpIf(Condition::NotZero),
+            0x92 => CPUInstruction::JumpIf(Condition::Carry),
+            0x93 => CPUInstruction::JumpIf(Condition::NotCarry),
             _ => CPUInstruction::NoOp,
         }
     }
@@ -210,9 +270,11 @@ impl TryFrom<CPUInstruction> for u8 {
             CPUInstruction::NoOp => 0x00,
             CPUInstruction::EnableInterrupts => 0x01,
             CPUInstruction::Call => 0x02,
+            CPUInstruction::Jump => 0x03,
             CPUInstruction::Halt => 0x10,
             CPUInstruction::DisableInterrupts => 0x11,
             CPUInstruction::Return => 0x12,
+            CPUInstruction::ReturnInterrupt => 0x13,
             CPUInstruction::AddR16(r16) => match r16 {
                 R16::BC => 0x04,
                 R16::DE => 0x05,
@@ -379,6 +441,12 @@ impl TryFrom<CPUInstruction> for u8 {
                 R8::L => 0x8E,
                 R8::HL => 0x8F,
             },
+            CPUInstruction::JumpIf(condition) => match condition {
+                Condition::Zero => 0x90,
+                Condition::NotZero => 0x91,
+                Condition::Carry => 0x92,
+                Condition::NotCarry => 0x93,
+            },
         };
 
         Ok(value)
@@ -408,6 +476,9 @@ impl Display for CPUInstruction {
             Self::DisableInterrupts => write!(f, "DI"),
             Self::Call => write!(f, "CALL"),
             Self::Return => write!(f, "RET"),
+            Self::ReturnInterrupt => write!(f, "RETI"),
+            Self::Jump => write!(f, "JP"),
+            Self::JumpIf(condition) => write!(f, "JP {condition}"),
         }
     }
 }
@@ -4,12 +4,24 @@ use bitflags::bitflags;
 // ISR Vectors
 pub const IV_TIMER: u16 = 0x0090;
 pub const IV_INPUT: u16 = 0x00A0;
+pub const IV_DMA: u16 = 0x00B0;
+pub const IV_SERIAL: u16 = 0x00C0;
+pub const IV_RTC: u16 = 0x00D0;
+pub const IV_WATCHDOG: u16 = 0x00E0;
+/// The space between two vectors
+pub const IV_SIZE: u16 = 0x0010;
+/// The end of the vector area, exclusive
+pub const IV_END: u16 = IV_WATCHDOG + IV_SIZE;
 
 bitflags! {
     #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
     pub struct InterruptFlags: u8 {
         const TIMER = 0b0000_0001;
         const INPUT = 0b0000_0010;
+        const DMA = 0b0000_0100;
+        const SERIAL = 0b0000_1000;
+        const RTC = 0b0001_0000;
+        const WATCHDOG = 0b0010_0000;
     }
 }
 
@@ -24,6 +36,20 @@ impl InterruptFlags {
         }
     }
 
+    /// The ISR vector of a single interrupt
+    #[inline(always)]
+    pub fn vector(&self) -> Option<u16> {
+        match *self {
+            InterruptFlags::TIMER => Some(IV_TIMER),
+            InterruptFlags::INPUT => Some(IV_INPUT),
+            InterruptFlags::DMA => Some(IV_DMA),
+            InterruptFlags::SERIAL => Some(IV_SERIAL),
+            InterruptFlags::RTC => Some(IV_RTC),
+            InterruptFlags::WATCHDOG => Some(IV_WATCHDOG),
+            _ => None,
+        }
+    }
+
     #[inline(always)]
     pub fn set_timer(&mut self) {
         self.insert(InterruptFlags::TIMER);
@@ -33,6 +59,26 @@ impl InterruptFlags {
     pub fn set_input(&mut self) {
         self.insert(InterruptFlags::INPUT);
     }
+
+    #[inline(always)]
+    pub fn set_dma(&mut self) {
+        self.insert(InterruptFlags::DMA);
+    }
+
+    #[inline(always)]
+    pub fn set_serial(&mut self) {
+        self.insert(InterruptFlags::SERIAL);
+    }
+
+    #[inline(always)]
+    pub fn set_rtc(&mut self) {
+        self.insert(InterruptFlags::RTC);
+    }
+
+    #[inline(always)]
+    pub fn set_watchdog(&mut self) {
+        self.insert(InterruptFlags::WATCHDOG);
+    }
 }
 
 impl From<Byte> for InterruptFlags {
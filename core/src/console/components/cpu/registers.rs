@@ -49,15 +49,17 @@ impl GeneralRegisters {
     }
 
     #[inline(always)]
-    pub fn increment_r8(&mut self, bus: &mut Bus, r8: R8) {
-        let before_value = self.get_r8(bus, r8);
-        self.set_r8(bus, r8, before_value.increment().0);
+    pub fn increment_r8(&mut self, bus: &mut Bus, r8: R8) -> Byte {
+        let value = self.get_r8(bus, r8).increment().0;
+        self.set_r8(bus, r8, value);
+        value
     }
 
     #[inline(always)]
-    pub fn decrement_r8(&mut self, bus: &mut Bus, r8: R8) {
-        let before_value = self.get_r8(bus, r8);
-        self.set_r8(bus, r8, before_value.decrement().0);
+    pub fn decrement_r8(&mut self, bus: &mut Bus, r8: R8) -> Byte {
+        let value = self.get_r8(bus, r8).decrement().0;
+        self.set_r8(bus, r8, value);
+        value
     }
 
     #[inline(always)]
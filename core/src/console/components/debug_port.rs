@@ -0,0 +1,66 @@
+use crate::console::components::bus::MemoryMapped;
+use crate::console::types::address::Address;
+use crate::console::types::byte::Byte;
+
+/// Lets programs print text, every byte written is appended to the output buffer
+#[derive(Debug, Default, Clone)]
+pub struct DebugPort {
+    output: Vec<u8>,
+}
+
+impl DebugPort {
+    pub fn output(&self) -> &[u8] {
+        &self.output
+    }
+
+    pub fn take_output(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.output)
+    }
+
+    /// Takes the output as text, a character that is only partly written stays until the rest follows.\
+    /// Invalid bytes are replaced with U+FFFD.
+    pub fn take_text(&mut self) -> String {
+        let rest = self.output.split_off(self.complete_len());
+        let text = String::from_utf8_lossy(&self.output).into_owned();
+        self.output = rest;
+        text
+    }
+
+    /// The length of the output without a partly written UTF-8 character at its end
+    fn complete_len(&self) -> usize {
+        let len = self.output.len();
+        for start in (len.saturating_sub(3)..len).rev() {
+            let char_len = match self.output[start] {
+                0x80..=0xBF => continue,
+                0xC0..=0xDF => 2,
+                0xE0..=0xEF => 3,
+                0xF0..=0xF7 => 4,
+                _ => return len,
+            };
+            return if start + char_len > len { start } else { len };
+        }
+        len
+    }
+
+    pub fn clear(&mut self) {
+        self.output.clear();
+    }
+}
+
+impl MemoryMapped for DebugPort {
+    #[inline(always)]
+    fn read(&mut self, _addr: Address) -> Byte {
+        Byte::new(0)
+    }
+
+    #[inline(always)]
+    fn write(&mut self, addr: Address, value: Byte) {
+        match u16::from(addr) {
+            0x0000 => self.output.push(value.into()),
+            0x0001 => self
+                .output
+                .extend_from_slice(format!("{:02X}", value.value()).as_bytes()),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use crate::console::components::bus::MemoryMapped;
+use crate::console::types::address::Address;
+use crate::console::types::byte::Byte;
+use crate::console::types::word::Word;
+use bitflags::bitflags;
+
+/// Copies memory without involving the CPU.\
+/// While a transfer is active the CPU is stalled, every transferred byte costs one read and one write cycle on the bus.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct DMA {
+    pub source: Word,
+    pub destination: Word,
+    pub length: Word,
+    pub control: DMAControl,
+}
+
+impl DMA {
+    #[inline(always)]
+    pub fn is_active(&self) -> bool {
+        self.control.contains(DMAControl::BUSY)
+    }
+
+    /// Returns the source and destination of the next byte to transfer and advances the registers
+    #[inline(always)]
+    pub fn next_transfer(&mut self) -> (Address, Address) {
+        let transfer = (self.source.into(), self.destination.into());
+        self.source = self.source.increment().0;
+        self.destination = self.destination.increment().0;
+        self.length = self.length.decrement().0;
+        transfer
+    }
+
+    #[inline(always)]
+    pub fn is_done(&self) -> bool {
+        self.length.is_zero()
+    }
+
+    #[inline(always)]
+    pub fn finish(&mut self) {
+        self.control.remove(DMAControl::BUSY);
+    }
+}
+
+impl MemoryMapped for DMA {
+    #[inline(always)]
+    fn read(&mut self, addr: Address) -> Byte {
+        match u16::from(addr) {
+            0x0000 => self.source.low_byte(),
+            0x0001 => self.source.high_byte(),
+            0x0002 => self.destination.low_byte(),
+            0x0003 => self.destination.high_byte(),
+            0x0004 => self.length.low_byte(),
+            0x0005 => self.length.high_byte(),
+            0x0006 => self.control.bits().into(),
+            _ => Byte::new(0),
+        }
+    }
+
+    #[inline(always)]
+    fn write(&mut self, addr: Address, value: Byte) {
+        // Registers are locked while a transfer is running
+        if self.is_active() {
+            return;
+        }
+
+        match u16::from(addr) {
+            0x0000 => self.source.set_low_byte(value),
+            0x0001 => self.source.set_high_byte(value),
+            0x0002 => self.destination.set_low_byte(value),
+            0x0003 => self.destination.set_high_byte(value),
+            0x0004 => self.length.set_low_byte(value),
+            0x0005 => self.length.set_high_byte(value),
+            0x0006 => self.control = DMAControl::from_bits_truncate(value.into()),
+            _ => {}
+        }
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+    pub struct DMAControl: u8 {
+        /// Write to start a transfer, reads as set until the transfer is complete
+        const BUSY = 0b1000_0000;
+    }
+}
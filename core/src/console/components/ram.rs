@@ -2,7 +2,7 @@ use crate::console::components::bus::MemoryMapped;
 use crate::console::types::address::Address;
 use crate::console::types::byte::Byte;
 
-pub const RAM_SIZE: usize = 0x8000; // 32KiB
+pub const RAM_SIZE: usize = 0x6000; // 24KiB
 
 #[derive(Debug, Clone)]
 pub struct RAM {
@@ -27,11 +27,11 @@ impl RAM {
 impl MemoryMapped for RAM {
     #[inline(always)]
     fn read(&mut self, addr: Address) -> Byte {
-        self.data[(u16::from(addr) & 0x7FFF) as usize].into()
+        self.data[u16::from(addr) as usize].into()
     }
 
     #[inline(always)]
     fn write(&mut self, addr: Address, value: Byte) {
-        self.data[(u16::from(addr) & 0x7FFF) as usize] = value.into();
+        self.data[u16::from(addr) as usize] = value.into();
     }
 }
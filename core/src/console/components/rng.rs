@@ -0,0 +1,85 @@
+use crate::console::components::bus::MemoryMapped;
+use crate::console::types::address::Address;
+use crate::console::types::byte::Byte;
+use crate::console::types::word::Word;
+
+/// 16-bit xorshift random number generator.\
+/// Every read of the value register advances the generator, the sequence only depends on the seed.
+#[derive(Debug, Copy, Clone)]
+pub struct RNG {
+    seed: Word,
+    state: Word,
+}
+
+impl Default for RNG {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_SEED)
+    }
+}
+
+impl RNG {
+    pub const DEFAULT_SEED: u16 = 0xACE1;
+
+    pub fn new(seed: u16) -> Self {
+        Self {
+            seed: Word::new(seed),
+            state: Self::initial_state(seed),
+        }
+    }
+
+    pub fn seed(&self) -> u16 {
+        self.seed.value()
+    }
+
+    /// Restarts the sequence with the given seed, a zero seed behaves like the default one
+    pub fn set_seed(&mut self, seed: u16) {
+        *self = Self::new(seed);
+    }
+
+    /// Restarts the sequence with the current seed
+    pub fn reset(&mut self) {
+        self.state = Self::initial_state(self.seed.value());
+    }
+
+    #[inline(always)]
+    pub fn next_byte(&mut self) -> Byte {
+        let mut state = self.state.value();
+        state ^= state << 7;
+        state ^= state >> 9;
+        state ^= state << 8;
+        self.state = Word::new(state);
+        self.state.high_byte()
+    }
+
+    /// xorshift gets stuck at zero
+    fn initial_state(seed: u16) -> Word {
+        match seed {
+            0 => Word::new(Self::DEFAULT_SEED),
+            seed => Word::new(seed),
+        }
+    }
+}
+
+impl MemoryMapped for RNG {
+    #[inline(always)]
+    fn read(&mut self, addr: Address) -> Byte {
+        match u16::from(addr) {
+            0x0000 => self.next_byte(),
+            0x0001 => self.seed.low_byte(),
+            0x0002 => self.seed.high_byte(),
+            _ => Byte::new(0),
+        }
+    }
+
+    /// Writing either seed byte restarts the sequence
+    #[inline(always)]
+    fn write(&mut self, addr: Address, value: Byte) {
+        let mut seed = self.seed;
+        match u16::from(addr) {
+            0x0001 => seed.set_low_byte(value),
+            0x0002 => seed.set_high_byte(value),
+            _ => return,
+        }
+        self.set_seed(seed.value());
+    }
+}
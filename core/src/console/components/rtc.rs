@@ -0,0 +1,234 @@
+use crate::console::components::bus::MemoryMapped;
+use crate::console::types::address::Address;
+use crate::console::types::byte::Byte;
+use bitflags::bitflags;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// What drives the real-time clock
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum ClockSource {
+    /// Time derived from emulated cycles, deterministic for tests and replays
+    #[default]
+    Virtual,
+    /// Wall clock time (UTC) of the host
+    Host,
+}
+
+/// Time of the clock split into its registers, days are counted from the start of the clock
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct RTCTime {
+    pub seconds: u8,
+    pub minutes: u8,
+    pub hours: u8,
+    pub day: u16,
+}
+
+impl RTCTime {
+    pub fn from_seconds(seconds: u64) -> Self {
+        Self {
+            seconds: (seconds % 60) as u8,
+            minutes: (seconds / 60 % 60) as u8,
+            hours: (seconds / 3600 % 24) as u8,
+            day: (seconds / SECONDS_PER_DAY) as u16,
+        }
+    }
+
+    pub fn second_of_day(&self) -> u64 {
+        self.hours as u64 * 3600 + self.minutes as u64 * 60 + self.seconds as u64
+    }
+}
+
+/// Real-time clock with latched time registers and a daily alarm.\
+/// Reading the time registers returns the time of the last latch, so multi-byte reads stay consistent.
+#[derive(Debug, Copy, Clone)]
+pub struct RTC {
+    source: ClockSource,
+    /// Emulated cycles per second of the virtual clock
+    cycles_per_second: u64,
+    /// Counts down to the next second of the virtual clock
+    cycles_until_second: u64,
+    /// The host time the clock was last synced at
+    host_synced: u64,
+    seconds: u64,
+    latched: RTCTime,
+    pub alarm: RTCTime,
+    pub control: RTCControl,
+}
+
+impl Default for RTC {
+    fn default() -> Self {
+        Self {
+            source: ClockSource::Virtual,
+            cycles_per_second: Self::DEFAULT_CYCLES_PER_SECOND,
+            cycles_until_second: Self::DEFAULT_CYCLES_PER_SECOND,
+            host_synced: 0,
+            seconds: 0,
+            latched: RTCTime::default(),
+            alarm: RTCTime::default(),
+            control: RTCControl::empty(),
+        }
+    }
+}
+
+impl RTC {
+    pub const DEFAULT_CYCLES_PER_SECOND: u64 = 1_000_000;
+
+    pub fn source(&self) -> ClockSource {
+        self.source
+    }
+
+    /// Switching to the host clock jumps to the current host time without triggering the alarm
+    pub fn set_source(&mut self, source: ClockSource) {
+        if self.source == source {
+            return;
+        }
+
+        self.source = source;
+        match source {
+            ClockSource::Virtual => self.cycles_until_second = self.cycles_per_second,
+            ClockSource::Host => {
+                self.host_synced = Self::host_seconds();
+                self.seconds = self.host_synced;
+                // The host clock is synced separately, cycles never advance it
+                self.cycles_until_second = u64::MAX;
+            }
+        }
+    }
+
+    pub fn set_cycles_per_second(&mut self, cycles_per_second: u64) {
+        self.cycles_per_second = cycles_per_second.max(1);
+        if self.source == ClockSource::Virtual {
+            self.cycles_until_second = self.cycles_per_second;
+        }
+    }
+
+    /// Sets the time, the host clock keeps counting from it
+    pub fn set_seconds(&mut self, seconds: u64) {
+        self.seconds = seconds;
+        match self.source {
+            ClockSource::Virtual => self.cycles_until_second = self.cycles_per_second,
+            ClockSource::Host => self.host_synced = Self::host_seconds(),
+        }
+    }
+
+    pub fn time(&self) -> RTCTime {
+        RTCTime::from_seconds(self.seconds)
+    }
+
+    pub fn latched(&self) -> RTCTime {
+        self.latched
+    }
+
+    /// Advances the virtual clock, returns whether the alarm went off
+    #[inline(always)]
+    pub fn tick(&mut self, cycles: u64) -> bool {
+        if self.cycles_until_second > cycles {
+            self.cycles_until_second -= cycles;
+            return false;
+        }
+
+        self.elapse(cycles)
+    }
+
+    #[cold]
+    fn elapse(&mut self, cycles: u64) -> bool {
+        if self.source != ClockSource::Virtual {
+            return false;
+        }
+
+        let overflow = cycles - self.cycles_until_second;
+        self.cycles_until_second = self.cycles_per_second - overflow % self.cycles_per_second;
+        self.advance(1 + overflow / self.cycles_per_second)
+    }
+
+    /// Catches up with the host clock, returns whether the alarm went off
+    pub fn sync_host(&mut self) -> bool {
+        if self.source != ClockSource::Host {
+            return false;
+        }
+
+        let now = Self::host_seconds();
+        if now <= self.host_synced {
+            return false;
+        }
+        let elapsed = now - self.host_synced;
+        self.host_synced = now;
+        self.advance(elapsed)
+    }
+
+    fn advance(&mut self, seconds: u64) -> bool {
+        let start = self.seconds % SECONDS_PER_DAY;
+        self.seconds += seconds;
+        if !self.control.contains(RTCControl::ALARM_ENABLE) {
+            return false;
+        }
+
+        let alarm = self.alarm.second_of_day();
+        let end = start + seconds;
+        let fired = seconds >= SECONDS_PER_DAY
+            || (start < alarm && alarm <= end)
+            || (start < alarm + SECONDS_PER_DAY && alarm + SECONDS_PER_DAY <= end);
+        if fired {
+            self.control.insert(RTCControl::ALARM);
+        }
+        fired
+    }
+
+    fn host_seconds() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default()
+    }
+}
+
+impl MemoryMapped for RTC {
+    #[inline(always)]
+    fn read(&mut self, addr: Address) -> Byte {
+        match u16::from(addr) {
+            0x0000 => Byte::new(self.latched.seconds),
+            0x0001 => Byte::new(self.latched.minutes),
+            0x0002 => Byte::new(self.latched.hours),
+            0x0003 => Byte::new(self.latched.day as u8),
+            0x0004 => Byte::new((self.latched.day >> 8) as u8),
+            0x0005 => self.control.bits().into(),
+            0x0006 => Byte::new(self.alarm.seconds),
+            0x0007 => Byte::new(self.alarm.minutes),
+            0x0008 => Byte::new(self.alarm.hours),
+            _ => Byte::new(0),
+        }
+    }
+
+    #[inline(always)]
+    fn write(&mut self, addr: Address, value: Byte) {
+        let value = u8::from(value);
+        match u16::from(addr) {
+            0x0005 => {
+                let control = RTCControl::from_bits_truncate(value);
+                if control.contains(RTCControl::LATCH) {
+                    self.latched = self.time();
+                }
+                // Latch is a strobe, it always reads as cleared
+                self.control = control - RTCControl::LATCH;
+            }
+            0x0006 => self.alarm.seconds = value % 60,
+            0x0007 => self.alarm.minutes = value % 60,
+            0x0008 => self.alarm.hours = value % 24,
+            _ => {}
+        }
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+    pub struct RTCControl: u8 {
+        /// Write to copy the current time into the time registers
+        const LATCH = 0b0000_0001;
+        /// Raise the RTC interrupt once the time of day matches the alarm registers
+        const ALARM_ENABLE = 0b0000_0010;
+        /// Set when the alarm went off, write zero to clear
+        const ALARM = 0b1000_0000;
+    }
+}
@@ -0,0 +1,80 @@
+use crate::console::components::bus::MemoryMapped;
+use crate::console::types::address::Address;
+use crate::console::types::byte::Byte;
+use bitflags::bitflags;
+
+/// Cycles it takes the clocking side to shift a full byte over the link
+pub const SERIAL_TRANSFER_CYCLES: u64 = 512;
+
+/// Byte-wise serial port, the side with the internal clock drives the transfer.\
+/// Both sides swap their data registers once the transfer is done.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct SerialPort {
+    pub data: Byte,
+    pub control: SerialControl,
+    remaining_cycles: u64,
+}
+
+impl SerialPort {
+    #[inline(always)]
+    pub fn is_clocking(&self) -> bool {
+        self.control
+            .contains(SerialControl::BUSY | SerialControl::INTERNAL_CLOCK)
+    }
+
+    /// Waiting for the other side to clock a transfer
+    #[inline(always)]
+    pub fn is_listening(&self) -> bool {
+        self.control.contains(SerialControl::BUSY)
+            && !self.control.contains(SerialControl::INTERNAL_CLOCK)
+    }
+
+    /// Advance a clocked transfer, returns true once the byte is ready to be exchanged
+    #[inline(always)]
+    pub fn clock(&mut self, cycles: u64) -> bool {
+        if !self.is_clocking() {
+            return false;
+        }
+        self.remaining_cycles = self.remaining_cycles.saturating_sub(cycles);
+        self.remaining_cycles == 0
+    }
+
+    #[inline(always)]
+    pub fn complete(&mut self, incoming: Byte) {
+        self.data = incoming;
+        self.control.remove(SerialControl::BUSY);
+    }
+}
+
+impl MemoryMapped for SerialPort {
+    #[inline(always)]
+    fn read(&mut self, addr: Address) -> Byte {
+        match u16::from(addr) {
+            0x0000 => self.data,
+            0x0001 => self.control.bits().into(),
+            _ => Byte::new(0),
+        }
+    }
+
+    #[inline(always)]
+    fn write(&mut self, addr: Address, value: Byte) {
+        match u16::from(addr) {
+            0x0000 => self.data = value,
+            0x0001 => {
+                self.control = SerialControl::from_bits_truncate(value.into());
+                self.remaining_cycles = SERIAL_TRANSFER_CYCLES;
+            }
+            _ => {}
+        }
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+    pub struct SerialControl: u8 {
+        /// Drive the transfer instead of waiting for the other side
+        const INTERNAL_CLOCK = 0b0000_0001;
+        /// Write to start a transfer, reads as set until the transfer is complete
+        const BUSY = 0b1000_0000;
+    }
+}
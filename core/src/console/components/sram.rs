@@ -0,0 +1,61 @@
+use crate::console::components::bus::MemoryMapped;
+use crate::console::types::address::Address;
+use crate::console::types::byte::Byte;
+
+pub const SRAM_SIZE: usize = 0x1000; // 4KiB
+
+/// Battery-backed cartridge RAM, survives console resets
+#[derive(Debug, Clone)]
+pub struct SRAM {
+    data: [u8; SRAM_SIZE],
+    /// Set on every write, cleared once the contents have been persisted
+    dirty: bool,
+}
+
+impl Default for SRAM {
+    fn default() -> Self {
+        Self {
+            data: [0; SRAM_SIZE],
+            dirty: false,
+        }
+    }
+}
+
+impl SRAM {
+    /// Load previously persisted contents, shorter data is zero-padded and longer data truncated
+    pub fn load(&mut self, data: &[u8]) {
+        let len = data.len().min(SRAM_SIZE);
+        self.data = [0; SRAM_SIZE];
+        self.data[..len].copy_from_slice(&data[..len]);
+        self.dirty = false;
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    pub fn mark_clean(&mut self) {
+        self.dirty = false;
+    }
+
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+}
+
+impl MemoryMapped for SRAM {
+    #[inline(always)]
+    fn read(&mut self, addr: Address) -> Byte {
+        self.data[(u16::from(addr) & 0x0FFF) as usize].into()
+    }
+
+    #[inline(always)]
+    fn write(&mut self, addr: Address, value: Byte) {
+        self.data[(u16::from(addr) & 0x0FFF) as usize] = value.into();
+        self.dirty = true;
+    }
+}
@@ -0,0 +1,126 @@
+use crate::console::components::bus::MemoryMapped;
+use crate::console::types::address::Address;
+use crate::console::types::byte::Byte;
+use crate::console::types::word::Word;
+use bitflags::bitflags;
+
+/// Cycles per unit of the timeout register
+pub const WATCHDOG_TICK_CYCLES: u64 = 1024;
+
+/// What happens once the watchdog expires
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WatchdogAction {
+    /// The console is reset
+    Reset,
+    /// The watchdog interrupt is raised and the countdown restarts
+    Trap,
+}
+
+/// Counts down while enabled, the program has to kick it before it runs out.\
+/// Writing any value to the kick register restarts the countdown.
+#[derive(Debug, Copy, Clone)]
+pub struct Watchdog {
+    pub timeout: Word,
+    control: WatchdogControl,
+    /// Only counts down while enabled, stays at the maximum otherwise
+    remaining_cycles: u64,
+}
+
+impl Default for Watchdog {
+    fn default() -> Self {
+        Self {
+            timeout: Word::default(),
+            control: WatchdogControl::empty(),
+            remaining_cycles: u64::MAX,
+        }
+    }
+}
+
+impl Watchdog {
+    pub fn control(&self) -> WatchdogControl {
+        self.control
+    }
+
+    /// Enabling the watchdog starts the countdown with the current timeout
+    pub fn set_control(&mut self, control: WatchdogControl) {
+        let was_enabled = self.is_enabled();
+        self.control = control;
+        if !self.is_enabled() {
+            self.remaining_cycles = u64::MAX;
+        } else if !was_enabled {
+            self.kick();
+        }
+    }
+
+    #[inline(always)]
+    pub fn is_enabled(&self) -> bool {
+        self.control.contains(WatchdogControl::ENABLE)
+    }
+
+    pub fn remaining_cycles(&self) -> u64 {
+        self.remaining_cycles
+    }
+
+    pub fn kick(&mut self) {
+        if self.is_enabled() {
+            self.remaining_cycles = self.timeout.value() as u64 * WATCHDOG_TICK_CYCLES;
+        }
+    }
+
+    /// Counts down the cycles, returns the action to take if the watchdog expired
+    #[inline(always)]
+    pub fn tick(&mut self, cycles: u64) -> Option<WatchdogAction> {
+        if self.remaining_cycles > cycles {
+            self.remaining_cycles -= cycles;
+            return None;
+        }
+
+        Some(self.expire())
+    }
+
+    #[cold]
+    fn expire(&mut self) -> WatchdogAction {
+        self.control.insert(WatchdogControl::EXPIRED);
+        self.kick();
+        if self.control.contains(WatchdogControl::TRAP) {
+            WatchdogAction::Trap
+        } else {
+            WatchdogAction::Reset
+        }
+    }
+}
+
+impl MemoryMapped for Watchdog {
+    #[inline(always)]
+    fn read(&mut self, addr: Address) -> Byte {
+        match u16::from(addr) {
+            0x0000 => self.timeout.low_byte(),
+            0x0001 => self.timeout.high_byte(),
+            0x0003 => self.control.bits().into(),
+            _ => Byte::new(0),
+        }
+    }
+
+    #[inline(always)]
+    fn write(&mut self, addr: Address, value: Byte) {
+        match u16::from(addr) {
+            0x0000 => self.timeout.set_low_byte(value),
+            0x0001 => self.timeout.set_high_byte(value),
+            0x0002 => self.kick(),
+            0x0003 => self.set_control(WatchdogControl::from_bits_truncate(value.into())),
+            _ => {}
+        }
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+    pub struct WatchdogControl: u8 {
+        /// Starts the countdown with the current timeout
+        const ENABLE = 0b0000_0001;
+        /// Raise the watchdog interrupt instead of resetting the console
+        const TRAP = 0b0000_0010;
+        /// Set when the watchdog expired, write zero to clear
+        const EXPIRED = 0b1000_0000;
+    }
+}
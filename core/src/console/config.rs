@@ -0,0 +1,160 @@
+use crate::console::components::bus::Bus;
+use crate::console::components::ram::RAM_SIZE;
+use crate::error::{LMVC8Error, LMVC8Result};
+use bitflags::bitflags;
+use std::fmt::{Display, Formatter};
+
+/// Hardware variants of the console, cartridges declare the one they require
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum HardwareProfile {
+    /// The full console
+    #[default]
+    Standard,
+    /// Cut-down model with 8KiB RAM, a slower clock and neither link port, RTC nor watchdog
+    Lite,
+}
+
+impl HardwareProfile {
+    pub const ALL: [Self; 2] = [Self::Standard, Self::Lite];
+
+    pub fn config(&self) -> ConsoleConfig {
+        match self {
+            Self::Standard => ConsoleConfig {
+                profile: *self,
+                ram_size: RAM_SIZE as u16,
+                cycles_per_second: 400_000_000,
+                peripherals: Peripherals::all(),
+                boot_pc: Bus::ROM_START,
+                boot_sp: Bus::DEFAULT_SP,
+                boot_rom: false,
+            },
+            Self::Lite => ConsoleConfig {
+                profile: *self,
+                ram_size: 0x2000,
+                cycles_per_second: 100_000_000,
+                peripherals: Peripherals::DMA | Peripherals::DEBUG_PORT | Peripherals::RNG,
+                boot_pc: Bus::ROM_START,
+                boot_sp: Bus::RAM_START + 0x1FFF,
+                boot_rom: false,
+            },
+        }
+    }
+}
+
+impl From<HardwareProfile> for u8 {
+    fn from(profile: HardwareProfile) -> Self {
+        match profile {
+            HardwareProfile::Standard => 0,
+            HardwareProfile::Lite => 1,
+        }
+    }
+}
+
+impl TryFrom<u8> for HardwareProfile {
+    type Error = LMVC8Error;
+
+    fn try_from(value: u8) -> LMVC8Result<Self> {
+        match value {
+            0 => Ok(Self::Standard),
+            1 => Ok(Self::Lite),
+            _ => Err(LMVC8Error::UnknownHardwareProfile(value)),
+        }
+    }
+}
+
+impl Display for HardwareProfile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+bitflags! {
+    /// Optional devices on the bus, the input controller and interrupt registers are always present
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    pub struct Peripherals: u8 {
+        const DMA = 0b0000_0001;
+        const SERIAL = 0b0000_0010;
+        const DEBUG_PORT = 0b0000_0100;
+        const RNG = 0b0000_1000;
+        const RTC = 0b0001_0000;
+        const WATCHDOG = 0b0010_0000;
+    }
+}
+
+/// Describes the hardware a [`Console`](crate::console::Console) is built with
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ConsoleConfig {
+    /// The profile this configuration is based on
+    pub profile: HardwareProfile,
+    /// RAM mapped from [`Bus::RAM_START`], at most [`RAM_SIZE`] bytes
+    pub ram_size: u16,
+    /// Default clock speed the emulator runs the console at
+    pub cycles_per_second: u64,
+    pub peripherals: Peripherals,
+    /// Entry point of cartridges that don't declare their own
+    pub boot_pc: u16,
+    /// Initial SP of cartridges that don't declare their own
+    pub boot_sp: u16,
+    /// Run the built-in [`BootROM`](crate::console::components::boot_rom::BootROM) before jumping to the entry point
+    pub boot_rom: bool,
+}
+
+impl Default for ConsoleConfig {
+    fn default() -> Self {
+        HardwareProfile::default().config()
+    }
+}
+
+impl ConsoleConfig {
+    pub fn with_ram_size(mut self, ram_size: u16) -> Self {
+        self.ram_size = ram_size;
+        self
+    }
+
+    pub fn with_cycles_per_second(mut self, cycles_per_second: u64) -> Self {
+        self.cycles_per_second = cycles_per_second;
+        self
+    }
+
+    pub fn with_peripherals(mut self, peripherals: Peripherals) -> Self {
+        self.peripherals = peripherals;
+        self
+    }
+
+    pub fn with_boot(mut self, pc: u16, sp: u16) -> Self {
+        self.boot_pc = pc;
+        self.boot_sp = sp;
+        self
+    }
+
+    pub fn with_boot_rom(mut self, boot_rom: bool) -> Self {
+        self.boot_rom = boot_rom;
+        self
+    }
+
+    /// Last RAM address, RAM always starts at [`Bus::RAM_START`]
+    pub fn ram_end(&self) -> u16 {
+        Bus::RAM_START + self.ram_size - 1
+    }
+
+    pub fn validate(&self) -> LMVC8Result<()> {
+        if self.ram_size == 0 || self.ram_size as usize > RAM_SIZE {
+            return Err(LMVC8Error::InvalidConsoleConfig(format!(
+                "RAM size {:#X} is not within 1..={RAM_SIZE:#X}",
+                self.ram_size
+            )));
+        }
+        if self.cycles_per_second == 0 {
+            return Err(LMVC8Error::InvalidConsoleConfig(
+                "the clock speed must not be zero".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Whether cartridges made for the profile run on this hardware
+    pub fn supports(&self, profile: HardwareProfile) -> bool {
+        let required = profile.config();
+        self.ram_size >= required.ram_size && self.peripherals.contains(required.peripherals)
+    }
+}
@@ -0,0 +1,93 @@
+use crate::console::components::bus::Bus;
+use crate::console::Console;
+
+/// The other end of a console's serial port
+pub trait SerialLink {
+    /// Called when the local port clocked out a byte, returns the byte shifted in from the other end
+    fn exchange(&mut self, outgoing: u8) -> u8;
+}
+
+/// Nothing plugged in, the line idles high
+#[derive(Debug, Default, Copy, Clone)]
+pub struct NullLink;
+
+impl SerialLink for NullLink {
+    #[inline(always)]
+    fn exchange(&mut self, _outgoing: u8) -> u8 {
+        0xFF
+    }
+}
+
+/// Output wired back into the input
+#[derive(Debug, Default, Copy, Clone)]
+pub struct LoopbackLink;
+
+impl SerialLink for LoopbackLink {
+    #[inline(always)]
+    fn exchange(&mut self, outgoing: u8) -> u8 {
+        outgoing
+    }
+}
+
+/// Cable into the serial port of another console's bus
+pub struct BusLink<'a>(pub &'a mut Bus);
+
+impl SerialLink for BusLink<'_> {
+    fn exchange(&mut self, outgoing: u8) -> u8 {
+        self.0.serial_receive(outgoing)
+    }
+}
+
+/// Two consoles connected by a link cable.\
+/// Always steps the console that is behind in cycles, so the exchange is deterministic.
+#[derive(Debug, Default, Clone)]
+pub struct LinkedConsoles {
+    pub first: Console,
+    pub second: Console,
+    first_cycles: u64,
+    second_cycles: u64,
+}
+
+impl LinkedConsoles {
+    pub fn new(first: Console, second: Console) -> Self {
+        Self {
+            first,
+            second,
+            first_cycles: 0,
+            second_cycles: 0,
+        }
+    }
+
+    /// Steps the console that is behind, returns true if that console halted
+    pub fn step(&mut self) -> bool {
+        if self.first_cycles <= self.second_cycles {
+            self.step_first()
+        } else {
+            self.step_second()
+        }
+    }
+
+    /// Steps until both consoles halted, a halted console waits for the other one
+    pub fn step_till_halt(&mut self) {
+        let (mut first_halted, mut second_halted) = (false, false);
+        while !(first_halted && second_halted) {
+            if !first_halted && (second_halted || self.first_cycles <= self.second_cycles) {
+                first_halted = self.step_first();
+            } else {
+                second_halted = self.step_second();
+            }
+        }
+    }
+
+    fn step_first(&mut self) -> bool {
+        let step = self.first.step_linked(&mut BusLink(&mut self.second.bus));
+        self.first_cycles += step.cycles;
+        step.cpu_step_flags.is_halt()
+    }
+
+    fn step_second(&mut self) -> bool {
+        let step = self.second.step_linked(&mut BusLink(&mut self.first.bus));
+        self.second_cycles += step.cycles;
+        step.cpu_step_flags.is_halt()
+    }
+}
@@ -1,6 +1,9 @@
 use crate::console::components::cpu::step_flags::CPUStepFlags;
+use crate::console::components::watchdog::WatchdogAction;
 
 pub struct ConsoleStep {
     pub cycles: u64,
     pub cpu_step_flags: CPUStepFlags,
+    /// Set if the watchdog expired during this step
+    pub watchdog: Option<WatchdogAction>,
 }
@@ -1,6 +1,8 @@
+use crate::console::cartridge::symbols::DebugSymbols;
 use crate::console::types::address::Address;
 use crate::console::Console;
 use crate::debugger::event::DebuggerEvent;
+use crate::error::{LMVC8Error, LMVC8Result};
 use std::collections::HashSet;
 
 pub mod event;
@@ -8,6 +10,8 @@ pub mod event;
 #[derive(Debug, Default, Clone)]
 pub struct Debugger {
     breakpoints: HashSet<Address>,
+    /// Symbols of the loaded cartridge
+    symbols: Option<DebugSymbols>,
 }
 
 impl Debugger {
@@ -37,4 +41,24 @@ impl Debugger {
     pub fn remove_breakpoint(&mut self, address: Address) {
         self.breakpoints.remove(&address);
     }
+
+    /// Set a breakpoint at a label or function of the loaded cartridge
+    pub fn set_breakpoint_by_name(&mut self, name: &str) -> LMVC8Result<Address> {
+        let address = self
+            .symbols
+            .as_ref()
+            .and_then(|symbols| symbols.address_of(name))
+            .ok_or_else(|| LMVC8Error::UnknownSymbol(name.to_string()))?
+            .into();
+        self.set_breakpoint(address);
+        Ok(address)
+    }
+
+    pub fn symbols(&self) -> Option<&DebugSymbols> {
+        self.symbols.as_ref()
+    }
+
+    pub fn set_symbols(&mut self, symbols: Option<DebugSymbols>) {
+        self.symbols = symbols;
+    }
 }
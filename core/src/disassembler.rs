@@ -1,3 +1,4 @@
+use crate::console::cartridge::symbols::DebugSymbols;
 use crate::console::components::cpu::instructions::CPUInstruction;
 use crate::disassembler::node::Node;
 
@@ -7,15 +8,26 @@ pub struct Disassembler<'a> {
     binary: &'a [u8],
     offset: usize,
     nodes: Vec<Node>,
+    symbols: Option<&'a DebugSymbols>,
 }
 
 #[derive(Debug, Default)]
-#[repr(transparent)]
-pub struct DisassembledBinary(Vec<Node>);
+pub struct DisassembledBinary {
+    nodes: Vec<Node>,
+    symbols: Option<DebugSymbols>,
+}
 
 impl DisassembledBinary {
     pub fn nodes(&self) -> &[Node] {
-        &self.0
+        &self.nodes
+    }
+
+    pub fn symbols(&self) -> Option<&DebugSymbols> {
+        self.symbols.as_ref()
+    }
+
+    pub fn label_at(&self, address: u16) -> Option<&str> {
+        self.symbols.as_ref()?.label_at(address)
     }
 }
 
@@ -25,15 +37,32 @@ impl<'a> Disassembler<'a> {
             binary,
             offset: 0,
             nodes: Vec::new(),
+            symbols: None,
         }
     }
 
+    /// Use the cartridge's debug symbols, data regions are kept as bytes instead of being decoded
+    pub fn with_symbols(mut self, symbols: Option<&'a DebugSymbols>) -> Self {
+        self.symbols = symbols;
+        self
+    }
+
+    fn is_data(&self) -> bool {
+        self.symbols
+            .is_some_and(|symbols| symbols.is_data(self.offset as u16))
+    }
+
     fn has_data(&self) -> bool {
         self.offset < self.binary.len()
     }
 
     pub fn disassemble(mut self) -> DisassembledBinary {
         while self.has_data() {
+            if self.is_data() {
+                self.push_read();
+                continue;
+            }
+
             let value = self.read();
             let instruction = CPUInstruction::from(value);
             self.push_instruction(instruction);
@@ -46,7 +75,10 @@ impl<'a> Disassembler<'a> {
             }
         }
 
-        DisassembledBinary(self.nodes)
+        DisassembledBinary {
+            nodes: self.nodes,
+            symbols: self.symbols.cloned(),
+        }
     }
 
     fn read(&mut self) -> u8 {
@@ -1,9 +1,13 @@
 use crate::console::cartridge::Cartridge;
+use crate::console::components::rtc::ClockSource;
+use crate::console::config::ConsoleConfig;
 use crate::console::types::address::Address;
 use crate::console::Console;
-use crate::emulator::command::{EmulatorCommand, EmulatorCommandSender};
+use crate::emulator::command::{EmulatorCommand, EmulatorCommandSender, RngSeed};
 use crate::emulator::event::{EmulatorEvent, EmulatorEventReceiver};
 use crate::emulator::state::EmulatorState;
+use crate::error::LMVC8Result;
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use std::thread::JoinHandle;
 
@@ -27,12 +31,19 @@ impl Default for Emulator {
 
 impl Emulator {
     pub fn new() -> Self {
+        Self::with_console(Console::new())
+    }
+
+    /// Starts the emulator with a console built from the config
+    pub fn with_config(config: ConsoleConfig) -> LMVC8Result<Self> {
+        Ok(Self::with_console(Console::with_config(config)?))
+    }
+
+    fn with_console(console: Console) -> Self {
         let (command_sender, command_receiver) = EmulatorCommand::channel();
         let (event_sender, event_receiver) = EmulatorEvent::channel();
         let state = Arc::new(Mutex::new(EmulatorState::new()));
 
-        let console = Console::new();
-
         let thread_state = state.clone();
         let thread_handle = std::thread::spawn(move || {
             #[cfg(not(feature = "debugger"))]
@@ -87,6 +98,24 @@ impl Emulator {
         self.command_sender.load(Box::new(cartridge));
     }
 
+    /// Write the current cartridge RAM contents to the specified file
+    pub fn export_save_ram(&self, path: PathBuf) {
+        self.command_sender.export_save_ram(path);
+    }
+
+    /// Replace the current cartridge RAM contents with the specified file
+    pub fn import_save_ram(&self, path: PathBuf) {
+        self.command_sender.import_save_ram(path);
+    }
+
+    pub fn set_rng_seed(&self, seed: RngSeed) {
+        self.command_sender.set_rng_seed(seed);
+    }
+
+    pub fn set_rtc_source(&self, source: ClockSource) {
+        self.command_sender.set_rtc_source(source);
+    }
+
     pub fn with_state<T, F>(&self, f: F) -> Option<T>
     where
         F: FnOnce(&EmulatorState) -> T,
@@ -108,6 +137,11 @@ impl Emulator {
         self.command_sender.set_breakpoint(address);
     }
 
+    #[cfg(feature = "debugger")]
+    pub fn set_breakpoint_by_name(&self, name: String) {
+        self.command_sender.set_breakpoint_by_name(name);
+    }
+
     #[cfg(feature = "debugger")]
     pub fn remove_breakpoint(&self, address: Address) {
         self.command_sender.remove_breakpoint(address);
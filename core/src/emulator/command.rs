@@ -1,7 +1,11 @@
 use crate::console::cartridge::Cartridge;
+use crate::console::components::rng::RNG;
+use crate::console::components::rtc::ClockSource;
 use crate::console::input::ConsoleInput;
 use crate::console::types::address::Address;
+use std::path::PathBuf;
 use std::sync::mpsc::{Receiver, Sender};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 #[derive(Debug)]
 pub enum EmulatorCommand {
@@ -13,8 +17,15 @@ pub enum EmulatorCommand {
     Shutdown,
     Input(ConsoleInput),
     SetClockSpeed(u64),
+    ExportSaveRam(PathBuf),
+    ImportSaveRam(PathBuf),
+    SetRngSeed(RngSeed),
+    SetRtcSource(ClockSource),
     #[cfg(feature = "debugger")]
     SetBreakpoint(Address),
+    /// Set a breakpoint at a label or function from the cartridge's debug symbols
+    #[cfg(feature = "debugger")]
+    SetBreakpointByName(String),
     #[cfg(feature = "debugger")]
     RemoveBreakpoint(Address),
 }
@@ -29,6 +40,36 @@ impl EmulatorCommand {
     }
 }
 
+/// Seed the RNG is restarted with on every reset and load
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RngSeed {
+    /// Same random sequence on every run, keeps input replays reproducible
+    Fixed(u16),
+    /// New seed taken from the host clock every time
+    Host,
+}
+
+impl Default for RngSeed {
+    fn default() -> Self {
+        Self::Fixed(RNG::DEFAULT_SEED)
+    }
+}
+
+impl RngSeed {
+    pub fn resolve(&self) -> u16 {
+        match self {
+            Self::Fixed(seed) => *seed,
+            Self::Host => {
+                let nanos = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|duration| duration.as_nanos())
+                    .unwrap_or_default();
+                (nanos ^ (nanos >> 16) ^ (nanos >> 32)) as u16
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 #[repr(transparent)]
 pub struct EmulatorCommandSender(Sender<EmulatorCommand>);
@@ -66,11 +107,32 @@ impl EmulatorCommandSender {
         self.send(EmulatorCommand::SetClockSpeed(clock_speed));
     }
 
+    pub fn export_save_ram(&self, path: PathBuf) {
+        self.send(EmulatorCommand::ExportSaveRam(path));
+    }
+
+    pub fn import_save_ram(&self, path: PathBuf) {
+        self.send(EmulatorCommand::ImportSaveRam(path));
+    }
+
+    pub fn set_rng_seed(&self, seed: RngSeed) {
+        self.send(EmulatorCommand::SetRngSeed(seed));
+    }
+
+    pub fn set_rtc_source(&self, source: ClockSource) {
+        self.send(EmulatorCommand::SetRtcSource(source));
+    }
+
     #[cfg(feature = "debugger")]
     pub fn set_breakpoint(&self, address: Address) {
         self.send(EmulatorCommand::SetBreakpoint(address));
     }
 
+    #[cfg(feature = "debugger")]
+    pub fn set_breakpoint_by_name(&self, name: String) {
+        self.send(EmulatorCommand::SetBreakpointByName(name));
+    }
+
     #[cfg(feature = "debugger")]
     pub fn remove_breakpoint(&self, address: Address) {
         self.send(EmulatorCommand::RemoveBreakpoint(address));
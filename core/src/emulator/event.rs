@@ -1,3 +1,5 @@
+use crate::console::components::watchdog::WatchdogAction;
+use crate::console::types::address::Address;
 use crate::console::Console;
 use std::sync::mpsc::{Receiver, Sender};
 
@@ -5,6 +7,19 @@ use std::sync::mpsc::{Receiver, Sender};
 pub enum EmulatorEvent {
     CartridgeLoadFailed,
     CartridgeLoadSuccess,
+    DebugOutput(String),
+    SaveRamFlushFailed,
+    SaveRamExportFailed,
+    SaveRamExportSuccess,
+    SaveRamImportFailed,
+    SaveRamImportSuccess,
+    /// A breakpoint was requested by a name the loaded cartridge has no symbol for
+    UnknownSymbol(String),
+    /// The program stopped kicking the watchdog, `pc` is where it was when the watchdog expired
+    WatchdogExpired {
+        action: WatchdogAction,
+        pc: Address,
+    },
     Shutdown(Box<Console>),
 }
 
@@ -35,6 +50,38 @@ impl EmulatorEventSender {
     pub fn cartridge_load_success(&self) {
         self.send(EmulatorEvent::CartridgeLoadSuccess);
     }
+
+    pub fn debug_output(&self, output: String) {
+        self.send(EmulatorEvent::DebugOutput(output));
+    }
+
+    pub fn save_ram_flush_failed(&self) {
+        self.send(EmulatorEvent::SaveRamFlushFailed);
+    }
+
+    pub fn save_ram_export_failed(&self) {
+        self.send(EmulatorEvent::SaveRamExportFailed);
+    }
+
+    pub fn save_ram_export_success(&self) {
+        self.send(EmulatorEvent::SaveRamExportSuccess);
+    }
+
+    pub fn save_ram_import_failed(&self) {
+        self.send(EmulatorEvent::SaveRamImportFailed);
+    }
+
+    pub fn save_ram_import_success(&self) {
+        self.send(EmulatorEvent::SaveRamImportSuccess);
+    }
+
+    pub fn unknown_symbol(&self, name: String) {
+        self.send(EmulatorEvent::UnknownSymbol(name));
+    }
+
+    pub fn watchdog_expired(&self, action: WatchdogAction, pc: Address) {
+        self.send(EmulatorEvent::WatchdogExpired { action, pc });
+    }
 }
 
 #[derive(Debug)]
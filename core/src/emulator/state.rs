@@ -1,13 +1,23 @@
+use crate::console::components::bus::memory_map::MemoryRegion;
 use crate::console::components::cpu::CPU;
+use crate::console::components::dma::DMA;
+use crate::console::components::rtc::ClockSource;
+use crate::console::config::ConsoleConfig;
+use crate::emulator::command::RngSeed;
 
 #[derive(Debug, Default)]
 pub struct EmulatorState {
     pub cpu_snapshot: CPU,
+    pub dma_snapshot: DMA,
+    pub memory_layout: Vec<MemoryRegion>,
+    pub console_config: ConsoleConfig,
     pub is_running: bool,
     pub is_halting: bool,
     #[cfg(feature = "debugger")]
     pub breakpoints: std::collections::HashSet<crate::console::types::address::Address>,
     pub cycles_per_second: u64,
+    pub rng_seed: RngSeed,
+    pub rtc_source: ClockSource,
     pub last_frame_mics: u64,
     pub last_frame_cycles: u64,
 }
@@ -1,14 +1,20 @@
+use crate::console::components::rtc::ClockSource;
+use crate::console::components::watchdog::WatchdogAction;
+use crate::console::config::HardwareProfile;
 use crate::console::step::ConsoleStep;
 use crate::console::Console;
-use crate::emulator::command::{EmulatorCommand, EmulatorCommandReceiver};
+use crate::emulator::command::{EmulatorCommand, EmulatorCommandReceiver, RngSeed};
 use crate::emulator::event::EmulatorEventSender;
 use crate::emulator::state::EmulatorState;
+use crate::error::{LMVC8Error, LMVC8Result};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
-const DEFAULT_CYCLES_PER_SECOND: u64 = 400_000_000;
 const FRAMES_PER_SECOND: u64 = 60;
 const FRAME_TIME: Duration = Duration::from_micros(1_000_000 / FRAMES_PER_SECOND);
+/// How often dirty cartridge RAM is written back to its save file
+const SAVE_FLUSH_FRAMES: u64 = FRAMES_PER_SECOND * 5;
 
 pub struct EmulatorThreadContext {
     console: Console,
@@ -20,10 +26,15 @@ pub struct EmulatorThreadContext {
     running: bool,
     halt: bool,
     cycles_per_second: u64,
+    /// Set when the clock speed was chosen explicitly, it then outlasts profile switches
+    custom_clock_speed: bool,
     last_frame_mics: u64,
     last_frame_cycles: u64,
     last_frame_steps: u64,
     frame_start: Instant,
+    save_path: Option<PathBuf>,
+    frames_since_flush: u64,
+    rng_seed: RngSeed,
 }
 
 impl EmulatorThreadContext {
@@ -34,7 +45,8 @@ impl EmulatorThreadContext {
         command_receiver: EmulatorCommandReceiver,
         event_sender: EmulatorEventSender,
     ) -> Self {
-        Self {
+        let cycles_per_second = console.config().cycles_per_second;
+        let mut context = Self {
             console,
             #[cfg(feature = "debugger")]
             debugger,
@@ -43,12 +55,44 @@ impl EmulatorThreadContext {
             event_sender,
             running: false,
             halt: false,
-            cycles_per_second: DEFAULT_CYCLES_PER_SECOND,
+            cycles_per_second,
+            custom_clock_speed: false,
             last_frame_mics: 0,
             last_frame_cycles: 0,
             last_frame_steps: 0,
             frame_start: Instant::now(),
+            save_path: None,
+            frames_since_flush: 0,
+            rng_seed: RngSeed::default(),
+        };
+        // In normal play the clock follows the host, tests and replays drive the console directly
+        context.setup_console(ClockSource::Host);
+        context
+    }
+
+    /// Applies the emulator settings to a freshly built console
+    fn setup_console(&mut self, rtc_source: ClockSource) {
+        self.console.bus.rtc.set_source(rtc_source);
+        self.console
+            .bus
+            .rtc
+            .set_cycles_per_second(self.cycles_per_second);
+    }
+
+    /// Swaps in hardware the cartridge runs on, if the current console doesn't support its profile
+    fn ensure_profile(&mut self, profile: HardwareProfile) -> LMVC8Result<()> {
+        if self.console.config().supports(profile) {
+            return Ok(());
+        }
+
+        // The new console starts with defaults, keep what was chosen for the old one
+        let rtc_source = self.console.bus.rtc.source();
+        self.console = Console::with_config(profile.config())?;
+        if !self.custom_clock_speed {
+            self.cycles_per_second = self.console.config().cycles_per_second;
         }
+        self.setup_console(rtc_source);
+        Ok(())
     }
 
     pub fn run(mut self) {
@@ -60,6 +104,7 @@ impl EmulatorThreadContext {
             } else {
                 (0, 0)
             };
+            self.console.sync_rtc();
 
             if let Some(command) = self.command_receiver.poll() {
                 let shutdown = self.handle_command(command);
@@ -69,6 +114,12 @@ impl EmulatorThreadContext {
             }
 
             self.update_state();
+            self.flush_debug_output();
+
+            self.frames_since_flush += 1;
+            if self.frames_since_flush >= SAVE_FLUSH_FRAMES {
+                self.flush_save_ram();
+            }
 
             let elapsed = self.frame_start.elapsed();
             self.last_frame_mics = elapsed.as_micros() as u64;
@@ -76,6 +127,7 @@ impl EmulatorThreadContext {
             std::thread::sleep(sleep_time);
         }
 
+        self.flush_save_ram();
         self.event_sender.shutdown(self.console);
     }
 
@@ -104,8 +156,16 @@ impl EmulatorThreadContext {
     }
 
     fn step(&mut self) -> ConsoleStep {
+        let pc = self.console.cpu.get_pc();
         let console_step = self.console.step();
 
+        if let Some(action) = console_step.watchdog {
+            if action == WatchdogAction::Reset {
+                self.seed_rng();
+            }
+            self.event_sender.watchdog_expired(action, pc.into());
+        }
+
         if console_step.cpu_step_flags.is_halt() {
             self.halt();
         }
@@ -119,9 +179,16 @@ impl EmulatorThreadContext {
     fn update_state(&mut self) {
         if let Ok(mut state_lock) = self.state.try_lock() {
             state_lock.cpu_snapshot = self.console.cpu;
+            state_lock.dma_snapshot = self.console.bus.dma;
+            state_lock.console_config = *self.console.config();
+            if state_lock.memory_layout != self.console.bus.layout() {
+                state_lock.memory_layout = self.console.bus.layout().to_vec();
+            }
             state_lock.is_running = self.running;
             state_lock.is_halting = self.halt;
             state_lock.cycles_per_second = self.cycles_per_second;
+            state_lock.rng_seed = self.rng_seed;
+            state_lock.rtc_source = self.console.bus.rtc.source();
             state_lock.last_frame_mics = self.last_frame_mics;
             state_lock.last_frame_cycles = self.last_frame_cycles;
         }
@@ -139,9 +206,25 @@ impl EmulatorThreadContext {
             EmulatorCommand::Load(cartridge) => {
                 self.running = false;
                 self.halt = false;
-                match self.console.load_cartridge(*cartridge) {
-                    Ok(_) => self.event_sender.cartridge_load_success(),
-                    Err(_) => self.event_sender.cartridge_load_failed(),
+                self.flush_save_ram();
+                self.save_path = cartridge.save_path();
+                #[cfg(feature = "debugger")]
+                let symbols = cartridge.symbols.clone();
+                let result = self
+                    .ensure_profile(cartridge.profile)
+                    .and_then(|_| self.console.load_cartridge(*cartridge));
+                match result {
+                    Ok(_) => {
+                        #[cfg(feature = "debugger")]
+                        self.debugger.set_symbols(symbols);
+                        self.seed_rng();
+                        self.load_save_ram();
+                        self.event_sender.cartridge_load_success()
+                    }
+                    Err(_) => {
+                        self.save_path = None;
+                        self.event_sender.cartridge_load_failed()
+                    }
                 }
                 self.update_state();
             }
@@ -149,6 +232,7 @@ impl EmulatorThreadContext {
                 self.running = false;
                 self.halt = false;
                 self.console.reset();
+                self.seed_rng();
                 self.update_state();
             }
             EmulatorCommand::Run => self.running = true,
@@ -168,6 +252,28 @@ impl EmulatorThreadContext {
             }
             EmulatorCommand::SetClockSpeed(cycles_per_second) => {
                 self.cycles_per_second = cycles_per_second;
+                self.custom_clock_speed = true;
+                self.console
+                    .bus
+                    .rtc
+                    .set_cycles_per_second(cycles_per_second);
+                self.update_state();
+            }
+            EmulatorCommand::ExportSaveRam(path) => match self.export_save_ram(&path) {
+                Ok(_) => self.event_sender.save_ram_export_success(),
+                Err(_) => self.event_sender.save_ram_export_failed(),
+            },
+            EmulatorCommand::ImportSaveRam(path) => match self.import_save_ram(&path) {
+                Ok(_) => self.event_sender.save_ram_import_success(),
+                Err(_) => self.event_sender.save_ram_import_failed(),
+            },
+            EmulatorCommand::SetRngSeed(seed) => {
+                self.rng_seed = seed;
+                self.seed_rng();
+                self.update_state();
+            }
+            EmulatorCommand::SetRtcSource(source) => {
+                self.console.bus.rtc.set_source(source);
                 self.update_state();
             }
             #[cfg(feature = "debugger")]
@@ -176,6 +282,13 @@ impl EmulatorThreadContext {
                 self.update_state_debug();
             }
             #[cfg(feature = "debugger")]
+            EmulatorCommand::SetBreakpointByName(name) => {
+                if self.debugger.set_breakpoint_by_name(&name).is_err() {
+                    self.event_sender.unknown_symbol(name);
+                }
+                self.update_state_debug();
+            }
+            #[cfg(feature = "debugger")]
             EmulatorCommand::RemoveBreakpoint(address) => {
                 self.debugger.remove_breakpoint(address);
                 self.update_state_debug();
@@ -185,6 +298,69 @@ impl EmulatorThreadContext {
         false
     }
 
+    fn seed_rng(&mut self) {
+        self.console.bus.rng.set_seed(self.rng_seed.resolve());
+    }
+
+    fn flush_debug_output(&mut self) {
+        let output = self.console.take_debug_text();
+        if output.is_empty() {
+            return;
+        }
+
+        self.event_sender.debug_output(output);
+    }
+
+    fn load_save_ram(&mut self) {
+        let (Some(path), Some(sram)) = (&self.save_path, self.console.bus.sram.as_mut()) else {
+            return;
+        };
+
+        // A missing save file just means the game has never been saved
+        if let Ok(data) = std::fs::read(path) {
+            sram.load(&data);
+        }
+    }
+
+    fn flush_save_ram(&mut self) {
+        self.frames_since_flush = 0;
+
+        let (Some(path), Some(sram)) = (&self.save_path, self.console.bus.sram.as_mut()) else {
+            return;
+        };
+        if !sram.is_dirty() {
+            return;
+        }
+
+        match std::fs::write(path, sram.data()) {
+            Ok(_) => sram.mark_clean(),
+            Err(_) => self.event_sender.save_ram_flush_failed(),
+        }
+    }
+
+    fn export_save_ram(&self, path: &Path) -> LMVC8Result<()> {
+        let sram = self
+            .console
+            .bus
+            .sram
+            .as_ref()
+            .ok_or(LMVC8Error::NoSaveRam)?;
+        Ok(std::fs::write(path, sram.data())?)
+    }
+
+    fn import_save_ram(&mut self, path: &Path) -> LMVC8Result<()> {
+        let sram = self
+            .console
+            .bus
+            .sram
+            .as_mut()
+            .ok_or(LMVC8Error::NoSaveRam)?;
+        let data = std::fs::read(path)?;
+        sram.load(&data);
+        sram.mark_dirty();
+        Ok(())
+    }
+
     #[cfg(feature = "debugger")]
     fn debug(&mut self) {
         let debugger_events = self.debugger.inspect(&self.console);
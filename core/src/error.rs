@@ -1,3 +1,4 @@
+use crate::console::config::HardwareProfile;
 use thiserror::Error;
 
 pub type LMVC8Result<T> = Result<T, LMVC8Error>;
@@ -8,4 +9,56 @@ pub enum LMVC8Error {
     IO(#[from] std::io::Error),
     #[error("The ROM size is too large")]
     ROMSizeExceeded,
+    #[error("The loaded cartridge has no save RAM")]
+    NoSaveRam,
+    #[error("Memory region '{0}' is empty")]
+    EmptyMemoryRegion(String),
+    #[error("Memory region '{name}' overlaps with '{other}'")]
+    MemoryRegionOverlap { name: String, other: String },
+    #[error("No more memory regions or devices can be mapped")]
+    MemoryMapFull,
+    #[error("No memory region named '{0}'")]
+    UnknownMemoryRegion(String),
+    #[error("Memory region '{0}' is fixed and can't be unmapped")]
+    FixedMemoryRegion(String),
+    #[error("Invalid console config: {0}")]
+    InvalidConsoleConfig(String),
+    #[error("Not an LMC cartridge")]
+    InvalidCartridgeMagic,
+    #[error("Unsupported LMC cartridge version {0}")]
+    UnsupportedCartridgeVersion(u8),
+    #[error("The cartridge file is truncated")]
+    TruncatedCartridge,
+    #[error("Invalid cartridge header: {0}")]
+    InvalidCartridgeHeader(String),
+    #[error(
+        "Cartridge checksum mismatch, expected {expected:#010X} but the ROM has {actual:#010X}"
+    )]
+    CartridgeChecksumMismatch { expected: u32, actual: u32 },
+    #[error("Invalid debug symbols: {0}")]
+    InvalidDebugSymbols(String),
+    #[error("Invalid patch: {0}")]
+    InvalidPatch(String),
+    #[error(
+        "Patch {checksum} checksum mismatch, expected {expected:#010X} but got {actual:#010X}"
+    )]
+    PatchChecksumMismatch {
+        checksum: &'static str,
+        expected: u32,
+        actual: u32,
+    },
+    #[error("Invalid ROM image in line {line}: {reason}")]
+    InvalidRomImage { line: usize, reason: String },
+    #[error("ROM image checksum mismatch in line {line}, expected {expected:#04X} but got {actual:#04X}")]
+    RomImageChecksumMismatch {
+        line: usize,
+        expected: u8,
+        actual: u8,
+    },
+    #[error("No label or function named '{0}'")]
+    UnknownSymbol(String),
+    #[error("Unknown hardware profile {0}")]
+    UnknownHardwareProfile(u8),
+    #[error("The cartridge requires the {0} hardware profile")]
+    UnsupportedHardwareProfile(HardwareProfile),
 }
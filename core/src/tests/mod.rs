@@ -1,7 +1,44 @@
 use crate::console::components::cpu::registers::{R16, R8};
 use crate::console::Console;
 
+#[cfg(feature = "compiler")]
+mod test_assembler;
+mod test_boot;
+mod test_cartridge_format;
+#[cfg(feature = "compiler")]
+mod test_compiler_control_flow;
+#[cfg(feature = "compiler")]
+mod test_compiler_data;
+#[cfg(feature = "compiler")]
+mod test_compiler_errors;
+#[cfg(feature = "compiler")]
+mod test_compiler_functions;
+#[cfg(feature = "compiler")]
+mod test_compiler_interrupts;
+#[cfg(feature = "compiler")]
+mod test_compiler_labels;
+#[cfg(feature = "compiler")]
+mod test_compiler_layout;
+#[cfg(feature = "compiler")]
+mod test_compiler_optimizer;
+#[cfg(feature = "compiler")]
+mod test_compiler_variables;
+mod test_console_config;
+mod test_debug_port;
+mod test_debug_symbols;
+mod test_dma;
 mod test_instructions;
+mod test_interrupts;
+#[cfg(feature = "compiler")]
+mod test_lang;
+mod test_memory_map;
+mod test_patch;
+mod test_rng;
+mod test_rom_image;
+mod test_rtc;
+mod test_save_ram;
+mod test_serial;
+mod test_watchdog;
 
 impl Console {
     pub fn builder() -> ConsoleBuilder {
@@ -0,0 +1,193 @@
+use crate::compiler::assembler::Assembler;
+use crate::compiler::Compiler;
+use crate::console::cartridge::Cartridge;
+use crate::console::components::cpu::registers::{R16, R16S, R8};
+use crate::console::types::byte::Byte;
+use crate::console::Console;
+use rstest::rstest;
+
+fn assemble(source: &str) -> Vec<u8> {
+    Assembler::new(source)
+        .assemble()
+        .unwrap()
+        .compile()
+        .unwrap()
+}
+
+#[rstest]
+#[case("NOP", vec![0x00])]
+#[case("HLT", vec![0x10])]
+#[case("ei\ndi", vec![0x01, 0x11])]
+#[case("ADD B", vec![0x09])]
+#[case("ADD HL", vec![0x06])]
+#[case("ADD [HL]", vec![0x0F])]
+#[case("LD A, B", vec![0x20])]
+#[case("LD A, HL", vec![0x26])]
+#[case("LD [HL], A", vec![0x51])]
+#[case("LD HL, BC", vec![0x5E])]
+#[case("LD A, 0x42", vec![0x68, 0x42])]
+#[case("LD B, -1", vec![0x69, 0xFF])]
+#[case("LD C, 'x'", vec![0x6A, b'x'])]
+#[case("LD HL, $1234", vec![0x66, 0x34, 0x12])]
+#[case("PUSH AF", vec![0x80])]
+#[case("POP HL", vec![0x87])]
+#[case("JP 0b101", vec![0x03, 0x05, 0x00])]
+#[case("JP nz, 0x10", vec![0x91, 0x10, 0x00])]
+fn test_assembler_instruction(#[case] source: &str, #[case] expected: Vec<u8>) {
+    let mut expected = expected;
+    expected.push(0x10);
+
+    assert_eq!(assemble(source), expected);
+}
+
+#[test]
+fn test_assembler_matches_builder() {
+    let source = "
+        ; Adds two numbers
+        main:   LD A, 12
+                LD B, 13   ; second operand
+                ADD B
+                INC BC
+                DEC [HL]
+                PUSH DE
+                POP DE
+                CALL add
+        add:    RET
+    ";
+    let builder = Compiler::new()
+        .label("main")
+        .load_r8i(R8::A, 12)
+        .load_r8i(R8::B, 13)
+        .add_r8(R8::B)
+        .increment_r16(R16::BC)
+        .decrement_r8(R8::HL)
+        .stack_push(R16S::DE)
+        .stack_pop(R16S::DE)
+        .call_label("add")
+        .label("add")
+        .ret()
+        .compile()
+        .unwrap();
+
+    assert_eq!(assemble(source), builder);
+}
+
+#[test]
+fn test_assembler_directives() {
+    let source = r#"
+        JP start
+        .org 0x0010
+        table: .dw start, 0xBEEF
+        .db 1, "hi\n", 'x'
+        start: HLT
+    "#;
+
+    let binary = assemble(source);
+
+    assert_eq!(binary[0..3], [0x03, 0x19, 0x00]);
+    assert_eq!(
+        binary[0x10..0x19],
+        [0x19, 0x00, 0xEF, 0xBE, 1, b'h', b'i', b'\n', b'x']
+    );
+    assert_eq!(binary[0x19], 0x10);
+}
+
+#[test]
+fn test_assembler_runs() {
+    let source = "
+        LD A, 1
+        JP skip
+        LD A, 10
+        skip: LD B, 2
+        ADD B
+    ";
+    let binary = assemble(source);
+
+    let mut console = Console::new();
+    console.load_cartridge(Cartridge::new(binary)).unwrap();
+    console.step_till_halt();
+
+    assert_eq!(
+        console.cpu.get_registers().get_r8(&mut console.bus, R8::A),
+        Byte::new(3)
+    );
+}
+
+#[test]
+fn test_assembler_source_locations() {
+    let (_, symbols) = Assembler::new("NOP\n  LD A, 1")
+        .with_path("main.asm")
+        .assemble()
+        .unwrap()
+        .compile_with_symbols()
+        .unwrap();
+
+    let source = symbols.source_at(0x0002).unwrap();
+    assert_eq!(source.file, "main.asm");
+    assert_eq!((source.line, source.column), (2, 3));
+}
+
+#[test]
+fn test_assembler_include() {
+    let directory = std::env::temp_dir().join("lmvc8_test_assembler_include");
+    std::fs::create_dir_all(&directory).unwrap();
+    let main_path = directory.join("main.asm");
+    std::fs::write(&main_path, ".include \"lib.asm\"\nCALL function").unwrap();
+    std::fs::write(directory.join("lib.asm"), "function: RET").unwrap();
+    std::fs::write(directory.join("loop.asm"), ".include \"loop.asm\"").unwrap();
+
+    let binary = Assembler::from_file(&main_path)
+        .unwrap()
+        .assemble()
+        .unwrap()
+        .compile()
+        .unwrap();
+    let error = Assembler::from_file(directory.join("loop.asm"))
+        .unwrap()
+        .assemble()
+        .unwrap_err();
+    std::fs::remove_dir_all(&directory).ok();
+
+    assert_eq!(binary, vec![0x12, 0x02, 0x00, 0x00, 0x10]);
+    assert!(error.message.contains("includes itself"));
+}
+
+#[rstest]
+#[case("FOO A", 1, 1, "unknown instruction 'FOO'")]
+#[case("  LD A, 256", 1, 9, "256 doesn't fit into a byte")]
+#[case("NOP\nLD A, A", 2, 1, "Can't load A from A")]
+#[case("ADD X", 1, 5, "expected a register, found 'X'")]
+#[case("LD A,", 1, 6, "missing operand")]
+#[case("LD AF, 1", 1, 4, "AF can only be pushed and popped")]
+#[case("PUSH SP", 1, 6, "expected AF, BC, DE or HL")]
+#[case("JP P, 0", 1, 4, "expected Z, NZ, C or NC")]
+#[case("RET 1", 1, 1, "expected 0 operand(s), found 1")]
+#[case(".org", 1, 1, "expected 1 operand(s), found 0")]
+#[case(".fill 3", 1, 1, "unknown directive '.fill'")]
+#[case(".db 0x-5", 1, 5, "invalid number '0x-5'")]
+#[case(".db $+5", 1, 5, "invalid number '$+5'")]
+#[case(".db --5", 1, 5, "invalid number '--5'")]
+#[case(
+    ".dw -0x-8000000000000000",
+    1,
+    5,
+    "invalid number '-0x-8000000000000000'"
+)]
+#[case(".db \"open", 1, 5, "invalid string \"open")]
+#[case("JP nowhere", 1, 4, "undefined label 'nowhere'")]
+#[case("x: NOP\n x: NOP", 2, 2, "label 'x' is already defined at <source>:1")]
+#[case("BC: NOP", 1, 1, "'BC' is a register and can't be a label")]
+fn test_assembler_errors(
+    #[case] source: &str,
+    #[case] line: usize,
+    #[case] column: usize,
+    #[case] message: &str,
+) {
+    let error = Assembler::new(source).assemble().unwrap_err();
+
+    assert_eq!(
+        (error.line, error.column, error.message.as_str()),
+        (line, column, message)
+    );
+    assert_eq!(error.file, "<source>");
+}
@@ -0,0 +1,121 @@
+use crate::console::cartridge::Cartridge;
+use crate::console::components::boot_rom::SPLASH;
+use crate::console::components::bus::Bus;
+use crate::console::components::cpu::registers::{R16, R8};
+use crate::console::config::{ConsoleConfig, Peripherals};
+use crate::console::types::byte::Byte;
+use crate::console::types::word::Word;
+use crate::console::Console;
+use rstest::rstest;
+
+const OP_HALT: u8 = 0x10;
+const OP_LD_A_N: u8 = 0x68;
+
+/// Halts at 0x0000, loads 0x42 into A and halts at 0x0100
+fn cartridge() -> Cartridge {
+    let mut binary = vec![0; 0x104];
+    binary[0x0000] = OP_HALT;
+    binary[0x0100..0x0103].copy_from_slice(&[OP_LD_A_N, 0x42, OP_HALT]);
+    Cartridge::new(binary)
+}
+
+fn sp(console: &Console) -> u16 {
+    console.cpu.get_registers().get_r16(R16::SP).into()
+}
+
+#[test]
+fn test_boot_default_stack_pointer() {
+    let console = Console::new();
+
+    assert_eq!(u16::from(console.cpu.get_pc()), Bus::ROM_START);
+    assert_eq!(sp(&console), Bus::DEFAULT_SP);
+}
+
+#[test]
+fn test_boot_cartridge_reset_vector() {
+    let mut console = Console::new();
+    console
+        .load_cartridge(
+            cartridge()
+                .with_entry_point(0x0100)
+                .with_stack_pointer(0x9000),
+        )
+        .unwrap();
+
+    assert_eq!(u16::from(console.cpu.get_pc()), 0x0100);
+    assert_eq!(sp(&console), 0x9000);
+
+    console.step_till_halt();
+    console.reset();
+
+    assert_eq!(u16::from(console.cpu.get_pc()), 0x0100);
+    assert_eq!(
+        console.cpu.get_registers().get_r16(R16::SP),
+        Word::new(0x9000)
+    );
+}
+
+#[test]
+fn test_boot_cartridge_without_vector_uses_config() {
+    let config = ConsoleConfig::default().with_boot(0x0100, 0x9000);
+    let mut console = Console::with_config(config).unwrap();
+    console
+        .load_cartridge(cartridge().with_entry_point(0x0000))
+        .unwrap();
+
+    assert_eq!(u16::from(console.cpu.get_pc()), 0x0000);
+    assert_eq!(sp(&console), 0x9000);
+}
+
+#[rstest]
+#[case(Peripherals::all(), SPLASH)]
+#[case(Peripherals::empty(), b"")]
+fn test_boot_rom(#[case] peripherals: Peripherals, #[case] splash: &[u8]) {
+    let config = ConsoleConfig::default()
+        .with_peripherals(peripherals)
+        .with_boot(0x0100, 0x9000)
+        .with_boot_rom(true);
+    let mut console = Console::with_config(config).unwrap();
+    let binary = cartridge().binary;
+    console.bus.rom.data[..binary.len()].copy_from_slice(&binary);
+    assert_eq!(u16::from(console.cpu.get_pc()), Bus::BOOT_ROM_START);
+
+    console.step_till_halt();
+
+    assert_eq!(u16::from(console.cpu.get_pc()), 0x0103);
+    assert_eq!(sp(&console), 0x9000);
+    assert_eq!(
+        console.cpu.get_registers().get_r8(&mut console.bus, R8::A),
+        Byte::new(0x42)
+    );
+    assert_eq!(console.debug_output(), splash);
+}
+
+#[test]
+fn test_boot_rom_mapped_only_if_enabled() {
+    let has_boot_rom = |console: &Console| {
+        console
+            .bus
+            .layout()
+            .iter()
+            .any(|region| region.range() == Bus::RANGE_BOOT_ROM)
+    };
+
+    assert!(!has_boot_rom(&Console::new()));
+    let config = ConsoleConfig::default().with_boot_rom(true);
+    assert!(has_boot_rom(&Console::with_config(config).unwrap()));
+}
+
+#[test]
+fn test_boot_rom_jumps_to_cartridge_entry_point() {
+    let config = ConsoleConfig::default().with_boot_rom(true);
+    let mut console = Console::with_config(config).unwrap();
+    console
+        .load_cartridge(cartridge().with_entry_point(0x0100))
+        .unwrap();
+
+    console.step_till_halt();
+
+    assert_eq!(u16::from(console.cpu.get_pc()), 0x0103);
+    assert_eq!(sp(&console), Bus::DEFAULT_SP);
+}
@@ -0,0 +1,92 @@
+use crate::console::cartridge::lmc::{crc32, LmcHeader};
+use crate::console::cartridge::Cartridge;
+use crate::console::config::HardwareProfile;
+use crate::error::LMVC8Error;
+use rstest::rstest;
+use std::mem::discriminant;
+
+fn cartridge() -> Cartridge {
+    Cartridge::new(vec![0x68, 0x42, 0x10])
+        .with_title("Demo")
+        .with_author("LMVC8")
+        .with_profile(HardwareProfile::Lite)
+        .with_save_ram(true)
+        .with_entry_point(0x0100)
+        .with_stack_pointer(0x9000)
+}
+
+#[test]
+fn test_crc32() {
+    assert_eq!(crc32(b""), 0);
+    assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+}
+
+#[test]
+fn test_cartridge_lmc_round_trip() {
+    let data = cartridge().to_lmc().unwrap();
+    assert_eq!(data.len(), LmcHeader::SIZE + 3);
+    assert_eq!(&data[..4], b"LMC8");
+
+    let loaded = Cartridge::from_lmc(&data).unwrap();
+
+    assert_eq!(loaded.binary, vec![0x68, 0x42, 0x10]);
+    assert_eq!(loaded.title, "Demo");
+    assert_eq!(loaded.author, "LMVC8");
+    assert_eq!(loaded.profile, HardwareProfile::Lite);
+    assert!(loaded.save_ram);
+    assert_eq!(loaded.entry_point, Some(0x0100));
+    assert_eq!(loaded.stack_pointer, Some(0x9000));
+}
+
+#[test]
+fn test_cartridge_lmc_defaults() {
+    let loaded = Cartridge::from_lmc(&Cartridge::new(vec![]).to_lmc().unwrap()).unwrap();
+
+    assert_eq!(loaded.entry_point, Some(0x0000));
+    assert_eq!(loaded.stack_pointer, None);
+    assert!(!loaded.save_ram);
+}
+
+#[rstest]
+#[case::magic(0, 0x00, LMVC8Error::InvalidCartridgeMagic)]
+#[case::version(4, 0x03, LMVC8Error::UnsupportedCartridgeVersion(3))]
+#[case::profile(69, 0x07, LMVC8Error::UnknownHardwareProfile(7))]
+#[case::checksum(LmcHeader::SIZE, 0x00, LMVC8Error::CartridgeChecksumMismatch {
+    expected: crc32(&[0x68, 0x42, 0x10]),
+    actual: crc32(&[0x00, 0x42, 0x10]),
+})]
+fn test_cartridge_lmc_corrupted(
+    #[case] offset: usize,
+    #[case] value: u8,
+    #[case] expected: LMVC8Error,
+) {
+    let mut data = cartridge().to_lmc().unwrap();
+    data[offset] = value;
+
+    let err = Cartridge::from_lmc(&data).unwrap_err();
+
+    assert_eq!(discriminant(&err), discriminant(&expected));
+    assert_eq!(err.to_string(), expected.to_string());
+}
+
+#[rstest]
+#[case(10)]
+#[case(LmcHeader::SIZE + 1)]
+fn test_cartridge_lmc_truncated(#[case] len: usize) {
+    let data = cartridge().to_lmc().unwrap();
+
+    assert!(matches!(
+        Cartridge::from_lmc(&data[..len]),
+        Err(LMVC8Error::TruncatedCartridge)
+    ));
+}
+
+#[test]
+fn test_cartridge_lmc_title_too_long() {
+    let cartridge = cartridge().with_title("A".repeat(33));
+
+    assert!(matches!(
+        cartridge.to_lmc(),
+        Err(LMVC8Error::InvalidCartridgeHeader(_))
+    ));
+}
@@ -0,0 +1,127 @@
+use crate::compiler::error::CompileErrorKind;
+use crate::compiler::Compiler;
+use crate::console::cartridge::Cartridge;
+use crate::console::components::cpu::instructions::Condition;
+use crate::console::components::cpu::registers::R8;
+use crate::console::Console;
+use rstest::rstest;
+
+fn run(compiler: Compiler) -> Console {
+    let mut console = Console::new();
+    console
+        .load_cartridge(Cartridge::new(compiler.compile().unwrap()))
+        .unwrap();
+    console.step_till_halt();
+    console
+}
+
+fn r8(console: &mut Console, r8: R8) -> u8 {
+    console
+        .cpu
+        .get_registers()
+        .get_r8(&mut console.bus, r8)
+        .value()
+}
+
+#[rstest]
+#[case::taken(1, 0x11)]
+#[case::not_taken(2, 0x22)]
+fn test_compiler_if_flag(#[case] counter: u8, #[case] expected: u8) {
+    let compiler = Compiler::new()
+        .load_r8i(R8::B, counter)
+        .decrement_r8(R8::B)
+        .if_flag(
+            Condition::Zero,
+            |c| c.load_r8i(R8::A, 0x11),
+            |c| c.load_r8i(R8::A, 0x22),
+        )
+        .load_r8i(R8::C, 0x33);
+
+    let mut console = run(compiler);
+
+    assert_eq!(r8(&mut console, R8::A), expected);
+    assert_eq!(r8(&mut console, R8::C), 0x33);
+}
+
+#[test]
+fn test_compiler_for_counter() {
+    let compiler = Compiler::new()
+        .load_r8i(R8::A, 0)
+        .load_r8i(R8::C, 3)
+        .for_counter(R8::B, 5, |c| c.add_r8(R8::C));
+
+    let mut console = run(compiler);
+
+    assert_eq!(r8(&mut console, R8::A), 15);
+    assert_eq!(r8(&mut console, R8::B), 0);
+}
+
+#[test]
+fn test_compiler_for_counter_zero_times() {
+    let binary = Compiler::new()
+        .for_counter(R8::B, 0, |c| c.add_r8(R8::C))
+        .compile()
+        .unwrap();
+
+    assert_eq!(binary, vec![0x10]);
+}
+
+#[test]
+fn test_compiler_loop_while() {
+    // Adds C to A until the addition carries
+    let compiler = Compiler::new()
+        .load_r8i(R8::A, 0xF0)
+        .load_r8i(R8::C, 0x04)
+        .load_r8i(R8::D, 0)
+        .loop_while(Condition::NotCarry, |c| c.increment_r8(R8::D).add_r8(R8::C));
+
+    let mut console = run(compiler);
+
+    assert_eq!(r8(&mut console, R8::D), 4);
+    assert_eq!(r8(&mut console, R8::A), 0x00);
+}
+
+#[test]
+fn test_compiler_nested_break() {
+    // The inner break only leaves the inner loop
+    let compiler = Compiler::new()
+        .load_r8i(R8::A, 0)
+        .load_r8i(R8::D, 1)
+        .for_counter(R8::B, 3, |c| {
+            c.for_counter(R8::C, 4, |c| c.add_r8(R8::D).break_loop())
+        });
+
+    let mut console = run(compiler);
+
+    assert_eq!(r8(&mut console, R8::A), 3);
+}
+
+#[test]
+fn test_compiler_continue_in_for_counter() {
+    // Skips the addition in every iteration
+    let compiler = Compiler::new()
+        .load_r8i(R8::A, 0)
+        .load_r8i(R8::D, 1)
+        .for_counter(R8::B, 3, |c| c.continue_loop().add_r8(R8::D));
+
+    let mut console = run(compiler);
+
+    assert_eq!(r8(&mut console, R8::A), 0);
+    assert_eq!(r8(&mut console, R8::B), 0);
+}
+
+#[rstest]
+#[case::break_loop(Compiler::break_loop, "break")]
+#[case::continue_loop(Compiler::continue_loop, "continue")]
+fn test_compiler_outside_loop(
+    #[case] f: fn(Compiler) -> Compiler,
+    #[case] statement: &'static str,
+) {
+    let error = f(Compiler::new().no_op()).compile().unwrap_err();
+
+    assert_eq!(error.errors[0].node, 1);
+    assert_eq!(
+        error.kinds().collect::<Vec<_>>(),
+        vec![&CompileErrorKind::OutsideLoop(statement)]
+    );
+}
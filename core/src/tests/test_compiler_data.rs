@@ -0,0 +1,118 @@
+use crate::compiler::char_map::CharMap;
+use crate::compiler::error::CompileErrorKind;
+use crate::compiler::Compiler;
+use crate::console::components::cpu::registers::R16;
+
+const HLT: u8 = 0x10;
+
+#[test]
+fn test_compiler_data_strings() {
+    let (binary, symbols) = Compiler::new()
+        .string_prefixed("name", "Hi")
+        .string_null_terminated("greeting", "ok")
+        .compile_with_symbols()
+        .unwrap();
+
+    assert_eq!(binary, vec![2, b'H', b'i', b'o', b'k', 0x00, HLT]);
+    assert_eq!(symbols.address_of("greeting"), Some(0x0003));
+    assert_eq!(symbols.data[0].range(), 0x0000..=0x0002);
+    assert_eq!(symbols.data[1].range(), 0x0003..=0x0005);
+}
+
+#[test]
+fn test_compiler_data_char_map() {
+    let char_map = CharMap::empty().with_sequence("ABC", 0x10).with(' ', 0x00);
+
+    let binary = Compiler::new()
+        .with_char_map(char_map)
+        .string_null_terminated("text", "CAB A")
+        .compile()
+        .unwrap();
+
+    assert_eq!(binary, vec![0x12, 0x10, 0x11, 0x00, 0x10, 0x00, HLT]);
+}
+
+#[test]
+fn test_compiler_data_jump_table() {
+    let (binary, symbols) = Compiler::new()
+        .load_r16i_label(R16::HL, "table")
+        .jump_table("table", ["first", "second"])
+        .label("first")
+        .no_op()
+        .label("second")
+        .compile_with_symbols()
+        .unwrap();
+
+    assert_eq!(&binary[3..7], &[0x07, 0x00, 0x08, 0x00]);
+    assert_eq!(symbols.data[0].range(), 0x0003..=0x0006);
+}
+
+#[test]
+fn test_compiler_data_include_bytes() {
+    let path = std::env::temp_dir().join("lmvc8_test_compiler_data.bin");
+    std::fs::write(&path, [0xDE, 0xAD]).unwrap();
+
+    let binary = Compiler::new()
+        .include_bytes("blob", &path)
+        .compile()
+        .unwrap();
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(binary, vec![0xDE, 0xAD, HLT]);
+}
+
+#[test]
+fn test_compiler_data_align() {
+    let (binary, symbols) = Compiler::new()
+        .with_fill_byte(0xFF)
+        .push_byte(0x01)
+        .align(4)
+        .string_prefixed("aligned", "")
+        .align(4)
+        .compile_with_symbols()
+        .unwrap();
+
+    assert_eq!(
+        binary,
+        vec![0x01, 0xFF, 0xFF, 0xFF, 0x00, 0xFF, 0xFF, 0xFF, HLT]
+    );
+    assert_eq!(symbols.address_of("aligned"), Some(0x0004));
+    assert!((0x0001..=0x0007).all(|address| symbols.is_data(address)));
+}
+
+#[test]
+fn test_compiler_data_errors() {
+    let error = Compiler::new()
+        .string_prefixed("long", &"x".repeat(256))
+        .string_null_terminated("unmapped", "é")
+        .include_bytes("missing", "lmvc8_missing_include.bin")
+        .align(0)
+        .compile()
+        .unwrap_err();
+
+    let kinds = error.kinds().collect::<Vec<_>>();
+    assert_eq!(kinds[0], &CompileErrorKind::StringTooLong(256));
+    assert_eq!(kinds[1], &CompileErrorKind::UnmappedCharacter('é'));
+    assert!(matches!(kinds[2], CompileErrorKind::IncludeFailed { .. }));
+    assert!(matches!(kinds[3], CompileErrorKind::InvalidOperand(_)));
+}
+
+#[cfg(feature = "disassembler")]
+#[test]
+fn test_compiler_data_disassembly() {
+    use crate::disassembler::Disassembler;
+
+    // 'h' is LD A, n and would swallow the next byte if decoded
+    let (binary, symbols) = Compiler::new()
+        .string_null_terminated("text", "h")
+        .compile_with_symbols()
+        .unwrap();
+
+    let disassembled = Disassembler::new(&binary)
+        .with_symbols(Some(&symbols))
+        .disassemble();
+
+    assert!(disassembled.nodes()[0].is_byte());
+    assert!(disassembled.nodes()[1].is_byte());
+    assert!(disassembled.nodes()[2].is_instruction());
+}
@@ -0,0 +1,92 @@
+use crate::compiler::error::CompileErrorKind;
+use crate::compiler::Compiler;
+use crate::console::components::cpu::registers::R8;
+
+#[test]
+fn test_compiler_error_invalid_instruction() {
+    let line = line!() + 1;
+    let result = Compiler::new().no_op().load_r8(R8::A, R8::A).compile();
+
+    let error = result.unwrap_err();
+    assert_eq!(error.errors.len(), 1);
+    assert_eq!(error.errors[0].node, 1);
+    assert!(matches!(
+        error.errors[0].kind,
+        CompileErrorKind::InvalidInstruction(_)
+    ));
+    assert!(error.errors[0]
+        .location
+        .file()
+        .ends_with("test_compiler_errors.rs"));
+    assert_eq!(error.errors[0].location.line(), line);
+}
+
+#[test]
+fn test_compiler_error_call_site_in_closure() {
+    let result = Compiler::new()
+        .position_context(0x0010, |c| c.no_op().label("twice"))
+        .label("twice")
+        .compile();
+
+    let error = result.unwrap_err();
+    assert_eq!(error.errors[0].node, 2);
+    assert!(error.errors[0]
+        .location
+        .file()
+        .ends_with("test_compiler_errors.rs"));
+}
+
+#[test]
+fn test_compiler_error_collects_all() {
+    let result = Compiler::new()
+        .call_label("missing")
+        .load_r8(R8::B, R8::B)
+        .jump_label("also_missing")
+        .compile();
+
+    assert_eq!(
+        result.unwrap_err().kinds().collect::<Vec<_>>(),
+        vec![
+            &CompileErrorKind::UndefinedLabel("missing".to_string()),
+            &CompileErrorKind::InvalidInstruction("Can't load B from B".to_string()),
+            &CompileErrorKind::UndefinedLabel("also_missing".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_compiler_error_position_overflow() {
+    let result = Compiler::new()
+        .set_push_position(0xFFFF)
+        .push_word(0x1234)
+        .compile();
+
+    assert!(result
+        .unwrap_err()
+        .kinds()
+        .any(|kind| *kind == CompileErrorKind::PositionOverflow(0xFFFF)));
+}
+
+#[test]
+fn test_compiler_error_display() {
+    let error = Compiler::new().jump_label("missing").compile().unwrap_err();
+
+    let message = error.to_string();
+    assert!(message.starts_with("Compilation failed with 1 error(s)"));
+    assert!(message.contains("node 1: Label 'missing' is referenced but never defined"));
+}
+
+#[test]
+fn test_compile_cartridge() {
+    let cartridge = Compiler::new()
+        .label("main")
+        .no_op()
+        .compile_cartridge()
+        .unwrap();
+
+    assert_eq!(cartridge.binary, vec![0x00, 0x10]);
+    assert_eq!(cartridge.symbols.unwrap().label_at(0x0000), Some("main"));
+
+    let cartridge = Compiler::new().no_op().compile_cartridge().unwrap();
+    assert!(cartridge.symbols.is_none());
+}
@@ -0,0 +1,133 @@
+use crate::compiler::error::CompileErrorKind;
+use crate::compiler::Compiler;
+use crate::console::cartridge::Cartridge;
+use crate::console::components::bus::Bus;
+use crate::console::components::cpu::instructions::Condition;
+use crate::console::components::cpu::registers::{R16, R16S, R8};
+use crate::console::Console;
+
+fn run(compiler: Compiler) -> Console {
+    let mut console = Console::new();
+    console
+        .load_cartridge(Cartridge::new(compiler.compile().unwrap()))
+        .unwrap();
+    console.step_till_halt();
+    console
+}
+
+fn r8(console: &mut Console, r8: R8) -> u8 {
+    console
+        .cpu
+        .get_registers()
+        .get_r8(&mut console.bus, r8)
+        .value()
+}
+
+#[test]
+fn test_compiler_function_call() {
+    let compiler = Compiler::new()
+        .function("add_b", &[], |c| c.add_r8(R8::B))
+        .load_r8i(R8::A, 1)
+        .load_r8i(R8::B, 2)
+        .call_label("add_b")
+        .call_label("add_b");
+
+    let mut console = run(compiler);
+
+    assert_eq!(r8(&mut console, R8::A), 5);
+    assert_eq!(
+        console.cpu.get_registers().get_r16(R16::SP).value(),
+        Bus::DEFAULT_SP
+    );
+}
+
+#[test]
+fn test_compiler_function_forward_call() {
+    let compiler = Compiler::new()
+        .load_r8i(R8::A, 1)
+        .call_label("double")
+        .halt()
+        .function("double", &[], |c| c.add_r8(R8::A));
+
+    let mut console = run(compiler);
+
+    assert_eq!(r8(&mut console, R8::A), 2);
+}
+
+#[test]
+fn test_compiler_function_restores_clobbers() {
+    let compiler = Compiler::new()
+        .function("clobber", &[R16S::BC, R16S::DE], |c| {
+            c.load_r8i(R8::B, 0xFF)
+                .load_r8i(R8::C, 0xFF)
+                .load_r8i(R8::D, 0xFF)
+                .load_r8i(R8::H, 0x77)
+        })
+        .load_r16i(R16::BC, 0x1234)
+        .load_r16i(R16::DE, 0x5678)
+        .call_label("clobber");
+
+    let mut console = run(compiler);
+
+    assert_eq!(console.cpu.get_registers().get_r16(R16::BC).value(), 0x1234);
+    assert_eq!(console.cpu.get_registers().get_r16(R16::DE).value(), 0x5678);
+    assert_eq!(r8(&mut console, R8::H), 0x77);
+}
+
+#[test]
+fn test_compiler_function_return() {
+    // Returns early when B is zero, otherwise sets A
+    let compiler = Compiler::new()
+        .function("maybe_set", &[R16S::BC], |c| {
+            c.decrement_r8(R8::B)
+                .if_flag(Condition::Zero, |c| c.function_return(), |c| c)
+                .load_r8i(R8::A, 0x42)
+        })
+        .load_r8i(R8::A, 0)
+        .load_r8i(R8::B, 1)
+        .call_label("maybe_set")
+        .load_r8(R8::C, R8::A)
+        .load_r8i(R8::B, 2)
+        .call_label("maybe_set");
+
+    let mut console = run(compiler);
+
+    assert_eq!(r8(&mut console, R8::C), 0);
+    assert_eq!(r8(&mut console, R8::A), 0x42);
+    assert_eq!(r8(&mut console, R8::B), 2);
+}
+
+#[test]
+fn test_compiler_function_symbols() {
+    let (_, symbols) = Compiler::new()
+        .function("f", &[R16S::HL], |c| c.no_op())
+        .compile_with_symbols()
+        .unwrap();
+
+    // JP f.end, then PUSH HL, NOP, POP HL, RET
+    assert_eq!(symbols.address_of("f"), Some(0x0003));
+    assert_eq!(symbols.functions[0].range(), 0x0003..=0x0006);
+}
+
+#[test]
+fn test_compiler_function_return_outside_function() {
+    let error = Compiler::new().function_return().compile().unwrap_err();
+
+    assert_eq!(
+        error.kinds().collect::<Vec<_>>(),
+        vec![&CompileErrorKind::OutsideFunction]
+    );
+}
+
+#[test]
+fn test_compiler_function_break_outside_loop() {
+    let error = Compiler::new()
+        .for_counter(R8::B, 2, |c| c.function("f", &[], |c| c.break_loop()))
+        .compile()
+        .unwrap_err();
+
+    assert_eq!(
+        error.kinds().collect::<Vec<_>>(),
+        vec![&CompileErrorKind::OutsideLoop("break")]
+    );
+}
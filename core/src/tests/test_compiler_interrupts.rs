@@ -0,0 +1,163 @@
+use crate::compiler::error::CompileErrorKind;
+use crate::compiler::Compiler;
+use crate::console::cartridge::Cartridge;
+use crate::console::components::bus::Bus;
+use crate::console::components::cpu::interrupts::{InterruptFlags, IV_END, IV_INPUT, IV_TIMER};
+use crate::console::components::cpu::registers::{R16, R16S, R8};
+use crate::console::Console;
+
+const JP: u8 = 0x03;
+const PUSH_AF: u8 = 0x80;
+
+fn load(compiler: Compiler) -> Console {
+    let mut console = Console::new();
+    console
+        .load_cartridge(Cartridge::new(compiler.compile().unwrap()))
+        .unwrap();
+    console
+}
+
+#[test]
+fn test_compiler_interrupt_handler() {
+    let compiler = Compiler::new()
+        .on_interrupt(InterruptFlags::TIMER, |c| {
+            c.load_r16i(R16::HL, Bus::RAM_START).load_r8i(R8::HL, 0x42)
+        })
+        .load_r8i(R8::B, 0x07)
+        .load_r16i(R16::HL, 0x1234);
+    let mut console = load(compiler);
+
+    // The startup code, the jump past the vectors and the main program
+    for _ in 0..7 {
+        console.step();
+    }
+    console.bus.ia.insert(InterruptFlags::TIMER);
+    console.step_till_halt();
+
+    let registers = console.cpu.get_registers();
+    assert_eq!(console.bus.read(Bus::RAM_START.into()).value(), 0x42);
+    assert_eq!(registers.get_r16(R16::HL).value(), 0x1234);
+    assert_eq!(registers.get_r16(R16::SP).value(), Bus::DEFAULT_SP);
+    assert!(console.cpu.get_ime());
+}
+
+#[test]
+fn test_compiler_interrupt_pending_on_return() {
+    let compiler = Compiler::new()
+        .on_interrupt(InterruptFlags::TIMER, |c| {
+            c.load_r16i(R16::HL, Bus::RAM_START).increment_r8(R8::HL)
+        })
+        .for_counter(R8::B, 0x20, |c| c.no_op());
+    let mut console = load(compiler);
+
+    for _ in 0..7 {
+        console.step();
+    }
+    console.bus.ia.insert(InterruptFlags::TIMER);
+    // Raise the interrupt again while the handler runs, it is serviced after the handler returned
+    let mut raised = false;
+    let mut lowest_sp = Bus::DEFAULT_SP;
+    while !console.step().cpu_step_flags.is_halt() {
+        if !raised && !console.cpu.get_ime() {
+            console.bus.ia.insert(InterruptFlags::TIMER);
+            raised = true;
+        }
+        lowest_sp = lowest_sp.min(console.cpu.get_registers().get_r16(R16::SP).value());
+    }
+
+    // The return address and the saved registers of a single handler
+    assert_eq!(lowest_sp, Bus::DEFAULT_SP - 2 - 2 * R16S::ALL.len() as u16);
+    assert_eq!(console.bus.read(Bus::RAM_START.into()).value(), 2);
+    assert_eq!(
+        console.cpu.get_registers().get_r16(R16::SP).value(),
+        Bus::DEFAULT_SP
+    );
+}
+
+#[test]
+fn test_compiler_interrupt_layout() {
+    let (binary, symbols) = Compiler::new()
+        .on_interrupt(InterruptFlags::TIMER, |c| c.no_op())
+        .on_interrupt(InterruptFlags::INPUT, |c| c.no_op())
+        .no_op()
+        .compile_with_symbols()
+        .unwrap();
+    let mut console = Console::new();
+    console
+        .load_cartridge(Cartridge::new(binary.clone()))
+        .unwrap();
+    console.step_till_halt();
+
+    assert_eq!(binary[IV_TIMER as usize], PUSH_AF);
+    assert_eq!(binary[IV_INPUT as usize], PUSH_AF);
+    assert_eq!(symbols.address_of("interrupt_timer"), Some(IV_TIMER));
+    assert_eq!(symbols.address_of("interrupt_input"), Some(IV_INPUT));
+    assert_eq!(symbols.address_of("interrupt_vectors.end"), Some(IV_END));
+    assert_eq!(binary.len(), IV_END as usize + 2);
+    assert_eq!(
+        console.bus.read(Bus::INTERRUPT_ENABLE.into()).value(),
+        (InterruptFlags::TIMER | InterruptFlags::INPUT).bits()
+    );
+}
+
+#[test]
+fn test_compiler_interrupt_large_handler() {
+    let (binary, symbols) = Compiler::new()
+        .on_interrupt(InterruptFlags::TIMER, |c| {
+            (0..0x10).fold(c, |c, _| c.no_op())
+        })
+        .compile_with_symbols()
+        .unwrap();
+
+    let handler = symbols.address_of("interrupt_timer").unwrap();
+    assert!(handler >= IV_END);
+    assert_eq!(
+        binary[IV_TIMER as usize..IV_TIMER as usize + 3],
+        [JP, handler as u8, (handler >> 8) as u8]
+    );
+}
+
+#[test]
+fn test_compiler_interrupt_large_handler_built_once() {
+    let (binary, symbols) = Compiler::new()
+        .on_interrupt(InterruptFlags::TIMER, |mut c| {
+            let count = c.var_u8("count");
+            c.load_u8(R8::A, count)
+                .increment_r8(R8::A)
+                .store_u8(count, R8::A)
+        })
+        .compile_with_symbols()
+        .unwrap();
+
+    let handler = symbols.address_of("interrupt_timer").unwrap();
+    assert_eq!(binary[handler as usize], PUSH_AF);
+    assert_eq!(symbols.variables.len(), 1);
+}
+
+#[test]
+fn test_compiler_interrupt_break_outside_loop() {
+    let error = Compiler::new()
+        .for_counter(R8::B, 2, |c| {
+            c.on_interrupt(InterruptFlags::TIMER, |c| c.break_loop())
+        })
+        .compile()
+        .unwrap_err();
+
+    assert_eq!(
+        error.kinds().collect::<Vec<_>>(),
+        vec![&CompileErrorKind::OutsideLoop("break")]
+    );
+}
+
+#[test]
+fn test_compiler_interrupt_invalid() {
+    let error = Compiler::new()
+        .on_interrupt(InterruptFlags::TIMER | InterruptFlags::DMA, |c| c)
+        .compile()
+        .unwrap_err();
+
+    assert_eq!(
+        error.kinds().collect::<Vec<_>>(),
+        vec![&CompileErrorKind::InvalidInterrupt(0b0000_0101)]
+    );
+}
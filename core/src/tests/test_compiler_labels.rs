@@ -0,0 +1,96 @@
+use crate::compiler::error::CompileErrorKind;
+use crate::compiler::Compiler;
+use crate::console::cartridge::Cartridge;
+use crate::console::components::cpu::registers::{R16, R8};
+use crate::console::types::byte::Byte;
+use crate::console::Console;
+
+const OP_CALL: u8 = 0x02;
+const OP_JP: u8 = 0x03;
+const OP_HALT: u8 = 0x10;
+const OP_RET: u8 = 0x12;
+const OP_LD_HL_NN: u8 = 0x66;
+
+fn run(binary: Vec<u8>) -> Console {
+    let mut console = Console::new();
+    console.load_cartridge(Cartridge::new(binary)).unwrap();
+    console.step_till_halt();
+    console
+}
+
+#[test]
+fn test_compiler_forward_reference() {
+    let binary = Compiler::new()
+        .call_label("add")
+        .jump_label("end")
+        .label("add")
+        .ret()
+        .label("end")
+        .compile()
+        .unwrap();
+
+    assert_eq!(
+        binary,
+        vec![OP_CALL, 0x06, 0x00, OP_JP, 0x07, 0x00, OP_RET, OP_HALT]
+    );
+}
+
+#[test]
+fn test_compiler_backward_reference() {
+    let binary = Compiler::new()
+        .label("start")
+        .no_op()
+        .load_r16i_label(R16::HL, "start")
+        .push_label_word("start")
+        .compile()
+        .unwrap();
+
+    assert_eq!(
+        binary,
+        vec![0x00, OP_LD_HL_NN, 0x00, 0x00, 0x00, 0x00, OP_HALT]
+    );
+}
+
+#[test]
+fn test_compiler_label_jump_runs() {
+    let binary = Compiler::new()
+        .load_r8i(R8::A, 1)
+        .jump_label("add")
+        .load_r8i(R8::A, 10)
+        .label("add")
+        .load_r8i(R8::B, 2)
+        .add_r8(R8::B)
+        .compile()
+        .unwrap();
+
+    let mut console = run(binary);
+
+    assert_eq!(
+        console.cpu.get_registers().get_r8(&mut console.bus, R8::A),
+        Byte::new(3)
+    );
+}
+
+#[test]
+fn test_compiler_undefined_label() {
+    let result = Compiler::new().call_label("missing").compile();
+
+    assert_eq!(
+        result.unwrap_err().kinds().collect::<Vec<_>>(),
+        vec![&CompileErrorKind::UndefinedLabel("missing".to_string())]
+    );
+}
+
+#[test]
+fn test_compiler_duplicate_label() {
+    let result = Compiler::new()
+        .label("main")
+        .no_op()
+        .label("main")
+        .compile();
+
+    assert_eq!(
+        result.unwrap_err().kinds().collect::<Vec<_>>(),
+        vec![&CompileErrorKind::DuplicateLabel("main".to_string())]
+    );
+}
@@ -0,0 +1,78 @@
+use crate::compiler::error::CompileErrorKind;
+use crate::compiler::Compiler;
+use crate::console::components::cpu::interrupts::IV_TIMER;
+use crate::console::components::rom::ROM_SIZE;
+
+const OP_NOP: u8 = 0x00;
+const OP_EI: u8 = 0x01;
+const OP_HALT: u8 = 0x10;
+const OP_RET: u8 = 0x12;
+
+#[test]
+fn test_compiler_layout_position_context() {
+    let binary = Compiler::new()
+        .enable_interrupts()
+        .position_context(IV_TIMER, |c| c.ret())
+        .compile()
+        .unwrap();
+
+    assert_eq!(binary.len(), IV_TIMER as usize + 1);
+    assert_eq!(binary[0..2], [OP_EI, OP_HALT]);
+    assert!(binary[2..IV_TIMER as usize]
+        .iter()
+        .all(|&byte| byte == OP_NOP));
+    assert_eq!(binary[IV_TIMER as usize], OP_RET);
+}
+
+#[test]
+fn test_compiler_layout_fill_byte() {
+    let binary = Compiler::new()
+        .with_fill_byte(0xFF)
+        .set_push_position(0x0004)
+        .halt()
+        .set_push_position(0x0000)
+        .no_op()
+        .compile()
+        .unwrap();
+
+    assert_eq!(binary, vec![OP_NOP, OP_HALT, 0xFF, 0xFF, OP_HALT]);
+}
+
+#[test]
+fn test_compiler_layout_labels_at_positions() {
+    let binary = Compiler::new()
+        .jump_label("handler")
+        .position_context(0x0020, |c| c.label("handler").ret())
+        .compile()
+        .unwrap();
+
+    assert_eq!(binary[1..3], [0x20, 0x00]);
+    assert_eq!(binary[0x20], OP_RET);
+}
+
+#[test]
+fn test_compiler_layout_overlap() {
+    let result = Compiler::new()
+        .no_op()
+        .no_op()
+        .position_context(0x0001, |c| c.ret())
+        .compile();
+
+    assert_eq!(
+        result.unwrap_err().kinds().collect::<Vec<_>>(),
+        vec![&CompileErrorKind::Overlap(0x0001)]
+    );
+}
+
+#[test]
+fn test_compiler_layout_rom_size() {
+    let result = Compiler::new()
+        .set_push_position(ROM_SIZE as u16 - 1)
+        .no_op()
+        .compile();
+
+    assert_eq!(
+        result.unwrap_err().kinds().collect::<Vec<_>>(),
+        vec![&CompileErrorKind::ROMSizeExceeded(ROM_SIZE + 1)]
+    );
+}
@@ -0,0 +1,212 @@
+use crate::compiler::optimizer::{Op, Optimizer, PeepholeRule, Rewrite};
+use crate::compiler::Compiler;
+use crate::console::cartridge::Cartridge;
+use crate::console::components::bus::Bus;
+use crate::console::components::cpu::instructions::CPUInstruction;
+use crate::console::components::cpu::interrupts::{InterruptFlags, IV_END, IV_TIMER};
+use crate::console::components::cpu::registers::{R16, R16S, R8};
+use crate::console::Console;
+use rstest::rstest;
+
+fn run(binary: Vec<u8>) -> Console {
+    let mut console = Console::new();
+    console.load_cartridge(Cartridge::new(binary)).unwrap();
+    console.step_till_halt();
+    console
+}
+
+/// Everything the program can change, except PC
+fn state(console: &mut Console) -> (Vec<u8>, u16, u8, u8) {
+    let registers = console.cpu.get_registers();
+    let r8 = R8::ALL[..7]
+        .iter()
+        .map(|r8| registers.get_r8(&mut console.bus, *r8).value())
+        .collect();
+    let ram = console.bus.read(Bus::RAM_START.into()).value();
+    (
+        r8,
+        registers.get_r16(R16::SP).value(),
+        console.cpu.get_alu().get_flags().bits(),
+        ram,
+    )
+}
+
+fn program() -> Compiler {
+    Compiler::new()
+        .load_r8i(R8::A, 5)
+        .load_r8i(R8::B, 3)
+        .no_op()
+        .stack_push(R16S::BC)
+        .stack_pop(R16S::BC)
+        .load_r8(R8::C, R8::A)
+        .load_r8(R8::A, R8::C)
+        .call_label("add_b")
+        .load_r16i(R16::HL, Bus::RAM_START)
+        .load_r8(R8::HL, R8::A)
+        .halt()
+        .label("add_b")
+        .add_r8(R8::B)
+        .call_label("increment")
+        .ret()
+        .label("increment")
+        .increment_r8(R8::A)
+        .ret()
+}
+
+#[test]
+fn test_compiler_optimizer_same_behavior() {
+    let (optimized, report) = program().optimize(&Optimizer::default());
+    let original = program().compile().unwrap();
+    let optimized = optimized.compile().unwrap();
+
+    assert_eq!(report.bytes_saved, 5);
+    assert!(!report.padded);
+    assert_eq!(original.len() - optimized.len(), 5);
+    assert_eq!(
+        report.rewrites.into_iter().collect::<Vec<_>>(),
+        vec![
+            ("push-pop", 1),
+            ("redundant-loads", 1),
+            ("remove-nops", 1),
+            ("tail-call", 1)
+        ]
+    );
+    let mut original = run(original);
+    let mut optimized = run(optimized);
+    assert_eq!(state(&mut optimized), state(&mut original));
+    assert_eq!(state(&mut optimized).3, 9);
+}
+
+#[test]
+fn test_compiler_optimizer_keeps_labels_and_positions() {
+    let build = || {
+        Compiler::new()
+            .on_interrupt(InterruptFlags::TIMER, |c| {
+                c.increment_r8(R8::B)
+                    .stack_push(R16S::AF)
+                    .stack_pop(R16S::AF)
+            })
+            .no_op()
+            .label("main")
+            .stack_push(R16S::DE)
+            .stack_pop(R16S::DE)
+            .jump_label("end")
+            .no_op()
+            .label("end")
+    };
+
+    let (optimized, report) = build().optimize(&Optimizer::default());
+    let (binary, symbols) = optimized.compile_with_symbols().unwrap();
+    let (_, original_symbols) = build().compile_with_symbols().unwrap();
+
+    assert_eq!(report.bytes_saved, 6);
+    assert_eq!(symbols.address_of("interrupt_timer"), Some(IV_TIMER));
+    assert_eq!(symbols.address_of("interrupt_vectors.end"), Some(IV_END));
+    assert_eq!(symbols.address_of("main"), Some(IV_END));
+    assert_eq!(symbols.address_of("end"), Some(IV_END + 3));
+    assert_eq!(original_symbols.address_of("end"), Some(IV_END + 7));
+    // The jump was reached through the label, not a fixed address
+    assert_eq!(
+        binary[IV_END as usize..IV_END as usize + 3],
+        [0x03, 0xF3, 0x00]
+    );
+    assert_eq!(symbols.functions[0].range(), IV_TIMER..=IV_TIMER + 9);
+}
+
+#[rstest]
+#[case(0xFF)]
+#[case(0x10)]
+fn test_compiler_optimizer_numeric_jump(#[case] fill_byte: u8) {
+    let build = || {
+        Compiler::new()
+            .with_fill_byte(fill_byte)
+            .no_op()
+            .jump(0x0006)
+            .load_r8i(R8::A, 1)
+            .load_r8i(R8::B, 2)
+    };
+
+    let (optimized, report) = build().optimize(&Optimizer::default());
+    let optimized = optimized.compile().unwrap();
+
+    assert!(report.padded);
+    assert_eq!(report.bytes_saved, 1);
+    assert_eq!(
+        optimized,
+        vec![0x00, 0x03, 0x06, 0x00, 0x68, 0x01, 0x69, 0x02, 0x10]
+    );
+    let mut optimized = run(optimized);
+    assert_eq!(
+        state(&mut optimized),
+        state(&mut run(build().compile().unwrap()))
+    );
+    assert_eq!(state(&mut optimized).0[..2], [0, 2]);
+}
+
+#[test]
+fn test_compiler_optimizer_keeps_alignment() {
+    let build = || {
+        Compiler::new()
+            .with_fill_byte(0xFF)
+            .load_r8i(R8::A, 1)
+            .no_op()
+            .align(8)
+            .string_null_terminated("msg", "h")
+    };
+
+    let (optimized, report) = build().optimize(&Optimizer::default());
+    let (binary, symbols) = optimized.compile_with_symbols().unwrap();
+
+    // The string isn't mistaken for a numeric address
+    assert!(!report.padded);
+    assert_eq!(report.bytes_saved, 1);
+    assert_eq!(symbols.address_of("msg"), Some(0x0008));
+    assert_eq!(binary[2..8], [0xFF; 6]);
+    assert_eq!(binary[8..10], [b'h', 0x00]);
+    assert_eq!(symbols.data[0].range(), 0x0002..=0x0007);
+}
+
+/// Drops an immediate load that the next one overwrites
+struct DeadImmediateLoad;
+
+impl PeepholeRule for DeadImmediateLoad {
+    fn name(&self) -> &'static str {
+        "dead-immediate-load"
+    }
+
+    fn rewrite(&self, window: &[Op]) -> Option<Rewrite> {
+        match (window.first()?.instruction, window.get(1)?.instruction) {
+            (CPUInstruction::LoadR8i(first), CPUInstruction::LoadR8i(second))
+                if first == second && first != R8::HL =>
+            {
+                Some(Rewrite {
+                    consumed: 2,
+                    replacement: vec![window[1].clone()],
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+#[test]
+fn test_compiler_optimizer_custom_rule() {
+    let build = || {
+        Compiler::new()
+            .load_r8i(R8::B, 1)
+            .load_r8i(R8::B, 2)
+            .no_op()
+    };
+    let optimizer = Optimizer::empty().with_rule(DeadImmediateLoad);
+
+    let (optimized, report) = build().optimize(&optimizer);
+    let optimized = optimized.compile().unwrap();
+
+    assert_eq!(report.bytes_saved, 2);
+    assert_eq!(report.rewrites["dead-immediate-load"], 1);
+    assert_eq!(optimized, vec![0x69, 0x02, 0x00, 0x10]);
+    assert_eq!(
+        state(&mut run(optimized)),
+        state(&mut run(build().compile().unwrap()))
+    );
+}
@@ -0,0 +1,122 @@
+use crate::compiler::error::CompileErrorKind;
+use crate::compiler::Compiler;
+use crate::console::cartridge::Cartridge;
+use crate::console::components::bus::Bus;
+use crate::console::components::cpu::registers::{R16, R8};
+use crate::console::config::HardwareProfile;
+use crate::console::Console;
+
+fn run(compiler: Compiler) -> Console {
+    let mut console = Console::new();
+    console
+        .load_cartridge(Cartridge::new(compiler.compile().unwrap()))
+        .unwrap();
+    console.step_till_halt();
+    console
+}
+
+#[test]
+fn test_compiler_variables_allocation() {
+    let mut compiler = Compiler::new();
+    let flag = compiler.var_u8("flag");
+    let score = compiler.var_u16("score");
+    let buffer = compiler.array("buffer", 4);
+
+    assert_eq!(flag.address, Bus::RAM_START);
+    assert_eq!(score.address, Bus::RAM_START + 1);
+    assert_eq!(buffer.address, Bus::RAM_START + 3);
+    assert_eq!(buffer.element(3).unwrap().address, Bus::RAM_START + 6);
+    assert!(buffer.element(4).is_none());
+
+    let (_, symbols) = compiler.compile_with_symbols().unwrap();
+    let variable = symbols.variable_at(Bus::RAM_START + 2).unwrap();
+    assert_eq!(variable.name, "score");
+    assert_eq!(variable.range(), 0x8001..=0x8002);
+    assert_eq!(symbols.variables.len(), 3);
+}
+
+#[test]
+fn test_compiler_variables_load_store() {
+    let mut compiler = Compiler::new();
+    let counter = compiler.var_u8("counter");
+    let total = compiler.var_u16("total");
+    let buffer = compiler.array("buffer", 2);
+    let compiler = compiler
+        .load_r8i(R8::A, 0x42)
+        .store_u8(counter, R8::A)
+        .load_r16i(R16::DE, 0xBEEF)
+        .store_u16(total, R16::DE)
+        .load_r8i(R8::B, 0x07)
+        .store_u8(buffer.element(1).unwrap(), R8::B)
+        .load_u8(R8::C, counter)
+        .load_u16(R16::BC, total)
+        .load_u8(R8::D, buffer.element(1).unwrap())
+        .load_u16(R16::HL, total);
+
+    let mut console = run(compiler);
+
+    let registers = console.cpu.get_registers();
+    assert_eq!(console.bus.read(counter.address.into()).value(), 0x42);
+    assert_eq!(console.bus.read(total.address.into()).value(), 0xEF);
+    assert_eq!(console.bus.read((total.address + 1).into()).value(), 0xBE);
+    assert_eq!(registers.get_r16(R16::BC).value(), 0xBEEF);
+    assert_eq!(registers.get_r16(R16::HL).value(), 0xBEEF);
+    assert_eq!(registers.get_r8(&mut console.bus, R8::D).value(), 0x07);
+}
+
+#[test]
+fn test_compiler_variables_stay_clear_of_stack() {
+    let mut compiler = Compiler::new();
+    let free = compiler.variables_end() - Bus::RAM_START;
+    let heap = compiler.array("heap", free);
+    compiler.var_u8("overflow");
+
+    assert!(heap.address + heap.len <= Bus::DEFAULT_SP - Compiler::STACK_SIZE + 1);
+    assert_eq!(
+        compiler.compile().unwrap_err().kinds().collect::<Vec<_>>(),
+        vec![&CompileErrorKind::OutOfRAM("overflow".to_string())]
+    );
+}
+
+#[test]
+fn test_compiler_variables_fit_profile() {
+    let mut compiler = Compiler::new().with_profile(HardwareProfile::Lite);
+    let ram_end = HardwareProfile::Lite.config().ram_end();
+    let free = compiler.variables_end() - Bus::RAM_START;
+    let heap = compiler.array("heap", free);
+    compiler.var_u8("overflow");
+
+    assert!(heap.address + heap.len <= ram_end - Compiler::STACK_SIZE + 1);
+    assert_eq!(
+        compiler.compile().unwrap_err().kinds().collect::<Vec<_>>(),
+        vec![&CompileErrorKind::OutOfRAM("overflow".to_string())]
+    );
+
+    // Variables allocated before the profile was picked are checked against it too
+    let mut compiler = Compiler::new();
+    compiler.array("heap", 0x2000);
+    let error = compiler
+        .with_profile(HardwareProfile::Lite)
+        .compile()
+        .unwrap_err();
+    assert_eq!(
+        error.kinds().collect::<Vec<_>>(),
+        vec![&CompileErrorKind::OutOfRAM("heap".to_string())]
+    );
+}
+
+#[test]
+fn test_compiler_variables_errors() {
+    let mut compiler = Compiler::new();
+    let value = compiler.var_u8("value");
+    compiler.var_u16("value");
+    let error = compiler.store_u8(value, R8::H).compile().unwrap_err();
+
+    let kinds = error.kinds().collect::<Vec<_>>();
+    assert_eq!(kinds.len(), 2);
+    assert_eq!(
+        kinds[0],
+        &CompileErrorKind::DuplicateVariable("value".to_string())
+    );
+    assert!(matches!(kinds[1], CompileErrorKind::InvalidOperand(_)));
+}
@@ -0,0 +1,98 @@
+use crate::console::cartridge::Cartridge;
+use crate::console::components::bus::Bus;
+use crate::console::components::cpu::registers::R16;
+use crate::console::config::{ConsoleConfig, HardwareProfile, Peripherals};
+use crate::console::types::byte::Byte;
+use crate::console::types::word::Word;
+use crate::console::Console;
+use crate::error::LMVC8Error;
+use rstest::rstest;
+
+#[test]
+fn test_console_config_lite_layout() {
+    let console = Console::with_config(HardwareProfile::Lite.config()).unwrap();
+
+    let names = console
+        .bus
+        .layout()
+        .iter()
+        .map(|region| region.name.as_str())
+        .collect::<Vec<_>>();
+    assert!(names.contains(&"DMA"));
+    assert!(!names.contains(&"Serial"));
+    assert!(!names.contains(&"RTC"));
+    assert!(!names.contains(&"Watchdog"));
+
+    let ram = console
+        .bus
+        .layout()
+        .iter()
+        .find(|region| region.name == "RAM");
+    assert_eq!(ram.unwrap().range(), Bus::RAM_START..=0x9FFF);
+}
+
+#[test]
+fn test_console_config_ram_size() {
+    let config = ConsoleConfig::default().with_ram_size(0x100);
+    let mut console = Console::with_config(config).unwrap();
+
+    console.bus.write(0x80FF.into(), Byte::new(0x12));
+    console.bus.write(0x8100.into(), Byte::new(0x34));
+
+    assert_eq!(console.bus.read(0x80FF.into()), Byte::new(0x12));
+    assert_eq!(console.bus.read(0x8100.into()), Byte::new(0));
+}
+
+#[test]
+fn test_console_config_boot() {
+    let config = ConsoleConfig::default().with_boot(0x0100, 0x9000);
+    let mut console = Console::with_config(config).unwrap();
+
+    assert_eq!(u16::from(console.cpu.get_pc()), 0x0100);
+    assert_eq!(
+        console.cpu.get_registers().get_r16(R16::SP),
+        Word::new(0x9000)
+    );
+
+    console.step();
+    console.reset();
+
+    assert_eq!(u16::from(console.cpu.get_pc()), 0x0100);
+}
+
+#[rstest]
+#[case(0)]
+#[case(0x6001)]
+fn test_console_config_invalid_ram_size(#[case] ram_size: u16) {
+    let config = ConsoleConfig::default().with_ram_size(ram_size);
+
+    assert!(matches!(
+        Console::with_config(config),
+        Err(LMVC8Error::InvalidConsoleConfig(_))
+    ));
+}
+
+#[rstest]
+#[case(HardwareProfile::Standard, HardwareProfile::Standard, true)]
+#[case(HardwareProfile::Standard, HardwareProfile::Lite, true)]
+#[case(HardwareProfile::Lite, HardwareProfile::Lite, true)]
+#[case(HardwareProfile::Lite, HardwareProfile::Standard, false)]
+fn test_console_config_cartridge_profile(
+    #[case] hardware: HardwareProfile,
+    #[case] required: HardwareProfile,
+    #[case] supported: bool,
+) {
+    let mut console = Console::with_config(hardware.config()).unwrap();
+
+    let result = console.load_cartridge(Cartridge::new(vec![0x00]).with_profile(required));
+
+    assert_eq!(result.is_ok(), supported);
+}
+
+#[test]
+fn test_console_config_supports_peripherals() {
+    let config = ConsoleConfig::default().with_peripherals(Peripherals::all() - Peripherals::RNG);
+
+    assert!(!config.supports(HardwareProfile::Lite));
+    assert!(!config.supports(HardwareProfile::Standard));
+}
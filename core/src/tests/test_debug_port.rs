@@ -0,0 +1,70 @@
+use crate::console::components::bus::Bus;
+use crate::console::components::cpu::registers::{R16, R8};
+use crate::console::Console;
+
+const OP_HALT: u8 = 0x10;
+const OP_LDR8_HL_A: u8 = 0x51;
+const OP_LDR16I_HL: u8 = 0x66;
+const OP_LDR8I_A: u8 = 0x68;
+
+#[test]
+fn test_debug_port_char() {
+    let mut console = Console::builder()
+        .r16(R16::HL, Bus::DEBUG_CHAR)
+        .rom(OP_LDR8I_A)
+        .rom(b'h')
+        .rom(OP_LDR8_HL_A)
+        .rom(OP_LDR8I_A)
+        .rom(b'i')
+        .rom(OP_LDR8_HL_A)
+        .rom(OP_HALT)
+        .build();
+
+    console.step_till_halt();
+
+    assert_eq!(console.debug_output(), b"hi");
+    assert_eq!(console.take_debug_output(), b"hi");
+    assert!(console.debug_output().is_empty());
+}
+
+#[test]
+fn test_debug_port_hex() {
+    let mut console = Console::builder()
+        .r8(R8::A, 0x3C)
+        .rom(OP_LDR16I_HL)
+        .rom(Bus::DEBUG_HEX as u8)
+        .rom((Bus::DEBUG_HEX >> 8) as u8)
+        .rom(OP_LDR8_HL_A)
+        .rom(OP_HALT)
+        .build();
+
+    console.step_till_halt();
+
+    assert_eq!(console.debug_output(), b"3C");
+}
+
+#[test]
+fn test_debug_port_text_split_char() {
+    let mut console = Console::builder()
+        .r16(R16::HL, Bus::DEBUG_CHAR)
+        .rom(OP_LDR8I_A)
+        .rom(b'a')
+        .rom(OP_LDR8_HL_A)
+        .rom(OP_LDR8I_A)
+        .rom(0xC3)
+        .rom(OP_LDR8_HL_A)
+        .rom(OP_HALT)
+        .rom(OP_LDR8I_A)
+        .rom(0xA9)
+        .rom(OP_LDR8_HL_A)
+        .rom(OP_HALT)
+        .build();
+
+    console.step_till_halt();
+    assert_eq!(console.take_debug_text(), "a");
+    assert_eq!(console.debug_output(), [0xC3]);
+
+    console.step_till_halt();
+    assert_eq!(console.take_debug_text(), "é");
+    assert!(console.debug_output().is_empty());
+}
@@ -0,0 +1,160 @@
+use crate::console::cartridge::symbols::{DebugSymbols, Label, SourceLocation, SymbolRange};
+use crate::console::cartridge::Cartridge;
+use crate::error::LMVC8Error;
+
+fn symbols() -> DebugSymbols {
+    DebugSymbols {
+        labels: vec![Label {
+            name: "main".to_string(),
+            address: 0x0000,
+        }],
+        functions: vec![SymbolRange {
+            name: "add".to_string(),
+            start: 0x0002,
+            end: 0x0003,
+        }],
+        data: vec![SymbolRange {
+            name: "table".to_string(),
+            start: 0x0004,
+            end: 0x0005,
+        }],
+        sources: vec![SourceLocation {
+            address: 0x0002,
+            file: "main.asm".to_string(),
+            line: 3,
+            column: 1,
+        }],
+        variables: vec![SymbolRange {
+            name: "score".to_string(),
+            start: 0x8000,
+            end: 0x8001,
+        }],
+    }
+}
+
+#[test]
+fn test_debug_symbols_lookup() {
+    let symbols = symbols();
+
+    assert_eq!(symbols.label_at(0x0000), Some("main"));
+    assert_eq!(symbols.address_of("add"), Some(0x0002));
+    assert_eq!(symbols.address_of("missing"), None);
+    assert_eq!(symbols.function_at(0x0003).unwrap().name, "add");
+    assert!(symbols.is_data(0x0005));
+    assert!(!symbols.is_data(0x0006));
+    assert_eq!(symbols.source_at(0x0005).unwrap().line, 3);
+    assert!(symbols.source_at(0x0001).is_none());
+    assert_eq!(symbols.variable_at(0x8001).unwrap().name, "score");
+    assert!(symbols.variable_at(0x8002).is_none());
+}
+
+#[test]
+fn test_debug_symbols_lmc_round_trip() {
+    let cartridge = Cartridge::new(vec![0x00; 6]).with_symbols(symbols());
+
+    let loaded = Cartridge::from_lmc(&cartridge.to_lmc().unwrap()).unwrap();
+
+    assert_eq!(loaded.symbols, Some(symbols()));
+    assert!(
+        Cartridge::from_lmc(&Cartridge::new(vec![]).to_lmc().unwrap())
+            .unwrap()
+            .symbols
+            .is_none()
+    );
+}
+
+#[test]
+fn test_debug_symbols_lmc_truncated_section() {
+    let mut data = Cartridge::new(vec![0x00; 6])
+        .with_symbols(symbols())
+        .to_lmc()
+        .unwrap();
+    data.truncate(data.len() - 2);
+
+    assert!(matches!(
+        Cartridge::from_lmc(&data),
+        Err(LMVC8Error::InvalidDebugSymbols(_))
+    ));
+}
+
+#[test]
+fn test_debug_symbols_lmc_version_1() {
+    let symbols = DebugSymbols {
+        variables: vec![],
+        ..symbols()
+    };
+    let mut data = Cartridge::new(vec![0x00; 6])
+        .with_symbols(symbols.clone())
+        .to_lmc()
+        .unwrap();
+    // Version 1 sections end without the variable count
+    data[4] = 1;
+    data.truncate(data.len() - 2);
+
+    let loaded = Cartridge::from_lmc(&data).unwrap();
+
+    assert_eq!(loaded.symbols, Some(symbols));
+}
+
+#[cfg(feature = "compiler")]
+#[test]
+fn test_debug_symbols_compiler() {
+    use crate::compiler::Compiler;
+    use crate::console::components::cpu::registers::R8;
+
+    let (binary, symbols) = Compiler::new()
+        .label("main")
+        .source_location("main.rs", 10, 5)
+        .load_r8i(R8::A, 1)
+        .function_symbol("add", |c| c.add_r8(R8::B).ret())
+        .data_region("table", |c| c.push_data(vec![1, 2, 3]))
+        .data_region("empty", |c| c)
+        .compile_with_symbols()
+        .unwrap();
+
+    assert_eq!(binary.len(), 8);
+    assert_eq!(symbols.label_at(0x0000), Some("main"));
+    assert_eq!(symbols.source_at(0x0001).unwrap().file, "main.rs");
+    assert_eq!(symbols.functions[0].range(), 0x0002..=0x0003);
+    assert_eq!(symbols.data.len(), 1);
+    assert_eq!(symbols.data[0].range(), 0x0004..=0x0006);
+}
+
+#[cfg(feature = "disassembler")]
+#[test]
+fn test_debug_symbols_disassembler_data() {
+    use crate::disassembler::Disassembler;
+
+    // LD A, n would swallow the second data byte if the data were decoded
+    let binary = [0x00, 0x00, 0x00, 0x00, 0x68, 0x10];
+    let symbols = symbols();
+
+    let disassembled = Disassembler::new(&binary)
+        .with_symbols(Some(&symbols))
+        .disassemble();
+
+    assert!(disassembled.nodes()[4].is_byte());
+    assert!(disassembled.nodes()[5].is_byte());
+    assert_eq!(disassembled.label_at(0x0000), Some("main"));
+}
+
+#[cfg(feature = "debugger")]
+#[test]
+fn test_debug_symbols_breakpoint_by_name() {
+    use crate::console::types::address::Address;
+    use crate::debugger::Debugger;
+
+    let mut debugger = Debugger::new();
+    assert!(matches!(
+        debugger.set_breakpoint_by_name("add"),
+        Err(LMVC8Error::UnknownSymbol(_))
+    ));
+
+    debugger.set_symbols(Some(symbols()));
+
+    assert_eq!(
+        debugger.set_breakpoint_by_name("add").unwrap(),
+        Address::from(0x0002)
+    );
+    assert!(debugger.get_breakpoints().contains(&Address::from(0x0002)));
+}
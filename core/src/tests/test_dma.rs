@@ -0,0 +1,88 @@
+use crate::console::components::bus::Bus;
+use crate::console::components::cpu::interrupts::{InterruptFlags, IV_DMA};
+use crate::console::components::cpu::registers::R16;
+use crate::console::types::address::Address;
+use crate::console::types::byte::Byte;
+use crate::console::types::word::Word;
+use crate::console::Console;
+
+const OP_NOP: u8 = 0x00;
+const OP_EI: u8 = 0x01;
+const OP_HALT: u8 = 0x10;
+
+fn start_transfer(console: &mut Console, source: u16, destination: u16, length: u16) {
+    let [source_low, source_high] = source.to_le_bytes();
+    let [destination_low, destination_high] = destination.to_le_bytes();
+    let [length_low, length_high] = length.to_le_bytes();
+    for (register, value) in [
+        (Bus::DMA_SOURCE_LOW, source_low),
+        (Bus::DMA_SOURCE_HIGH, source_high),
+        (Bus::DMA_DESTINATION_LOW, destination_low),
+        (Bus::DMA_DESTINATION_HIGH, destination_high),
+        (Bus::DMA_LENGTH_LOW, length_low),
+        (Bus::DMA_LENGTH_HIGH, length_high),
+        (Bus::DMA_CONTROL, 0b1000_0000),
+    ] {
+        console.bus.write(register.into(), value.into());
+    }
+    console.bus.take_step_cycles();
+}
+
+#[test]
+fn test_dma_copies_memory_and_stalls_cpu() {
+    let mut console = Console::builder()
+        .rom(0x11)
+        .rom(0x22)
+        .rom(0x33)
+        .rom(0x44)
+        .build();
+    start_transfer(&mut console, 0x0000, Bus::RAM_START, 4);
+
+    for _ in 0..4 {
+        let step = console.step();
+        assert_eq!(step.cycles, 2);
+        assert_eq!(console.cpu.get_pc(), Word::new(0));
+    }
+
+    assert!(!console.bus.dma.is_active());
+    assert!(console.bus.ia.contains(InterruptFlags::DMA));
+    for (offset, value) in [0x11, 0x22, 0x33, 0x44].into_iter().enumerate() {
+        assert_eq!(
+            console
+                .bus
+                .read(Address::from(Bus::RAM_START + offset as u16)),
+            Byte::new(value)
+        );
+    }
+}
+
+#[test]
+fn test_dma_registers_locked_while_busy() {
+    let mut console = Console::new();
+    start_transfer(&mut console, 0x0000, Bus::RAM_START, 2);
+
+    console
+        .bus
+        .write(Bus::DMA_LENGTH_LOW.into(), Byte::new(0xFF));
+
+    assert_eq!(console.bus.dma.length, Word::new(2));
+}
+
+#[test]
+fn test_dma_completion_interrupt() {
+    let mut console = Console::builder()
+        .rom(OP_EI)
+        .rom(OP_NOP)
+        .rom(OP_HALT)
+        .r16(R16::SP, Bus::DEFAULT_SP)
+        .write(Bus::INTERRUPT_ENABLE, InterruptFlags::DMA.bits())
+        .build();
+    console.step();
+    start_transfer(&mut console, 0x0000, Bus::RAM_START, 1);
+
+    console.step();
+    console.step();
+
+    assert_eq!(console.cpu.get_pc(), Word::new(IV_DMA + 1));
+    assert!(!console.bus.ia.contains(InterruptFlags::DMA));
+}
@@ -1,4 +1,5 @@
 use crate::console::components::bus::Bus;
+use crate::console::components::cpu::alu::ALUFlags;
 use crate::console::components::cpu::registers::{R16, R16S, R8};
 use crate::console::types::address::Address;
 use crate::console::types::byte::Byte;
@@ -6,7 +7,12 @@ use crate::console::types::word::Word;
 use crate::console::Console;
 use rstest::rstest;
 
+const OP_CALL: u8 = 0x02;
+const OP_JP: u8 = 0x03;
+const OP_ADD_B: u8 = 0x09;
 const OP_HALT: u8 = 0x10;
+const OP_RET: u8 = 0x12;
+const OP_RETI: u8 = 0x13;
 const OP_LDR8_A_B: u8 = 0x20;
 const OP_LDR8_A_C: u8 = 0x21;
 const OP_LDR8_A_D: u8 = 0x22;
@@ -63,6 +69,7 @@ const OP_LDR8_HL_D: u8 = 0x54;
 const OP_LDR8_HL_E: u8 = 0x55;
 const OP_LDR8_HL_H: u8 = 0x56;
 const OP_LDR8_HL_L: u8 = 0x57;
+const OP_INC_C: u8 = 0x7A;
 const OP_PUSH_AF: u8 = 0x80;
 const OP_PUSH_BC: u8 = 0x81;
 const OP_PUSH_DE: u8 = 0x82;
@@ -71,6 +78,11 @@ const OP_POP_AF: u8 = 0x84;
 const OP_POP_BC: u8 = 0x85;
 const OP_POP_DE: u8 = 0x86;
 const OP_POP_HL: u8 = 0x87;
+const OP_DEC_B: u8 = 0x89;
+const OP_JP_Z: u8 = 0x90;
+const OP_JP_NZ: u8 = 0x91;
+const OP_JP_C: u8 = 0x92;
+const OP_JP_NC: u8 = 0x93;
 
 #[rstest]
 #[case::ldr8_a_b(OP_LDR8_A_B, R8::A, R8::B)]
@@ -227,7 +239,7 @@ fn test_push(#[case] opcode: u8, #[case] register: R16S) {
 #[case::pop_hl(OP_POP_HL, R16S::HL)]
 fn test_op(#[case] opcode: u8, #[case] register: R16S) {
     let mut console = Console::builder()
-        .r16(R16::SP, Bus::DEFAULT_SP - 1)
+        .r16(R16::SP, Bus::DEFAULT_SP - 2)
         .write(Bus::DEFAULT_SP, 0x75)
         .write(Bus::DEFAULT_SP - 1, 0x01)
         .rom(opcode)
@@ -264,3 +276,130 @@ fn test_op(#[case] opcode: u8, #[case] register: R16S) {
         }
     }
 }
+
+#[test]
+fn test_jump() {
+    let mut console = Console::builder()
+        .rom(OP_JP)
+        .rom(0x04)
+        .rom(0x00)
+        .rom(OP_HALT)
+        .rom(OP_LDR8_A_B)
+        .rom(OP_HALT)
+        .r8(R8::B, 0x42)
+        .build();
+
+    console.step_till_halt();
+
+    assert_eq!(u16::from(console.cpu.get_pc()), 0x0006);
+    assert_eq!(
+        console.cpu.get_registers().get_r8(&mut console.bus, R8::A),
+        Byte::new(0x42)
+    );
+}
+
+#[rstest]
+#[case::z_taken(OP_DEC_B, 0x00, 0x01, OP_JP_Z, true)]
+#[case::z_not_taken(OP_DEC_B, 0x00, 0x02, OP_JP_Z, false)]
+#[case::nz_taken(OP_DEC_B, 0x00, 0x02, OP_JP_NZ, true)]
+#[case::nz_not_taken(OP_DEC_B, 0x00, 0x01, OP_JP_NZ, false)]
+#[case::c_taken(OP_ADD_B, 0xFF, 0x01, OP_JP_C, true)]
+#[case::c_not_taken(OP_ADD_B, 0x00, 0x01, OP_JP_C, false)]
+#[case::nc_taken(OP_ADD_B, 0x00, 0x01, OP_JP_NC, true)]
+#[case::nc_not_taken(OP_ADD_B, 0xFF, 0x01, OP_JP_NC, false)]
+fn test_jump_if(
+    #[case] flag_opcode: u8,
+    #[case] a: u8,
+    #[case] b: u8,
+    #[case] jump_opcode: u8,
+    #[case] taken: bool,
+) {
+    let mut console = Console::builder()
+        .rom(flag_opcode)
+        .rom(jump_opcode)
+        .rom(0x05)
+        .rom(0x00)
+        .rom(OP_HALT)
+        .rom(OP_HALT)
+        .r8(R8::A, a)
+        .r8(R8::B, b)
+        .build();
+
+    console.step_till_halt();
+
+    let expected_pc = if taken { 0x0006 } else { 0x0005 };
+    assert_eq!(u16::from(console.cpu.get_pc()), expected_pc);
+}
+
+#[test]
+fn test_increment_keeps_carry() {
+    let mut console = Console::builder()
+        .rom(OP_ADD_B)
+        .rom(OP_INC_C)
+        .rom(OP_HALT)
+        .r8(R8::A, 0xFF)
+        .r8(R8::B, 0x02)
+        .r8(R8::C, 0xFF)
+        .build();
+
+    console.step_till_halt();
+
+    assert!(console
+        .cpu
+        .get_alu()
+        .get_flags()
+        .contains(ALUFlags::ZERO | ALUFlags::CARRY));
+}
+
+#[test]
+fn test_call_return() {
+    let mut console = Console::builder()
+        .rom(OP_CALL)
+        .rom(0x05)
+        .rom(0x00)
+        .rom(OP_LDR8_A_B)
+        .rom(OP_HALT)
+        .rom(OP_PUSH_BC)
+        .rom(OP_POP_DE)
+        .rom(OP_RET)
+        .r16(R16::SP, Bus::DEFAULT_SP)
+        .r8(R8::B, 0x42)
+        .build();
+
+    console.step_till_halt();
+
+    assert_eq!(u16::from(console.cpu.get_pc()), 0x0005);
+    assert_eq!(
+        console.cpu.get_registers().get_r8(&mut console.bus, R8::A),
+        Byte::new(0x42)
+    );
+    assert_eq!(
+        console.cpu.get_registers().get_r16(R16::SP),
+        Word::new(Bus::DEFAULT_SP)
+    );
+    assert_eq!(
+        console.cpu.get_registers().get_r8(&mut console.bus, R8::D),
+        Byte::new(0x42)
+    );
+}
+
+#[test]
+fn test_return_interrupt() {
+    let mut console = Console::builder()
+        .rom(OP_CALL)
+        .rom(0x04)
+        .rom(0x00)
+        .rom(OP_HALT)
+        .rom(OP_RETI)
+        .r16(R16::SP, Bus::DEFAULT_SP)
+        .build();
+
+    console.step_till_halt();
+
+    assert_eq!(u16::from(console.cpu.get_pc()), 0x0004);
+    assert_eq!(
+        console.cpu.get_registers().get_r16(R16::SP),
+        Word::new(Bus::DEFAULT_SP)
+    );
+    assert!(console.cpu.get_ime());
+}
@@ -0,0 +1,44 @@
+use crate::console::components::bus::Bus;
+use crate::console::components::cpu::interrupts::{InterruptFlags, IV_INPUT};
+use crate::console::components::cpu::registers::R16;
+use crate::console::types::word::Word;
+use crate::console::Console;
+
+const OP_NOP: u8 = 0x00;
+const OP_EI: u8 = 0x01;
+
+fn console_with_pending_input(first_opcode: u8) -> Console {
+    Console::builder()
+        .rom(first_opcode)
+        .rom(OP_NOP)
+        .r16(R16::SP, Bus::DEFAULT_SP)
+        .write(Bus::INTERRUPT_ENABLE, InterruptFlags::INPUT.bits())
+        .write(Bus::INTERRUPT_ACTIVE, InterruptFlags::INPUT.bits())
+        .build()
+}
+
+#[test]
+fn test_interrupt_ignored_while_disabled() {
+    let mut console = console_with_pending_input(OP_NOP);
+
+    console.step();
+    console.step();
+
+    assert_eq!(console.cpu.get_pc(), Word::new(2));
+    assert!(console.bus.ia.contains(InterruptFlags::INPUT));
+}
+
+#[test]
+fn test_interrupt_acknowledged() {
+    let mut console = console_with_pending_input(OP_EI);
+
+    console.step();
+    console.step();
+
+    assert_eq!(console.cpu.get_pc(), Word::new(IV_INPUT + 1));
+    assert!(!console.bus.ia.contains(InterruptFlags::INPUT));
+    assert_eq!(
+        console.cpu.get_registers().get_r16(R16::SP),
+        Word::new(Bus::DEFAULT_SP - 2)
+    );
+}
@@ -0,0 +1,191 @@
+use crate::compiler::error::LangError;
+use crate::compiler::lang::{Frontend, Span};
+use crate::console::cartridge::symbols::DebugSymbols;
+use crate::console::cartridge::Cartridge;
+use crate::console::Console;
+use rstest::rstest;
+
+fn run(source: &str) -> (Console, DebugSymbols) {
+    let (binary, symbols) = Frontend::new(source)
+        .compile()
+        .unwrap()
+        .compile_with_symbols()
+        .unwrap();
+    let mut console = Console::new();
+    console.load_cartridge(Cartridge::new(binary)).unwrap();
+    console.step_till_halt();
+    (console, symbols)
+}
+
+/// The value of a global variable, little endian if it's a word
+fn global(console: &mut Console, symbols: &DebugSymbols, name: &str) -> u16 {
+    let variable = symbols
+        .variables
+        .iter()
+        .find(|variable| variable.name == name)
+        .unwrap();
+    variable.range().rev().fold(0, |value, address| {
+        value << 8 | console.bus.read(address.into()).value() as u16
+    })
+}
+
+fn error(source: &str) -> LangError {
+    Frontend::new(source).compile().err().unwrap()
+}
+
+#[test]
+fn test_lang_program() {
+    let source = "
+        // Sums 1 to 10
+        let total: u16 = 0;
+        let large: u8 = 0;
+
+        fn add(a: u16, b: u16) -> u16 {
+            return a + b;
+        }
+
+        fn main() {
+            let i: u8 = 1;
+            while i <= 10 {
+                total = add(total, i as u16);
+                i = i + 1;
+            }
+            if total > 50 {
+                large = 1;
+            } else {
+                large = 2;
+            }
+        }
+    ";
+
+    let (mut console, symbols) = run(source);
+
+    assert_eq!(global(&mut console, &symbols, "total"), 55);
+    assert_eq!(global(&mut console, &symbols, "large"), 1);
+    assert_eq!(global(&mut console, &symbols, "main.i"), 11);
+    assert!(symbols.address_of("add").is_some());
+    assert!(symbols
+        .sources
+        .iter()
+        .all(|source| source.file == "<source>"));
+    assert_eq!(
+        symbols
+            .source_at(symbols.address_of("add").unwrap())
+            .unwrap()
+            .line,
+        7
+    );
+}
+
+#[rstest]
+#[case("u8", "3 == 3", true)]
+#[case("u8", "3 != 3", false)]
+#[case("u8", "2 < 3", true)]
+#[case("u8", "3 < 3", false)]
+#[case("u8", "3 <= 3", true)]
+#[case("u8", "4 <= 3", false)]
+#[case("u8", "4 > 3", true)]
+#[case("u8", "3 > 3", false)]
+#[case("u8", "3 >= 3", true)]
+#[case("u8", "2 >= 3", false)]
+#[case("u16", "0x1234 == 0x1234", true)]
+#[case("u16", "0x1234 < 0x1300", true)]
+#[case("u16", "0x1400 <= 0x1300", false)]
+#[case("u16", "0x0100 > 0x00FF", true)]
+fn test_lang_comparisons(#[case] ty: &str, #[case] condition: &str, #[case] holds: bool) {
+    // The left side goes through a variable so both sides are typed
+    let (lhs, rest) = condition.split_once(' ').unwrap();
+    let source = format!(
+        "let result: u8 = 0;
+        fn main() {{
+            let lhs: {ty} = {lhs};
+            if lhs {rest} {{ result = 1; }} else {{ result = 2; }}
+        }}"
+    );
+
+    let (mut console, symbols) = run(&source);
+
+    let expected = if holds { 1 } else { 2 };
+    assert_eq!(global(&mut console, &symbols, "result"), expected);
+}
+
+#[test]
+fn test_lang_calls_and_casts() {
+    let source = "
+        let result: u8 = 0;
+        let wrapped: u8 = 0;
+        let widened: u16 = 0;
+
+        fn sub(a: u8, b: u8) -> u8 {
+            return a - b;
+        }
+
+        fn main() {
+            result = sub(10, sub(5, 3));
+            wrapped = sub(0, 1);
+            widened = (0x01F0 as u8) as u16 + 0x0100;
+        }
+    ";
+
+    let (mut console, symbols) = run(source);
+
+    assert_eq!(global(&mut console, &symbols, "result"), 8);
+    assert_eq!(global(&mut console, &symbols, "wrapped"), 0xFF);
+    assert_eq!(global(&mut console, &symbols, "widened"), 0x01F0);
+}
+
+#[test]
+fn test_lang_memory_mapped_io() {
+    let source = "
+        fn main() {
+            poke(0x8100, 0x40);
+            let next: u8 = peek(0x8100) + 1;
+            poke(0xFFAA, 104);
+            poke(0xFFAA, 105);
+            poke(0x8101, next);
+        }
+    ";
+
+    let (mut console, _) = run(source);
+
+    assert_eq!(console.debug_output(), b"hi");
+    assert_eq!(console.bus.read(0x8101.into()).value(), 0x41);
+}
+
+#[rstest]
+#[case("fn main() { let x: u8 = 300; }", 1, 25, "300 doesn't fit in a u8")]
+#[case("fn main() { x = 1; }", 1, 13, "unknown variable 'x'")]
+#[case(
+    "fn main() {\n  let x: u16 = 1;\n  let y: u8 = x;\n}",
+    3,
+    15,
+    "expected u8, found u16"
+)]
+#[case("fn main() { f(); }\nfn f() { main(); }", 1, 13, "'main' calls itself")]
+#[case("fn f() -> u8 { }\nfn main() { }", 1, 4, "can end without returning")]
+#[case("fn main() { let x: u8 = 1 }", 1, 27, "expected ';'")]
+#[case("fn main() { 1 + 2; }", 1, 13, "only calls")]
+#[case("fn main() { poke(1); }", 1, 13, "takes 2 argument(s)")]
+#[case("fn peek() { }", 1, 4, "built-in")]
+#[case("fn start() { }", 1, 1, "no 'main'")]
+#[case("fn main() { let x: u8 = 1 # 2; }", 1, 27, "unexpected character '#'")]
+fn test_lang_errors(
+    #[case] source: &str,
+    #[case] line: usize,
+    #[case] column: usize,
+    #[case] message: &str,
+) {
+    let error = error(source);
+
+    assert_eq!((error.line, error.column), (line, column), "{error}");
+    assert!(error.message.contains(message), "{error}");
+    assert_eq!(error.file, "<source>");
+}
+
+#[test]
+fn test_lang_error_span() {
+    let error = error("fn main() {\n  let x: u8 = 1;\n  x = x + 0x1234;\n}");
+
+    assert_eq!(error.span, Span::new(39, 45));
+    assert_eq!(error.to_string(), "<source>:3:11: 4660 doesn't fit in a u8");
+}
@@ -0,0 +1,104 @@
+use crate::console::components::bus::memory_map::DeviceId;
+use crate::console::components::bus::{Bus, MemoryMapped};
+use crate::console::components::cpu::registers::{R16, R8};
+use crate::console::types::address::Address;
+use crate::console::types::byte::Byte;
+use crate::console::Console;
+use crate::error::LMVC8Error;
+
+const OP_HALT: u8 = 0x10;
+const OP_LDR8_A_HL: u8 = 0x26;
+const OP_LDR8_HL_A: u8 = 0x51;
+
+const LATCH_START: u16 = 0xF000;
+
+/// Remembers the last write and the device relative address it went to
+#[derive(Debug, Default, Clone)]
+struct Latch {
+    addr: u16,
+    value: u8,
+}
+
+impl MemoryMapped for Latch {
+    fn read(&mut self, _addr: Address) -> Byte {
+        Byte::new(self.value.wrapping_add(1))
+    }
+
+    fn write(&mut self, addr: Address, value: Byte) {
+        self.addr = addr.into();
+        self.value = value.into();
+    }
+}
+
+#[test]
+fn test_memory_map_custom_device() {
+    let mut console = Console::builder()
+        .r8(R8::A, 0x41)
+        .r16(R16::HL, LATCH_START + 3)
+        .rom(OP_LDR8_HL_A)
+        .rom(OP_LDR8_A_HL)
+        .rom(OP_HALT)
+        .build();
+    console
+        .bus
+        .register_device("Latch", LATCH_START..=LATCH_START + 0xF, Latch::default())
+        .unwrap();
+
+    console.step_till_halt();
+
+    let latch = console.bus.device_mut::<Latch>("Latch").unwrap();
+    assert_eq!(latch.addr, 3);
+    assert_eq!(latch.value, 0x41);
+    assert_eq!(
+        console.cpu.get_registers().get_r8(&mut console.bus, R8::A),
+        Byte::new(0x42)
+    );
+}
+
+#[test]
+fn test_memory_map_overlap() {
+    let mut bus = Bus::default();
+
+    let result = bus.register_device("Latch", 0xDFFF..=0xE000, Latch::default());
+
+    assert!(matches!(
+        result,
+        Err(LMVC8Error::MemoryRegionOverlap { other, .. }) if other == "RAM"
+    ));
+    assert_eq!(bus.memory_map().resolve(0xDFFF.into()).0, DeviceId::RAM);
+}
+
+#[test]
+fn test_memory_map_unmap() {
+    let mut bus = Bus::default();
+
+    assert!(matches!(
+        bus.unmap("ROM"),
+        Err(LMVC8Error::FixedMemoryRegion(_))
+    ));
+    assert!(matches!(
+        bus.unmap("Missing"),
+        Err(LMVC8Error::UnknownMemoryRegion(_))
+    ));
+
+    bus.unmap("Debug").unwrap();
+    bus.write(Bus::DEBUG_CHAR.into(), Byte::new(b'x'));
+
+    assert!(bus.debug_port.output().is_empty());
+    assert!(bus.memory_map().region_at(Bus::DEBUG_CHAR.into()).is_none());
+}
+
+#[test]
+fn test_memory_map_layout() {
+    let mut bus = Bus::default();
+    bus.register_device("Latch", LATCH_START..=LATCH_START, Latch::default())
+        .unwrap();
+
+    let layout = bus.layout();
+
+    assert!(layout.windows(2).all(|pair| pair[0].end < pair[1].start));
+    let latch = layout.iter().find(|region| region.name == "Latch").unwrap();
+    assert_eq!(latch.range(), LATCH_START..=LATCH_START);
+    assert_eq!(latch.device, DeviceId::Custom(0));
+    assert_eq!(latch.size(), 1);
+}
@@ -0,0 +1,175 @@
+use crate::console::cartridge::lmc::crc32;
+use crate::console::cartridge::patch::{self, PatchFormat};
+use crate::console::cartridge::Cartridge;
+use crate::error::LMVC8Error;
+use rstest::rstest;
+
+fn binaries() -> Vec<(Vec<u8>, Vec<u8>)> {
+    let source = (0..=255).cycle().take(1000).collect::<Vec<u8>>();
+    let mut changed = source.clone();
+    changed[3] = 0xAA;
+    changed[500..700].fill(0x55);
+    let mut grown = source.clone();
+    grown.extend_from_slice(&[1, 2, 3]);
+    let shrunk = source[..900].to_vec();
+
+    vec![
+        (source.clone(), source.clone()),
+        (source.clone(), changed),
+        (source.clone(), grown),
+        (source, shrunk),
+        (vec![], vec![7; 300]),
+    ]
+}
+
+#[rstest]
+#[case(PatchFormat::Ips)]
+#[case(PatchFormat::Bps)]
+fn test_patch_round_trip(#[case] format: PatchFormat) {
+    for (source, target) in binaries() {
+        let patch = patch::create(format, &source, &target).unwrap();
+
+        assert_eq!(PatchFormat::detect(&patch), Some(format));
+        assert_eq!(patch::apply(&source, &patch).unwrap(), target);
+    }
+}
+
+#[test]
+fn test_patch_ips_records() {
+    let mut patch = b"PATCH".to_vec();
+    // Two bytes at 0x0001
+    patch.extend_from_slice(&[0x00, 0x00, 0x01, 0x00, 0x02, 0xAA, 0xBB]);
+    // Four times 0xCC at 0x0006, past the end of the source
+    patch.extend_from_slice(&[0x00, 0x00, 0x06, 0x00, 0x00, 0x00, 0x04, 0xCC]);
+    patch.extend_from_slice(b"EOF");
+
+    let target = patch::apply_ips(&[0; 4], &patch).unwrap();
+
+    assert_eq!(target, vec![0, 0xAA, 0xBB, 0, 0, 0, 0xCC, 0xCC, 0xCC, 0xCC]);
+}
+
+#[test]
+fn test_patch_ips_truncated() {
+    let mut patch = b"PATCH".to_vec();
+    patch.extend_from_slice(&[0x00, 0x00, 0x01, 0x00, 0x02, 0xAA]);
+
+    assert!(matches!(
+        patch::apply_ips(&[0; 4], &patch),
+        Err(LMVC8Error::InvalidPatch(_))
+    ));
+}
+
+#[test]
+fn test_patch_ips_past_rom() {
+    let mut patch = b"PATCH".to_vec();
+    // A single 0xFF at the largest offset IPS can address
+    patch.extend_from_slice(&[0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00, 0x01, 0xFF]);
+    patch.extend_from_slice(b"EOF");
+
+    assert!(matches!(
+        patch::apply_ips(&[], &patch),
+        Err(LMVC8Error::ROMSizeExceeded)
+    ));
+}
+
+fn bps_patch(source: &[u8], target: &[u8], actions: &[u8]) -> Vec<u8> {
+    let mut patch = b"BPS1".to_vec();
+    patch.extend_from_slice(&[0x80 | source.len() as u8, 0x80 | target.len() as u8, 0x80]);
+    patch.extend_from_slice(actions);
+    patch.extend_from_slice(&crc32(source).to_le_bytes());
+    patch.extend_from_slice(&crc32(target).to_le_bytes());
+    patch.extend_from_slice(&crc32(&patch).to_le_bytes());
+    patch
+}
+
+#[test]
+fn test_patch_bps_copies() {
+    // Source copy of "D", source copy two bytes back of "C", then an overlapping target copy
+    let patch = bps_patch(b"ABCD", b"DCDCDC", &[0x82, 0x86, 0x82, 0x85, 0x8F, 0x80]);
+
+    assert_eq!(patch::apply_bps(b"ABCD", &patch).unwrap(), b"DCDCDC");
+}
+
+/// BPS variable-length number
+fn bps_number(mut value: usize) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            bytes.push(byte | 0x80);
+            return bytes;
+        }
+        bytes.push(byte);
+        value -= 1;
+    }
+}
+
+#[rstest]
+#[case::source(0x82, "source copy out of bounds")]
+#[case::target(0x83, "target copy out of bounds")]
+fn test_patch_bps_offset_overflow(#[case] copy: u8, #[case] reason: &str) {
+    // Source copy and target copy of "A" move both offsets to 1, then move one by isize::MAX
+    let mut actions = vec![0x82, 0x80, 0x83, 0x80, copy];
+    actions.extend(bps_number((isize::MAX as usize) << 1));
+    let patch = bps_patch(b"ABCD", b"AAA", &actions);
+
+    assert!(matches!(
+        patch::apply_bps(b"ABCD", &patch),
+        Err(LMVC8Error::InvalidPatch(err)) if err == reason
+    ));
+}
+
+#[test]
+fn test_patch_bps_source_checksum() {
+    let patch = patch::create_bps(b"ABCD", b"ABCE");
+
+    assert!(matches!(
+        patch::apply_bps(b"ABCF", &patch),
+        Err(LMVC8Error::PatchChecksumMismatch {
+            checksum: "source",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_patch_bps_patch_checksum() {
+    let mut patch = patch::create_bps(b"ABCD", b"ABCE");
+    patch[8] ^= 0xFF;
+
+    assert!(matches!(
+        patch::apply_bps(b"ABCD", &patch),
+        Err(LMVC8Error::PatchChecksumMismatch {
+            checksum: "patch",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_patch_cartridge() {
+    let mut cartridge = Cartridge::new(vec![0x68, 0x01, 0x10]);
+    let patch = cartridge
+        .create_patch(PatchFormat::Bps, &[0x68, 0x02, 0x10])
+        .unwrap();
+
+    cartridge.apply_patch(&patch).unwrap();
+
+    assert_eq!(cartridge.binary, vec![0x68, 0x02, 0x10]);
+    assert!(matches!(
+        cartridge.apply_patch(b"NOTAPATCH"),
+        Err(LMVC8Error::InvalidPatch(_))
+    ));
+}
+
+#[test]
+fn test_patch_cartridge_rom_size() {
+    let mut cartridge = Cartridge::new(vec![]);
+    let patch = patch::create_ips(&[], &[1; 0x8001]).unwrap();
+
+    assert!(matches!(
+        cartridge.apply_patch(&patch),
+        Err(LMVC8Error::ROMSizeExceeded)
+    ));
+}
@@ -0,0 +1,76 @@
+use crate::console::components::bus::{Bus, MemoryMapped};
+use crate::console::components::cpu::registers::{R16, R8};
+use crate::console::components::rng::RNG;
+use crate::console::types::byte::Byte;
+use crate::console::Console;
+use rstest::rstest;
+
+const OP_HALT: u8 = 0x10;
+const OP_LDR8_A_HL: u8 = 0x26;
+const OP_LDR8_B_HL: u8 = 0x2D;
+
+fn sequence(rng: &mut RNG, length: usize) -> Vec<u8> {
+    (0..length)
+        .map(|_| rng.read(0x0000.into()).into())
+        .collect()
+}
+
+#[rstest]
+#[case(RNG::DEFAULT_SEED)]
+#[case(0x0001)]
+#[case(0xFFFF)]
+fn test_rng_deterministic(#[case] seed: u16) {
+    let first = sequence(&mut RNG::new(seed), 64);
+    let second = sequence(&mut RNG::new(seed), 64);
+
+    assert_eq!(first, second);
+    assert_ne!(first, sequence(&mut RNG::new(seed ^ 0x5555), 64));
+}
+
+#[test]
+fn test_rng_zero_seed() {
+    let mut rng = RNG::new(0);
+
+    assert_eq!(rng.seed(), 0);
+    assert_eq!(sequence(&mut rng, 16), sequence(&mut RNG::default(), 16));
+}
+
+#[test]
+fn test_rng_seed_registers() {
+    let mut bus = Bus::default();
+    bus.write(Bus::RNG_SEED_LOW.into(), Byte::new(0x34));
+    bus.write(Bus::RNG_SEED_HIGH.into(), Byte::new(0x12));
+
+    assert_eq!(bus.rng.seed(), 0x1234);
+    assert_eq!(bus.read(Bus::RNG_SEED_LOW.into()), Byte::new(0x34));
+    assert_eq!(bus.read(Bus::RNG_SEED_HIGH.into()), Byte::new(0x12));
+
+    let first = bus.read(Bus::RNG_VALUE.into());
+    bus.reset();
+
+    assert_eq!(bus.rng.seed(), 0x1234);
+    assert_eq!(bus.read(Bus::RNG_VALUE.into()), first);
+}
+
+#[test]
+fn test_rng_program() {
+    let mut expected = RNG::default();
+    let mut console = Console::builder()
+        .r16(R16::HL, Bus::RNG_VALUE)
+        .rom(OP_LDR8_A_HL)
+        .rom(OP_LDR8_B_HL)
+        .rom(OP_HALT)
+        .build();
+
+    console.step_till_halt();
+
+    let registers = console.cpu.get_registers();
+    assert_eq!(
+        registers.get_r8(&mut console.bus, R8::A),
+        expected.next_byte()
+    );
+    assert_eq!(
+        registers.get_r8(&mut console.bus, R8::B),
+        expected.next_byte()
+    );
+}
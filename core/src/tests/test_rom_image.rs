@@ -0,0 +1,122 @@
+use crate::console::cartridge::rom_image::RomImageFormat;
+use crate::console::cartridge::Cartridge;
+use crate::error::LMVC8Error;
+use rstest::rstest;
+
+/// Code at the start, a handler at 0x0090 and zero padding up to 0x0200
+fn sparse_cartridge() -> Cartridge {
+    let mut binary = vec![0; 0x200];
+    binary[..4].copy_from_slice(&[0x68, 0x01, 0x02, 0x10]);
+    binary[0x90..0x93].copy_from_slice(&[0x01, 0x12, 0x10]);
+    Cartridge::new(binary)
+        .with_title("Sparse")
+        .with_entry_point(0x0000)
+}
+
+#[rstest]
+#[case(RomImageFormat::IntelHex, Some(0x0090), Some(0x0090))]
+#[case(RomImageFormat::IntelHex, None, None)]
+#[case(RomImageFormat::SRec, Some(0x0090), Some(0x0090))]
+#[case(RomImageFormat::SRec, None, Some(0x0000))]
+fn test_rom_image_round_trip(
+    #[case] format: RomImageFormat,
+    #[case] entry_point: Option<u16>,
+    #[case] expected_entry_point: Option<u16>,
+) {
+    let mut cartridge = sparse_cartridge();
+    cartridge.entry_point = entry_point;
+
+    let text = cartridge.to_rom_image(format);
+    let imported = Cartridge::from_rom_image(format, &text).unwrap();
+
+    assert_eq!(imported.binary, cartridge.binary);
+    assert_eq!(imported.entry_point, expected_entry_point);
+    // Two data records and one for the last padding byte
+    let data_records = text
+        .lines()
+        .filter(|line| line.starts_with("S1") || line.get(7..9) == Some("00"))
+        .count();
+    assert_eq!(data_records, 3);
+}
+
+#[rstest]
+#[case(RomImageFormat::IntelHex, "hex")]
+#[case(RomImageFormat::SRec, "srec")]
+fn test_rom_image_lmc_round_trip(#[case] format: RomImageFormat, #[case] extension: &str) {
+    let directory = std::env::temp_dir();
+    let image_path = directory.join(format!("lmvc8_test_rom_image.{extension}"));
+    let lmc_path = directory.join(format!("lmvc8_test_rom_image_{extension}.lmc"));
+
+    sparse_cartridge()
+        .dump_rom_image_file(&image_path, format)
+        .unwrap();
+    Cartridge::load_rom_image_file(&image_path, format)
+        .unwrap()
+        .dump_to_file(&lmc_path)
+        .unwrap();
+    let loaded = Cartridge::load_from_file(&lmc_path).unwrap();
+    std::fs::remove_file(image_path).ok();
+    std::fs::remove_file(lmc_path).ok();
+
+    assert_eq!(loaded.binary, sparse_cartridge().binary);
+    assert_eq!(loaded.entry_point, Some(0x0000));
+    assert_eq!(RomImageFormat::from_extension(extension), Some(format));
+}
+
+#[test]
+fn test_rom_image_intel_hex_records() {
+    let text = ":0300300002337A1E\n:020000040000FA\n:040000050000009067\n:00000001FF\n";
+
+    let cartridge = Cartridge::from_rom_image(RomImageFormat::IntelHex, text).unwrap();
+
+    assert_eq!(cartridge.binary.len(), 0x33);
+    assert_eq!(&cartridge.binary[0x30..], &[0x02, 0x33, 0x7A]);
+    assert_eq!(cartridge.entry_point, Some(0x0090));
+}
+
+#[test]
+fn test_rom_image_srec_records() {
+    let text = "S00600004844521B\nS107001001021010C5\nS5030001FB\nS9030010EC\n";
+
+    let cartridge = Cartridge::from_rom_image(RomImageFormat::SRec, text).unwrap();
+
+    assert_eq!(cartridge.title, "HDR");
+    assert_eq!(&cartridge.binary[0x10..], &[0x01, 0x02, 0x10, 0x10]);
+    assert_eq!(cartridge.entry_point, Some(0x0010));
+}
+
+#[rstest]
+#[case(RomImageFormat::IntelHex, ":0300300002337A1F\n:00000001FF\n", 1)]
+#[case(RomImageFormat::SRec, "S107001001021010C6\nS9030010EC\n", 1)]
+fn test_rom_image_checksum(
+    #[case] format: RomImageFormat,
+    #[case] text: &str,
+    #[case] line: usize,
+) {
+    assert!(matches!(
+        Cartridge::from_rom_image(format, text),
+        Err(LMVC8Error::RomImageChecksumMismatch { line: l, .. }) if l == line
+    ));
+}
+
+#[rstest]
+#[case(RomImageFormat::IntelHex, ":0300300002337A1E\n")]
+#[case(RomImageFormat::IntelHex, ":0300300002337A\n:00000001FF\n")]
+#[case(RomImageFormat::SRec, "S107001001021010C5\nS5030002FA\nS9030010EC\n")]
+#[case(RomImageFormat::SRec, "X1070010010210104D\n")]
+fn test_rom_image_invalid(#[case] format: RomImageFormat, #[case] text: &str) {
+    assert!(matches!(
+        Cartridge::from_rom_image(format, text),
+        Err(LMVC8Error::InvalidRomImage { .. })
+    ));
+}
+
+#[test]
+fn test_rom_image_outside_rom() {
+    let text = ":020000040001F9\n:00000001FF\n";
+
+    assert!(matches!(
+        Cartridge::from_rom_image(RomImageFormat::IntelHex, text),
+        Err(LMVC8Error::ROMSizeExceeded)
+    ));
+}
@@ -0,0 +1,113 @@
+use crate::console::components::bus::Bus;
+use crate::console::components::cpu::interrupts::InterruptFlags;
+use crate::console::components::rtc::{ClockSource, RTCControl, RTCTime, RTC};
+use crate::console::types::byte::Byte;
+use crate::console::Console;
+use rstest::rstest;
+
+fn latch(bus: &mut Bus) -> (u8, u8, u8, u16) {
+    bus.write(Bus::RTC_CONTROL.into(), RTCControl::LATCH.bits().into());
+    let mut read = |addr: u16| u8::from(bus.read(addr.into()));
+    (
+        read(Bus::RTC_SECONDS),
+        read(Bus::RTC_MINUTES),
+        read(Bus::RTC_HOURS),
+        u16::from_le_bytes([read(Bus::RTC_DAY_LOW), read(Bus::RTC_DAY_HIGH)]),
+    )
+}
+
+#[rstest]
+#[case(0, RTCTime { seconds: 0, minutes: 0, hours: 0, day: 0 })]
+#[case(59, RTCTime { seconds: 59, minutes: 0, hours: 0, day: 0 })]
+#[case(3_723, RTCTime { seconds: 3, minutes: 2, hours: 1, day: 0 })]
+#[case(2 * 86_400 + 86_399, RTCTime { seconds: 59, minutes: 59, hours: 23, day: 2 })]
+fn test_rtc_time_from_seconds(#[case] seconds: u64, #[case] expected: RTCTime) {
+    assert_eq!(RTCTime::from_seconds(seconds), expected);
+}
+
+#[test]
+fn test_rtc_virtual_clock() {
+    let mut console = Console::new();
+    let cycles = console.step().cycles;
+    console.bus.rtc.set_cycles_per_second(cycles * 4);
+    console.bus.rtc.set_seconds(86_400 + 3_599);
+
+    for _ in 0..3 {
+        console.step();
+    }
+    assert_eq!(console.bus.rtc.time().seconds, 59);
+    console.step();
+
+    assert_eq!(latch(&mut console.bus), (0, 0, 1, 1));
+}
+
+#[test]
+fn test_rtc_latch() {
+    let mut bus = Bus::default();
+    bus.rtc.set_cycles_per_second(1);
+
+    assert_eq!(latch(&mut bus), (0, 0, 0, 0));
+    bus.rtc.tick(2);
+
+    assert_eq!(bus.read(Bus::RTC_SECONDS.into()), Byte::new(0));
+    assert!(bus.rtc.control.is_empty());
+    assert_eq!(latch(&mut bus).0, 2);
+}
+
+#[rstest]
+#[case(5, 10, true)]
+#[case(10, 10, true)]
+#[case(11, 10, false)]
+#[case(5, 86_400, true)]
+fn test_rtc_alarm(#[case] alarm_seconds: u8, #[case] elapsed: u64, #[case] fired: bool) {
+    let mut console = Console::new();
+    console.bus.rtc.set_cycles_per_second(1);
+    console
+        .bus
+        .write(Bus::RTC_ALARM_SECONDS.into(), alarm_seconds.into());
+    console.bus.write(
+        Bus::RTC_CONTROL.into(),
+        RTCControl::ALARM_ENABLE.bits().into(),
+    );
+
+    assert_eq!(console.bus.rtc.tick(elapsed), fired);
+    assert_eq!(console.bus.rtc.control.contains(RTCControl::ALARM), fired);
+}
+
+#[test]
+fn test_rtc_alarm_interrupt() {
+    let mut console = Console::new();
+    console.bus.rtc.alarm.seconds = 1;
+    console.bus.rtc.control = RTCControl::ALARM_ENABLE;
+    let cycles = console.clone().step().cycles;
+    console.bus.rtc.set_cycles_per_second(cycles * 2);
+
+    console.step();
+    assert!(!console.bus.ia.contains(InterruptFlags::RTC));
+    console.step();
+    assert!(console.bus.ia.contains(InterruptFlags::RTC));
+}
+
+#[test]
+fn test_rtc_host_clock() {
+    let mut rtc = RTC::default();
+    rtc.set_source(ClockSource::Host);
+
+    assert!(!rtc.tick(u64::MAX / 2));
+    // Any reasonable host clock is past 2020
+    assert!(rtc.time().day > 18_000);
+}
+
+#[test]
+fn test_rtc_host_clock_keeps_written_time() {
+    let mut rtc = RTC::default();
+    rtc.set_source(ClockSource::Host);
+    rtc.set_seconds(3_600);
+    rtc.set_cycles_per_second(1);
+    rtc.set_source(ClockSource::Host);
+    rtc.sync_host();
+
+    // The host clock may tick over while the test runs
+    assert!((3_600..3_602).contains(&rtc.time().second_of_day()));
+    assert_eq!(rtc.time().day, 0);
+}
@@ -0,0 +1,56 @@
+use crate::console::cartridge::Cartridge;
+use crate::console::components::bus::Bus;
+use crate::console::types::byte::Byte;
+use crate::console::Console;
+
+#[test]
+fn test_save_ram_survives_reset() {
+    let mut console = Console::new();
+    console
+        .load_cartridge(Cartridge::new(vec![]).with_save_ram(true))
+        .unwrap();
+
+    console.bus.write(Bus::SRAM_START.into(), Byte::new(0x42));
+    console.bus.write(Bus::RAM_START.into(), Byte::new(0x42));
+    assert!(console.bus.sram.as_ref().unwrap().is_dirty());
+
+    console.reset();
+
+    assert_eq!(console.bus.read(Bus::SRAM_START.into()), Byte::new(0x42));
+    assert_eq!(console.bus.read(Bus::RAM_START.into()), Byte::new(0x00));
+}
+
+#[test]
+fn test_save_ram_absent_without_cartridge_support() {
+    let mut console = Console::new();
+    console.load_cartridge(Cartridge::new(vec![])).unwrap();
+
+    console.bus.write(Bus::SRAM_START.into(), Byte::new(0x42));
+
+    assert!(console.bus.sram.is_none());
+    assert_eq!(console.bus.read(Bus::SRAM_START.into()), Byte::new(0x00));
+}
+
+#[test]
+fn test_save_path_next_to_cartridge() {
+    let mut cartridge = Cartridge::new(vec![]).with_save_ram(true);
+    assert_eq!(cartridge.save_path(), None);
+
+    cartridge.path = Some("games/demo.bin".into());
+    assert_eq!(cartridge.save_path(), Some("games/demo.sav".into()));
+}
+
+#[test]
+fn test_cartridge_file_has_save_ram() {
+    let path = std::env::temp_dir().join("lmvc8_test_save_ram.bin");
+    std::fs::write(&path, [0x10]).unwrap();
+
+    let cartridge = Cartridge::load_raw_from_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(cartridge.save_ram);
+    assert_eq!(
+        cartridge.save_path(),
+        Some(path.with_extension(Cartridge::SAVE_EXTENSION))
+    );
+}
@@ -0,0 +1,109 @@
+use crate::console::components::bus::Bus;
+use crate::console::components::cpu::interrupts::InterruptFlags;
+use crate::console::components::serial::{SerialControl, SERIAL_TRANSFER_CYCLES};
+use crate::console::link::{LinkedConsoles, LoopbackLink};
+use crate::console::types::byte::Byte;
+use crate::console::Console;
+
+fn start_transfer(console: &mut Console, data: u8, control: SerialControl) {
+    console.bus.write(Bus::SERIAL_DATA.into(), data.into());
+    console
+        .bus
+        .write(Bus::SERIAL_CONTROL.into(), control.bits().into());
+    console.bus.take_step_cycles();
+}
+
+#[test]
+fn test_serial_loopback() {
+    let mut console = Console::new();
+    start_transfer(
+        &mut console,
+        0x42,
+        SerialControl::BUSY | SerialControl::INTERNAL_CLOCK,
+    );
+
+    let mut cycles = 0;
+    while console.bus.serial.control.contains(SerialControl::BUSY) {
+        cycles += console.step_linked(&mut LoopbackLink).cycles;
+    }
+
+    assert!(cycles >= SERIAL_TRANSFER_CYCLES);
+    assert_eq!(console.bus.serial.data, Byte::new(0x42));
+    assert!(console.bus.ia.contains(InterruptFlags::SERIAL));
+}
+
+#[test]
+fn test_serial_disconnected() {
+    let mut console = Console::new();
+    start_transfer(
+        &mut console,
+        0x42,
+        SerialControl::BUSY | SerialControl::INTERNAL_CLOCK,
+    );
+
+    while console.bus.serial.control.contains(SerialControl::BUSY) {
+        console.step();
+    }
+
+    assert_eq!(console.bus.serial.data, Byte::new(0xFF));
+}
+
+#[test]
+fn test_serial_linked_consoles_exchange() {
+    let mut linked = LinkedConsoles::new(Console::new(), Console::new());
+    start_transfer(
+        &mut linked.first,
+        0x12,
+        SerialControl::BUSY | SerialControl::INTERNAL_CLOCK,
+    );
+    start_transfer(&mut linked.second, 0x34, SerialControl::BUSY);
+
+    while linked
+        .first
+        .bus
+        .serial
+        .control
+        .contains(SerialControl::BUSY)
+    {
+        linked.step();
+    }
+
+    assert_eq!(linked.first.bus.serial.data, Byte::new(0x34));
+    assert_eq!(linked.second.bus.serial.data, Byte::new(0x12));
+    assert!(linked.first.bus.ia.contains(InterruptFlags::SERIAL));
+    assert!(linked.second.bus.ia.contains(InterruptFlags::SERIAL));
+    assert!(!linked
+        .second
+        .bus
+        .serial
+        .control
+        .contains(SerialControl::BUSY));
+}
+
+#[test]
+fn test_serial_linked_console_not_listening() {
+    let mut linked = LinkedConsoles::new(Console::new(), Console::new());
+    start_transfer(
+        &mut linked.first,
+        0x12,
+        SerialControl::BUSY | SerialControl::INTERNAL_CLOCK,
+    );
+    linked
+        .second
+        .bus
+        .write(Bus::SERIAL_DATA.into(), Byte::new(0x34));
+
+    while linked
+        .first
+        .bus
+        .serial
+        .control
+        .contains(SerialControl::BUSY)
+    {
+        linked.step();
+    }
+
+    assert_eq!(linked.first.bus.serial.data, Byte::new(0xFF));
+    assert_eq!(linked.second.bus.serial.data, Byte::new(0x34));
+    assert!(!linked.second.bus.ia.contains(InterruptFlags::SERIAL));
+}
@@ -0,0 +1,77 @@
+use crate::console::components::bus::Bus;
+use crate::console::components::cpu::interrupts::InterruptFlags;
+use crate::console::components::cpu::registers::R16;
+use crate::console::components::watchdog::{WatchdogAction, WatchdogControl, WATCHDOG_TICK_CYCLES};
+use crate::console::types::byte::Byte;
+use crate::console::Console;
+
+const OP_LDR8_HL_A: u8 = 0x51;
+
+/// Runs until the watchdog expires, gives up after the specified number of steps
+fn run_till_watchdog(console: &mut Console, max_steps: usize) -> Option<(usize, WatchdogAction)> {
+    (0..max_steps).find_map(|steps| console.step().watchdog.map(|action| (steps, action)))
+}
+
+fn enable(console: &mut Console, timeout: u8, control: WatchdogControl) {
+    console
+        .bus
+        .write(Bus::WATCHDOG_TIMEOUT_LOW.into(), Byte::new(timeout));
+    console.bus.write(
+        Bus::WATCHDOG_CONTROL.into(),
+        (control | WatchdogControl::ENABLE).bits().into(),
+    );
+}
+
+#[test]
+fn test_watchdog_disabled() {
+    let mut console = Console::new();
+
+    assert_eq!(run_till_watchdog(&mut console, 10_000), None);
+}
+
+#[test]
+fn test_watchdog_reset() {
+    let mut console = Console::new();
+    enable(&mut console, 1, WatchdogControl::empty());
+
+    let (steps, action) = run_till_watchdog(&mut console, 10_000).unwrap();
+
+    assert_eq!(action, WatchdogAction::Reset);
+    assert!(steps as u64 <= WATCHDOG_TICK_CYCLES);
+    assert_eq!(u16::from(console.cpu.get_pc()), 0);
+    assert!(!console.bus.watchdog.is_enabled());
+    assert_eq!(console.bus.watchdog.control(), WatchdogControl::EXPIRED);
+}
+
+#[test]
+fn test_watchdog_trap() {
+    let mut console = Console::new();
+    enable(&mut console, 1, WatchdogControl::TRAP);
+
+    assert_eq!(
+        run_till_watchdog(&mut console, 10_000).map(|(_, action)| action),
+        Some(WatchdogAction::Trap)
+    );
+    assert!(console.bus.ia.contains(InterruptFlags::WATCHDOG));
+    assert!(console.bus.watchdog.is_enabled());
+    assert!(console
+        .bus
+        .watchdog
+        .control()
+        .contains(WatchdogControl::EXPIRED));
+}
+
+#[test]
+fn test_watchdog_kick() {
+    // Program which keeps kicking the watchdog forever
+    let mut console = Console::builder()
+        .r16(R16::HL, Bus::WATCHDOG_KICK)
+        .rom(OP_LDR8_HL_A)
+        .build();
+    for address in 1..=Bus::ROM_END {
+        console.bus.rom.data[address as usize] = OP_LDR8_HL_A;
+    }
+    enable(&mut console, 1, WatchdogControl::empty());
+
+    assert_eq!(run_till_watchdog(&mut console, 10_000), None);
+}